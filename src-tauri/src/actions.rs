@@ -1,6 +1,10 @@
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::live_segments;
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::intent_router::IntentRouterManager;
+use crate::managers::reranker::RerankerManager;
 use crate::managers::transcription::TranscriptionManager;
+use crate::managers::workspace_jargon::WorkspaceJargonManager;
 use crate::pipeline::TranscriptionPipeline;
 use crate::settings::get_settings;
 use crate::shortcut;
@@ -23,6 +27,8 @@ pub trait ShortcutAction: Send + Sync {
 // Transcribe Action
 struct TranscribeAction {
     post_process: bool,
+    /// Turn the dictation into a shell command instead of text.
+    cli_mode: bool,
 }
 
 impl ShortcutAction for TranscribeAction {
@@ -45,6 +51,23 @@ impl ShortcutAction for TranscribeAction {
         let is_always_on = settings.always_on_microphone;
         debug!("Microphone mode - always_on: {}", is_always_on);
 
+        // Pick up identifiers from the workspace while the user speaks
+        app.state::<Arc<WorkspaceJargonManager>>()
+            .refresh(&settings);
+        if settings.intent_router_enabled {
+            app.state::<Arc<IntentRouterManager>>().warm_up(&settings);
+        }
+        if settings.reranker_enabled {
+            app.state::<Arc<RerankerManager>>().warm_up(&settings);
+        }
+
+        // A CLI dictation is one command, so it is never pasted in pieces.
+        let segments = if self.cli_mode {
+            None
+        } else {
+            live_segments::begin(app, &settings, &binding_id)
+        };
+
         let mut recording_started = false;
         if is_always_on {
             // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
@@ -56,13 +79,13 @@ impl ShortcutAction for TranscribeAction {
                 rm_clone.apply_mute();
             });
 
-            recording_started = rm.try_start_recording(&binding_id);
+            recording_started = rm.try_start_recording(&binding_id, segments);
             debug!("Recording started: {}", recording_started);
         } else {
             // On-demand mode: Start recording first, then play audio feedback, then apply mute
             debug!("On-demand mode: Starting recording first, then audio feedback");
             let recording_start_time = Instant::now();
-            if rm.try_start_recording(&binding_id) {
+            if rm.try_start_recording(&binding_id, segments) {
                 recording_started = true;
                 debug!("Recording started in {:?}", recording_start_time.elapsed());
                 let app_clone = app.clone();
@@ -86,6 +109,7 @@ impl ShortcutAction for TranscribeAction {
             shortcut::register_cancel_shortcut(app);
         } else {
             info!("Recording did not start (binding={})", binding_id);
+            live_segments::cancel();
             // In toggle mode the handler flips state before calling start().
             // If start failed (e.g. another binding already owns recording),
             // force this binding back to inactive to avoid stale toggle state.
@@ -136,6 +160,7 @@ impl ShortcutAction for TranscribeAction {
 
         let binding_id = binding_id.to_string();
         let post_process = self.post_process;
+        let cli_mode = self.cli_mode;
 
         tauri::async_runtime::spawn(async move {
             debug!(
@@ -158,18 +183,21 @@ impl ShortcutAction for TranscribeAction {
 
                 let settings = get_settings(&ah);
 
-                // Segment-on-silence is disabled; there are no pre-pasted segments.
-                let pasted_segments = Vec::new();
+                // Segments pasted while recording; `samples` is what followed them.
+                let segments = live_segments::finish(&ah).await;
 
                 // Create and run the pipeline
                 let pipeline = TranscriptionPipeline::new(
                     samples,
-                    pasted_segments,
+                    segments.texts,
                     settings,
                     post_process,
+                    cli_mode,
                     binding_id,
                     ah,
-                );
+                )
+                .with_segment_audio(segments.samples)
+                .with_segment_snippets(segments.snippet_expansions);
                 pipeline.run().await;
             } else {
                 info!(
@@ -206,6 +234,36 @@ impl ShortcutAction for CancelAction {
     }
 }
 
+// Undo Last Dictation Action
+struct UndoLastDictationAction;
+
+impl ShortcutAction for UndoLastDictationAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        if let Err(e) = crate::last_dictation::undo(app) {
+            info!("Not undoing last dictation: {}", e);
+        }
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop for undo
+    }
+}
+
+// Template Next Placeholder Action
+struct TemplateNextPlaceholderAction;
+
+impl ShortcutAction for TemplateNextPlaceholderAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        if let Err(e) = crate::templates::jump_to_next_placeholder(app) {
+            info!("Not jumping to a template placeholder: {}", e);
+        }
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop for placeholder jumps
+    }
+}
+
 // Test Action
 struct TestAction;
 
@@ -236,16 +294,35 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe".to_string(),
         Arc::new(TranscribeAction {
             post_process: false,
+            cli_mode: false,
         }) as Arc<dyn ShortcutAction>,
     );
     map.insert(
         "transcribe_with_post_process".to_string(),
-        Arc::new(TranscribeAction { post_process: true }) as Arc<dyn ShortcutAction>,
+        Arc::new(TranscribeAction {
+            post_process: true,
+            cli_mode: false,
+        }) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "transcribe_cli".to_string(),
+        Arc::new(TranscribeAction {
+            post_process: false,
+            cli_mode: true,
+        }) as Arc<dyn ShortcutAction>,
     );
     map.insert(
         "cancel".to_string(),
         Arc::new(CancelAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "undo_last_dictation".to_string(),
+        Arc::new(UndoLastDictationAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "template_next_placeholder".to_string(),
+        Arc::new(TemplateNextPlaceholderAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
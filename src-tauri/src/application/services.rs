@@ -3,19 +3,29 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use tauri::{AppHandle, Manager};
 
+use crate::cleanup::CleanupManager;
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::domain_selector::DomainSelectorManager;
 use crate::managers::history::HistoryManager;
+use crate::managers::intent_router::IntentRouterManager;
+use crate::managers::jargon_sources::JargonSourceManager;
 use crate::managers::model::ModelManager;
+use crate::managers::reranker::RerankerManager;
 use crate::managers::transcription::TranscriptionManager;
+use crate::managers::workspace_jargon::WorkspaceJargonManager;
 
 #[derive(Clone)]
 pub struct AppServices {
     pub recording_manager: Arc<AudioRecordingManager>,
     pub domain_selector_manager: Arc<DomainSelectorManager>,
+    pub intent_router_manager: Arc<IntentRouterManager>,
+    pub reranker_manager: Arc<RerankerManager>,
+    pub jargon_source_manager: Arc<JargonSourceManager>,
+    pub workspace_jargon_manager: Arc<WorkspaceJargonManager>,
     pub model_manager: Arc<ModelManager>,
     pub transcription_manager: Arc<TranscriptionManager>,
     pub history_manager: Arc<HistoryManager>,
+    pub cleanup_manager: Arc<CleanupManager>,
 }
 
 impl AppServices {
@@ -26,27 +36,51 @@ impl AppServices {
         let model_manager =
             Arc::new(ModelManager::new(app_handle).context("initialize model manager")?);
         let domain_selector_manager = Arc::new(DomainSelectorManager::new());
+        let intent_router_manager = Arc::new(IntentRouterManager::new());
+        let reranker_manager = Arc::new(RerankerManager::new());
+        let jargon_source_manager = Arc::new(JargonSourceManager::new(app_handle));
+        let workspace_jargon_manager = Arc::new(WorkspaceJargonManager::new());
         let transcription_manager = Arc::new(
             TranscriptionManager::new(app_handle, model_manager.clone())
                 .context("initialize transcription manager")?,
         );
         let history_manager =
             Arc::new(HistoryManager::new(app_handle).context("initialize history manager")?);
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .context("resolve app data dir for cleanup manager")?;
+        let cleanup_manager = Arc::new(CleanupManager::new(
+            app_data_dir,
+            crate::settings::get_settings(app_handle)
+                .local_cleanup_model_dir
+                .map(std::path::PathBuf::from),
+        ));
 
         Ok(Self {
             recording_manager,
             domain_selector_manager,
+            intent_router_manager,
+            reranker_manager,
+            jargon_source_manager,
+            workspace_jargon_manager,
             model_manager,
             transcription_manager,
             history_manager,
+            cleanup_manager,
         })
     }
 
     pub fn register(self, app_handle: &AppHandle) {
         app_handle.manage(self.recording_manager);
         app_handle.manage(self.domain_selector_manager);
+        app_handle.manage(self.intent_router_manager);
+        app_handle.manage(self.reranker_manager);
+        app_handle.manage(self.jargon_source_manager);
+        app_handle.manage(self.workspace_jargon_manager);
         app_handle.manage(self.model_manager);
         app_handle.manage(self.transcription_manager);
         app_handle.manage(self.history_manager);
+        app_handle.manage(self.cleanup_manager);
     }
 }
@@ -401,7 +401,7 @@ static TRAILING_AFTER_EXTENSION_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)^(.+?\.[a-z0-9]{1,10})(?:\s+.*)?$").unwrap());
 
 /// Walk the workspace directory, skipping common non-source directories.
-fn walk_workspace(root: &Path) -> Vec<PathBuf> {
+pub(crate) fn walk_workspace(root: &Path) -> Vec<PathBuf> {
     let mut entries = Vec::new();
 
     let walker = WalkDir::new(root)
@@ -624,7 +624,7 @@ pub fn maybe_expand_at_refs(
     expanded
 }
 
-fn is_git_repository(start: &Path) -> bool {
+pub(crate) fn is_git_repository(start: &Path) -> bool {
     let mut current = Some(start);
     while let Some(path) = current {
         let git_path = path.join(".git");
@@ -16,8 +16,20 @@ use crate::audio_toolkit::{
     VoiceActivityDetector,
 };
 
+/// Length of the frames the consumer hands to the VAD.
+const FRAME_DURATION: Duration = Duration::from_millis(30);
+/// Shortest speech sent as a segment; shorter stretches wait for more.
+const MIN_SEGMENT_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize;
+
+/// Where a recording sends the speech it cuts off at pauses.
+pub struct SegmentSink {
+    /// Trailing silence, after the VAD's hangover, that ends a segment.
+    pub silence: Duration,
+    pub tx: mpsc::Sender<Vec<f32>>,
+}
+
 enum Cmd {
-    Start,
+    Start(Option<SegmentSink>),
     Stop(mpsc::Sender<Vec<f32>>),
     Shutdown,
 }
@@ -54,12 +66,7 @@ impl AudioRecorder {
         self
     }
 
-    pub fn open(
-        &mut self,
-        device: Option<Device>,
-        segment_tx: Option<mpsc::Sender<Vec<f32>>>,
-        segment_size_samples: Option<usize>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
         }
@@ -122,15 +129,7 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(
-                sample_rate,
-                vad,
-                sample_rx,
-                cmd_rx,
-                level_cb,
-                segment_tx,
-                segment_size_samples,
-            );
+            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
             // stream is dropped here, after run_consumer returns
         });
 
@@ -142,8 +141,18 @@ impl AudioRecorder {
     }
 
     pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.start_with_segments(None)
+    }
+
+    /// Start recording and, with a `sink`, send each stretch of speech that
+    /// ends in a long enough pause to it while recording continues. The
+    /// samples returned by [`Self::stop`] are what came after the last one.
+    pub fn start_with_segments(
+        &self,
+        sink: Option<SegmentSink>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = &self.cmd_tx {
-            tx.send(Cmd::Start)?;
+            tx.send(Cmd::Start(sink))?;
         }
         Ok(())
     }
@@ -252,23 +261,56 @@ impl AudioRecorder {
     }
 }
 
+/// Cuts recorded speech into segments at pauses.
+struct Segmenter {
+    sink: SegmentSink,
+    silence_frames: usize,
+    silent_frames: usize,
+}
+
+impl Segmenter {
+    fn new(sink: SegmentSink) -> Self {
+        let silence_frames = (sink.silence.as_millis() as usize)
+            .div_ceil(FRAME_DURATION.as_millis() as usize)
+            .max(1);
+        Self {
+            sink,
+            silence_frames,
+            silent_frames: 0,
+        }
+    }
+
+    /// Called after every frame: once `speech` has been followed by enough
+    /// silence, send it as a segment and start over.
+    fn after_frame(&mut self, was_speech: bool, speech: &mut Vec<f32>) {
+        if was_speech {
+            self.silent_frames = 0;
+            return;
+        }
+        self.silent_frames += 1;
+        if self.silent_frames >= self.silence_frames && speech.len() >= MIN_SEGMENT_SAMPLES {
+            let _ = self.sink.tx.send(std::mem::take(speech));
+            self.silent_frames = 0;
+        }
+    }
+}
+
 fn run_consumer(
     in_sample_rate: u32,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
-    segment_tx: Option<mpsc::Sender<Vec<f32>>>,
-    segment_size_samples: Option<usize>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
         constants::WHISPER_SAMPLE_RATE as usize,
-        Duration::from_millis(30),
+        FRAME_DURATION,
     );
 
     let mut processed_samples = Vec::<f32>::new();
     let mut recording = false;
+    let mut segmenter: Option<Segmenter> = None;
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
@@ -286,30 +328,28 @@ fn run_consumer(
         recording: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
-        segment_tx: &Option<mpsc::Sender<Vec<f32>>>,
-        segment_size_samples: Option<usize>,
+        segmenter: &mut Option<Segmenter>,
     ) {
         if !recording {
             return;
         }
 
-        if let Some(vad_arc) = vad {
+        let was_speech = if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
             match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => out_buf.extend_from_slice(buf),
-                VadFrame::Noise => {}
+                VadFrame::Speech(buf) => {
+                    out_buf.extend_from_slice(buf);
+                    true
+                }
+                VadFrame::Noise => false,
             }
         } else {
             out_buf.extend_from_slice(samples);
-        }
+            true
+        };
 
-        if let (Some(tx), Some(target)) = (segment_tx, segment_size_samples) {
-            if target > 0 {
-                while out_buf.len() >= target {
-                    let segment: Vec<f32> = out_buf.drain(..target).collect();
-                    let _ = tx.send(segment);
-                }
-            }
+        if let Some(segmenter) = segmenter {
+            segmenter.after_frame(was_speech, out_buf);
         }
     }
 
@@ -333,17 +373,17 @@ fn run_consumer(
                 recording,
                 &vad,
                 &mut processed_samples,
-                &segment_tx,
-                segment_size_samples,
+                &mut segmenter,
             )
         });
 
         // non-blocking check for a command
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
-                Cmd::Start => {
+                Cmd::Start(sink) => {
                     processed_samples.clear();
                     recording = true;
+                    segmenter = sink.map(Segmenter::new);
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
                         v.lock().unwrap().reset();
@@ -354,15 +394,11 @@ fn run_consumer(
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
                         // we still want to process the last few frames
-                        handle_frame(
-                            frame,
-                            true,
-                            &vad,
-                            &mut processed_samples,
-                            &segment_tx,
-                            segment_size_samples,
-                        )
+                        handle_frame(frame, true, &vad, &mut processed_samples, &mut segmenter)
                     });
+                    // Closes the segment channel, so its reader sees the
+                    // recording is over.
+                    segmenter = None;
 
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
                 }
@@ -371,3 +407,47 @@ fn run_consumer(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segmenter(silence_ms: u64) -> (Segmenter, mpsc::Receiver<Vec<f32>>) {
+        let (tx, rx) = mpsc::channel();
+        let sink = SegmentSink {
+            silence: Duration::from_millis(silence_ms),
+            tx,
+        };
+        (Segmenter::new(sink), rx)
+    }
+
+    #[test]
+    fn segment_is_sent_after_trailing_silence() {
+        let (mut segmenter, rx) = segmenter(90);
+        let mut speech = vec![0.1; MIN_SEGMENT_SAMPLES];
+        segmenter.after_frame(true, &mut speech);
+        segmenter.after_frame(false, &mut speech);
+        segmenter.after_frame(false, &mut speech);
+        assert!(rx.try_recv().is_err());
+
+        segmenter.after_frame(false, &mut speech);
+        assert_eq!(rx.try_recv().unwrap().len(), MIN_SEGMENT_SAMPLES);
+        assert!(speech.is_empty());
+    }
+
+    #[test]
+    fn speech_resets_the_pause_and_short_speech_waits() {
+        let (mut segmenter, rx) = segmenter(60);
+        let mut speech = vec![0.1; MIN_SEGMENT_SAMPLES / 2];
+        segmenter.after_frame(false, &mut speech);
+        segmenter.after_frame(false, &mut speech);
+        assert!(rx.try_recv().is_err(), "too short to send");
+
+        speech.extend(vec![0.1; MIN_SEGMENT_SAMPLES]);
+        segmenter.after_frame(true, &mut speech);
+        segmenter.after_frame(false, &mut speech);
+        assert!(rx.try_recv().is_err(), "pause restarted by speech");
+        segmenter.after_frame(false, &mut speech);
+        assert!(rx.try_recv().is_ok());
+    }
+}
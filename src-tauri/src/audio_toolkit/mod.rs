@@ -6,6 +6,7 @@ pub mod vad;
 
 pub use audio::{
     list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    SegmentSink,
 };
 pub use text::{apply_custom_words, clean_segment_boundaries, filter_transcription_output};
 pub use utils::get_cpal_host;
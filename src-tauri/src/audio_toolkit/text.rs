@@ -7,7 +7,7 @@ use strsim::levenshtein;
 ///
 /// Strips punctuation from each word, lowercases, and joins without spaces.
 /// This allows matching "Charge B" against "ChargeBee".
-fn build_ngram(words: &[&str]) -> String {
+pub(crate) fn build_ngram(words: &[&str]) -> String {
     words
         .iter()
         .map(|w| {
@@ -18,6 +18,27 @@ fn build_ngram(words: &[&str]) -> String {
         .concat()
 }
 
+/// Byte ranges of the whitespace-separated words of `text`, so words can be
+/// replaced without touching the whitespace between them.
+pub(crate) fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
 /// Finds the best matching custom word for a candidate string
 ///
 /// Uses Levenshtein distance and Soundex phonetic matching to find
@@ -31,7 +52,7 @@ fn build_ngram(words: &[&str]) -> String {
 ///
 /// # Returns
 /// The best matching custom word and its score, if any match was found
-fn find_best_match<'a>(
+pub(crate) fn find_best_match<'a>(
     candidate: &str,
     custom_words: &'a [String],
     custom_words_nospace: &[String],
@@ -371,6 +392,18 @@ fn is_hallucination(text: &str) -> bool {
 /// # Returns
 /// The filtered text with filler words and stutters removed
 pub fn filter_transcription_output(text: &str) -> String {
+    let filtered = remove_filler_words(text);
+
+    // Discard entire output if it is a known hallucination phrase
+    if is_hallucination(&filtered) {
+        return String::new();
+    }
+
+    filtered
+}
+
+/// Removes filler words and stutters, then normalizes whitespace.
+pub(crate) fn remove_filler_words(text: &str) -> String {
     let mut filtered = text.to_string();
 
     // Remove filler words
@@ -385,14 +418,7 @@ pub fn filter_transcription_output(text: &str) -> String {
     filtered = MULTI_SPACE_PATTERN.replace_all(&filtered, " ").to_string();
 
     // Trim leading/trailing whitespace
-    filtered = filtered.trim().to_string();
-
-    // Discard entire output if it is a known hallucination phrase
-    if is_hallucination(&filtered) {
-        return String::new();
-    }
-
-    filtered
+    filtered.trim().to_string()
 }
 
 #[cfg(test)]
@@ -3,7 +3,8 @@ use log::{debug, info, warn};
 use ort::session::Session;
 use ort::value::Tensor;
 use regex::Regex;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tokenizers::Tokenizer;
 
@@ -12,8 +13,8 @@ use tokenizers::Tokenizer;
 // ============================================================================
 
 /// Trait for text cleanup providers. Implementations can be local (T5 ONNX)
-/// or remote (API-based). The pipeline in actions.rs uses this trait so that
-/// any provider can be swapped in.
+/// or remote (API-based). The post-processing step in pipeline.rs uses this
+/// trait so that any provider can be swapped in.
 #[async_trait::async_trait]
 pub trait TextCleanupProvider: Send + Sync {
     /// Clean the given text. Implementations should fall back to returning the
@@ -87,96 +88,119 @@ struct CleanupSession {
 // CleanupManager — local T5 ONNX provider
 // ============================================================================
 
+/// Files a flan-t5 ONNX export must contain to be loaded.
+pub const CLEANUP_MODEL_FILES: &[&str] =
+    &["encoder_model.onnx", "decoder_model.onnx", "tokenizer.json"];
+
+/// Return the required model files that are missing from `dir`.
+pub fn missing_model_files(dir: &Path) -> Vec<&'static str> {
+    CLEANUP_MODEL_FILES
+        .iter()
+        .copied()
+        .filter(|file| !dir.join(file).is_file())
+        .collect()
+}
+
 pub struct CleanupManager {
     session: Arc<Mutex<Option<CleanupSession>>>,
-    model_dir: PathBuf,
+    /// Where downloaded model files are stored.
+    download_dir: PathBuf,
+    /// Set while a background download is running.
+    downloading: Arc<AtomicBool>,
+    /// User-supplied model directory. When set, nothing is downloaded and the
+    /// model is loaded from here, so cleanup works fully offline.
+    local_model_dir: Mutex<Option<PathBuf>>,
 }
 
 impl CleanupManager {
-    pub fn new(app_data_dir: PathBuf) -> Self {
-        let model_dir = app_data_dir.join("models").join("flan-t5-small-onnx");
+    pub fn new(app_data_dir: PathBuf, local_model_dir: Option<PathBuf>) -> Self {
+        let download_dir = app_data_dir.join("models").join("flan-t5-small-onnx");
         Self {
             session: Arc::new(Mutex::new(None)),
-            model_dir,
+            download_dir,
+            downloading: Arc::new(AtomicBool::new(false)),
+            local_model_dir: Mutex::new(local_model_dir),
         }
     }
 
-    /// Download model files if they don't exist yet.
-    async fn ensure_models_downloaded(&self) -> Result<()> {
-        std::fs::create_dir_all(&self.model_dir)?;
-
-        let base_url = "https://huggingface.co/nickmuchi/flan-t5-small-onnx/resolve/main";
-        let files = [
-            (
-                "encoder_model.onnx",
-                format!("{}/encoder_model.onnx", base_url),
-            ),
-            (
-                "decoder_model.onnx",
-                format!("{}/decoder_model.onnx", base_url),
-            ),
-            ("tokenizer.json", format!("{}/tokenizer.json", base_url)),
-        ];
-
-        let client = reqwest::Client::new();
-        for (filename, url) in &files {
-            let path = self.model_dir.join(filename);
-            if path.exists() {
-                debug!("Cleanup model file already exists: {}", filename);
-                continue;
-            }
+    /// Switch between a local model directory and the downloaded model.
+    /// A loaded session is dropped so the next cleanup loads from the new place.
+    pub fn set_local_model_dir(&self, dir: Option<PathBuf>) {
+        let mut local = self.local_model_dir.lock().unwrap();
+        if *local == dir {
+            return;
+        }
+        info!("Cleanup model directory changed to {:?}", dir);
+        *local = dir;
+        if let Ok(mut session) = self.session.lock() {
+            *session = None;
+        }
+    }
 
-            info!("Downloading cleanup model file: {} from {}", filename, url);
-            let response = client
-                .get(url)
-                .send()
-                .await
-                .with_context(|| format!("Failed to download {}", filename))?;
+    fn local_model_dir(&self) -> Option<PathBuf> {
+        self.local_model_dir.lock().unwrap().clone()
+    }
 
-            if !response.status().is_success() {
+    /// The directory to load the model from, once all its files are there.
+    /// Never downloads: a missing downloaded model is fetched in the
+    /// background and this attempt fails, so a dictation never waits on it.
+    fn ready_model_dir(&self) -> Result<PathBuf> {
+        if let Some(dir) = self.local_model_dir() {
+            let missing = missing_model_files(&dir);
+            if !missing.is_empty() {
                 anyhow::bail!(
-                    "Failed to download {}: HTTP {}",
-                    filename,
-                    response.status()
+                    "Cleanup model directory {:?} is missing: {}",
+                    dir,
+                    missing.join(", ")
                 );
             }
-
-            let bytes = response
-                .bytes()
-                .await
-                .with_context(|| format!("Failed to read bytes for {}", filename))?;
-
-            let tmp_path = path.with_extension("tmp");
-            std::fs::write(&tmp_path, &bytes)
-                .with_context(|| format!("Failed to write {}", filename))?;
-            std::fs::rename(&tmp_path, &path)
-                .with_context(|| format!("Failed to rename {}", filename))?;
-            info!(
-                "Downloaded cleanup model file: {} ({} bytes)",
-                filename,
-                bytes.len()
-            );
+            return Ok(dir);
         }
+        if missing_model_files(&self.download_dir).is_empty() {
+            return Ok(self.download_dir.clone());
+        }
+        self.prefetch();
+        anyhow::bail!("Cleanup model is not downloaded yet; downloading in the background")
+    }
 
-        Ok(())
+    /// Start downloading the model in the background unless a local model
+    /// directory is set, the files are already there or a download is running.
+    pub fn prefetch(&self) {
+        if self.local_model_dir().is_some()
+            || missing_model_files(&self.download_dir).is_empty()
+            || self.downloading.swap(true, Ordering::AcqRel)
+        {
+            return;
+        }
+        let download_dir = self.download_dir.clone();
+        let downloading = Arc::clone(&self.downloading);
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = download_models(&download_dir).await {
+                warn!("Failed to download cleanup model: {:#}", e);
+            }
+            downloading.store(false, Ordering::Release);
+        });
     }
 
-    /// Load the ONNX sessions + tokenizer if not already loaded.
-    fn ensure_session_loaded(&self) -> Result<()> {
-        let mut guard = self.session.lock().map_err(|e| anyhow::anyhow!("{}", e))?;
-        if guard.is_some() {
-            return Ok(());
+    /// Load the ONNX sessions + tokenizer from `model_dir` into `slot` if
+    /// not already loaded.
+    fn ensure_session_loaded<'a>(
+        slot: &'a mut Option<CleanupSession>,
+        model_dir: &Path,
+    ) -> Result<&'a mut CleanupSession> {
+        if slot.is_some() {
+            return Ok(slot.as_mut().unwrap());
         }
 
-        let encoder_path = self.model_dir.join("encoder_model.onnx");
-        let decoder_path = self.model_dir.join("decoder_model.onnx");
-        let tokenizer_path = self.model_dir.join("tokenizer.json");
+        let encoder_path = model_dir.join("encoder_model.onnx");
+        let decoder_path = model_dir.join("decoder_model.onnx");
+        let tokenizer_path = model_dir.join("tokenizer.json");
 
-        if !encoder_path.exists() || !decoder_path.exists() || !tokenizer_path.exists() {
-            anyhow::bail!("Cleanup model files not found. Download required.");
+        if !missing_model_files(&model_dir).is_empty() {
+            anyhow::bail!("Cleanup model files not found in {:?}.", model_dir);
         }
 
-        info!("Loading cleanup T5 sessions...");
+        info!("Loading cleanup T5 sessions from {:?}...", model_dir);
         let encoder = Session::builder()
             .with_context(|| "Failed to create encoder session builder")?
             .commit_from_file(&encoder_path)
@@ -191,22 +215,15 @@ impl CleanupManager {
             .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
 
         info!("Cleanup T5 sessions loaded successfully");
-        *guard = Some(CleanupSession {
+        Ok(slot.insert(CleanupSession {
             encoder,
             decoder,
             tokenizer,
-        });
-
-        Ok(())
+        }))
     }
 
     /// Run T5 inference on a single text segment.
-    fn run_t5(&self, input_text: &str) -> Result<String> {
-        let mut guard = self.session.lock().map_err(|e| anyhow::anyhow!("{}", e))?;
-        let session = guard
-            .as_mut()
-            .ok_or_else(|| anyhow::anyhow!("Session not loaded"))?;
-
+    fn run_t5(session: &mut CleanupSession, input_text: &str) -> Result<String> {
         // Tokenize input
         let encoding = session
             .tokenizer
@@ -325,6 +342,58 @@ impl CleanupManager {
     }
 }
 
+/// Download the model files that are not in `download_dir` yet.
+async fn download_models(download_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(download_dir)?;
+
+    let base_url = "https://huggingface.co/nickmuchi/flan-t5-small-onnx/resolve/main";
+    let files = CLEANUP_MODEL_FILES
+        .iter()
+        .map(|filename| (*filename, format!("{}/{}", base_url, filename)));
+
+    let client = reqwest::Client::new();
+    for (filename, url) in files {
+        let path = download_dir.join(filename);
+        if path.exists() {
+            debug!("Cleanup model file already exists: {}", filename);
+            continue;
+        }
+
+        info!("Downloading cleanup model file: {} from {}", filename, url);
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", filename))?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to download {}: HTTP {}",
+                filename,
+                response.status()
+            );
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to read bytes for {}", filename))?;
+
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, &bytes)
+            .with_context(|| format!("Failed to write {}", filename))?;
+        std::fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to rename {}", filename))?;
+        info!(
+            "Downloaded cleanup model file: {} ({} bytes)",
+            filename,
+            bytes.len()
+        );
+    }
+
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl TextCleanupProvider for CleanupManager {
     fn name(&self) -> &str {
@@ -336,46 +405,54 @@ impl TextCleanupProvider for CleanupManager {
             return Ok(text.to_string());
         }
 
-        // Download models if needed
-        self.ensure_models_downloaded().await?;
-
-        // Load sessions if needed
-        self.ensure_session_loaded()?;
+        let model_dir = self.ready_model_dir()?;
 
         // Mask protected spans
         let (masked_text, spans) = mask_protected_spans(text);
 
         // Split into sentences for long texts
-        let segments = if masked_text.len() > 100 {
+        let segments: Vec<String> = if masked_text.len() > 100 {
             Self::split_sentences(&masked_text)
+                .into_iter()
+                .map(|segment| segment.trim().to_string())
+                .collect()
         } else {
-            vec![masked_text.as_str()]
+            vec![masked_text.trim().to_string()]
         };
 
-        let mut cleaned_parts = Vec::new();
-        for segment in &segments {
-            let trimmed = segment.trim();
-            if trimmed.is_empty() {
-                cleaned_parts.push(String::new());
-                continue;
-            }
+        // Loading and inference are CPU-bound, so keep them off the async runtime.
+        let session = Arc::clone(&self.session);
+        let cleaned_parts = tauri::async_runtime::spawn_blocking(move || -> Result<Vec<String>> {
+            let mut guard = session.lock().map_err(|e| anyhow::anyhow!("{}", e))?;
+            let session = Self::ensure_session_loaded(&mut guard, &model_dir)?;
+
+            let mut cleaned_parts = Vec::new();
+            for trimmed in segments {
+                if trimmed.is_empty() {
+                    cleaned_parts.push(String::new());
+                    continue;
+                }
 
-            let prompt = format!(
-                "Fix punctuation and capitalization, remove filler words, and keep meaning. \
-                 Do not rewrite placeholders like \u{27E6}P0\u{27E7}. Text: {}",
-                trimmed
-            );
+                let prompt = format!(
+                    "Fix punctuation and capitalization, remove filler words, and keep meaning. \
+                     Do not rewrite placeholders like \u{27E6}P0\u{27E7}. Text: {}",
+                    trimmed
+                );
 
-            match self.run_t5(&prompt) {
-                Ok(cleaned) => {
-                    cleaned_parts.push(cleaned);
-                }
-                Err(e) => {
-                    warn!("T5 cleanup failed for segment, using original: {}", e);
-                    cleaned_parts.push(trimmed.to_string());
+                match Self::run_t5(session, &prompt) {
+                    Ok(cleaned) => {
+                        cleaned_parts.push(cleaned);
+                    }
+                    Err(e) => {
+                        warn!("T5 cleanup failed for segment, using original: {}", e);
+                        cleaned_parts.push(trimmed);
+                    }
                 }
             }
-        }
+            Ok(cleaned_parts)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Cleanup task failed: {}", e))??;
 
         let joined = cleaned_parts.join(" ");
 
@@ -396,6 +473,24 @@ impl TextCleanupProvider for CleanupManager {
 // Guardrails
 // ============================================================================
 
+/// Accepted range for `rewritten.len() / original.len()`.
+const MIN_LENGTH_RATIO: f64 = 0.5;
+const MAX_LENGTH_RATIO: f64 = 2.0;
+
+/// Minimum word-level similarity between a rewrite and its source.
+const MIN_WORD_SIMILARITY: f32 = 0.4;
+
+/// Inputs shorter than this many words skip the ratio and similarity checks;
+/// small edits to a few words swing both measures too far to be meaningful.
+const MIN_WORDS_FOR_RATIO_CHECKS: usize = 4;
+
+fn length_ratio(original: &str, rewritten: &str) -> Option<f64> {
+    if original.is_empty() {
+        return None;
+    }
+    Some(rewritten.len() as f64 / original.len() as f64)
+}
+
 fn validate_cleanup(original: &str, cleaned: &str, spans: &[ProtectedSpan]) -> bool {
     // Non-empty check
     if cleaned.trim().is_empty() {
@@ -404,11 +499,8 @@ fn validate_cleanup(original: &str, cleaned: &str, spans: &[ProtectedSpan]) -> b
     }
 
     // Length ratio check (0.5x to 2.0x)
-    let orig_len = original.len() as f64;
-    let clean_len = cleaned.len() as f64;
-    if orig_len > 0.0 {
-        let ratio = clean_len / orig_len;
-        if ratio < 0.5 || ratio > 2.0 {
+    if let Some(ratio) = length_ratio(original, cleaned) {
+        if !(MIN_LENGTH_RATIO..=MAX_LENGTH_RATIO).contains(&ratio) {
             warn!(
                 "Cleanup length ratio out of bounds: {:.2} (original: {}, cleaned: {})",
                 ratio,
@@ -433,6 +525,76 @@ fn validate_cleanup(original: &str, cleaned: &str, spans: &[ProtectedSpan]) -> b
     true
 }
 
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Similarity of the two texts' word sequences, ignoring case and
+/// surrounding punctuation. 1.0 means the same words in the same order.
+pub(crate) fn word_similarity(original: &str, rewritten: &str) -> f32 {
+    let old = normalized_words(original);
+    let new = normalized_words(rewritten);
+    let ops = similar::capture_diff_slices(similar::Algorithm::Myers, &old, &new);
+    similar::get_diff_ratio(&ops, old.len(), new.len())
+}
+
+/// Check that a post-processed `rewritten` text is still a cleanup of
+/// `original` rather than an answer to it or a truncated copy. Returns the
+/// reason when the rewrite diverges too far to be trusted.
+///
+/// Applied to every post-processing result, local or remote, on top of the
+/// placeholder checks `CleanupManager` runs on its own output.
+pub(crate) fn check_output_divergence(original: &str, rewritten: &str) -> Result<(), String> {
+    check_protected_spans(original, rewritten)?;
+
+    if normalized_words(original).len() < MIN_WORDS_FOR_RATIO_CHECKS {
+        return Ok(());
+    }
+
+    if let Some(ratio) = length_ratio(original, rewritten) {
+        if !(MIN_LENGTH_RATIO..=MAX_LENGTH_RATIO).contains(&ratio) {
+            return Err(format!(
+                "length ratio {:.2} outside {:.1}-{:.1}",
+                ratio, MIN_LENGTH_RATIO, MAX_LENGTH_RATIO
+            ));
+        }
+    }
+
+    let similarity = word_similarity(original, rewritten);
+    if similarity < MIN_WORD_SIMILARITY {
+        return Err(format!(
+            "word similarity {:.2} below {:.2}",
+            similarity, MIN_WORD_SIMILARITY
+        ));
+    }
+
+    Ok(())
+}
+
+/// The part of `check_output_divergence` that still holds for a prompt that
+/// restructures the text: the output is not empty and @refs, URLs, paths and
+/// CLI flags come through verbatim.
+pub(crate) fn check_protected_spans(original: &str, rewritten: &str) -> Result<(), String> {
+    if rewritten.trim().is_empty() {
+        return Err("output is empty".to_string());
+    }
+
+    let (_, spans) = mask_protected_spans(original);
+    for span in &spans {
+        let protected = span.original.trim();
+        if !rewritten.contains(protected) {
+            return Err(format!("protected span '{}' was dropped", protected));
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -440,6 +602,7 @@ fn validate_cleanup(original: &str, cleaned: &str, spans: &[ProtectedSpan]) -> b
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn test_mask_and_restore_at_tokens() {
@@ -527,6 +690,57 @@ mod tests {
         assert!(validate_cleanup("hello world", "Hello world.", &[]));
     }
 
+    #[test]
+    fn test_output_divergence_accepts_light_cleanup() {
+        let original = "um so we should uh update the readme before the release";
+        let rewritten = "So we should update the README before the release.";
+        assert_eq!(check_output_divergence(original, rewritten), Ok(()));
+        assert_eq!(check_output_divergence("hi", "Hi."), Ok(()));
+    }
+
+    #[test]
+    fn test_output_divergence_rejects_answers_and_truncation() {
+        let original = "what is the capital of france and how big is it";
+        let answer = "Paris is the capital of France, with about two million residents \
+                      in the city proper and over twelve million in the wider region.";
+        assert!(check_output_divergence(original, answer).is_err());
+
+        let original = "first we build the app then we run the tests and finally we ship it";
+        assert!(check_output_divergence(original, "First we build the app.").is_err());
+    }
+
+    #[test]
+    fn test_output_divergence_requires_protected_spans() {
+        let original = "open @src/main.rs and run it with --release please";
+        let dropped = "Open the main file and run it with --release, please.";
+        let err = check_output_divergence(original, dropped).unwrap_err();
+        assert!(err.contains("@src/main.rs"));
+
+        let kept = "Open @src/main.rs and run it with --release, please.";
+        assert_eq!(check_output_divergence(original, kept), Ok(()));
+    }
+
+    #[test]
+    fn test_protected_spans_allow_restructured_output() {
+        let original = "so tomorrow sam will fix the crash in @src/main.rs and i will review the release notes before friday";
+        let notes = "- [ ] Sam - Fix crash in @src/main.rs (Due: TBA)";
+        assert!(check_output_divergence(original, notes).is_err());
+        assert_eq!(check_protected_spans(original, notes), Ok(()));
+        assert!(check_protected_spans(original, "- [ ] Sam - Fix the crash").is_err());
+        assert!(check_protected_spans(original, "  ").is_err());
+    }
+
+    #[test]
+    fn test_missing_model_files_lists_absent_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("tokenizer.json"), "{}").unwrap();
+
+        assert_eq!(
+            missing_model_files(dir.path()),
+            vec!["encoder_model.onnx", "decoder_model.onnx"]
+        );
+    }
+
     #[test]
     fn test_sentence_splitting() {
         let text = "Hello world. This is a test! Another sentence? Final one.";
@@ -1,7 +1,7 @@
 use crate::input::{self, EnigoState};
 #[cfg(target_os = "linux")]
 use crate::settings::TypingTool;
-use crate::settings::{get_settings, AutoSubmitKey, ClipboardHandling, PasteMethod};
+use crate::settings::{get_settings, AppSettings, AutoSubmitKey, ClipboardHandling, PasteMethod};
 use enigo::{Direction, Enigo, Key, Keyboard};
 use log::{debug, info};
 use std::time::Duration;
@@ -630,6 +630,26 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Send the configured auto-submit key if `auto_submit` is on, for callers
+/// that paste with `paste_raw` and decide on submitting separately.
+pub fn send_auto_submit(app_handle: &AppHandle) -> Result<(), String> {
+    let settings = get_settings(app_handle);
+    if !should_send_auto_submit(settings.auto_submit, settings.paste_method) {
+        return Ok(());
+    }
+
+    let enigo_state = app_handle
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    std::thread::sleep(Duration::from_millis(50));
+    send_return_key(&mut enigo, settings.auto_submit_key)
+}
+
 /// Pastes text without appending a trailing space and without auto-submit.
 /// Used to immediately show raw transcription text before post-processing completes.
 pub fn paste_raw(text: String, app_handle: AppHandle) -> Result<(), String> {
@@ -680,17 +700,30 @@ pub fn paste_raw(text: String, app_handle: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-/// Apply a minimal text diff to the already-pasted text:
-/// 1. Move cursor left from the end to skip unchanged suffix
+/// One replacement within already-pasted text, applied at the cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    /// Characters to move the cursor left before editing, which places it
+    /// at the end of the region to replace.
+    pub cursor_left: usize,
+    /// Characters before the cursor to delete.
+    pub delete_chars: usize,
+    /// Replacement text typed at the cursor.
+    pub insert: String,
+}
+
+/// Apply a text diff to the already-pasted text, starting with the cursor at
+/// the end. For each edit, in order:
+/// 1. Move cursor left to the end of the changed region
 /// 2. Backspace-delete the changed region at that boundary
 /// 3. Insert replacement text
-/// 4. Move right over unchanged suffix to restore cursor-at-end
+///
+/// Finally move right by `restore_chars` to put the cursor back at the end.
 ///
 /// This produces a much less jarring visual experience than deleting everything.
 pub fn apply_text_diff(
-    suffix_chars: usize,
-    delete_chars: usize,
-    insert_text: &str,
+    edits: &[TextEdit],
+    restore_chars: usize,
     app_handle: AppHandle,
 ) -> Result<(), String> {
     let settings = get_settings(&app_handle);
@@ -700,10 +733,9 @@ pub fn apply_text_diff(
     }
 
     debug!(
-        "apply_text_diff: suffix={}, delete={}, insert={} chars",
-        suffix_chars,
-        delete_chars,
-        insert_text.len(),
+        "apply_text_diff: {} edit(s), restore={} chars",
+        edits.len(),
+        restore_chars,
     );
 
     let enigo_state = app_handle
@@ -714,10 +746,38 @@ pub fn apply_text_diff(
         .lock()
         .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
 
+    for edit in edits {
+        apply_text_edit(&mut enigo, edit, &settings, &app_handle)?;
+    }
+
+    // Move cursor back to end (past the unchanged text after the first edit)
+    if restore_chars > 0 {
+        input::send_right_arrow(&mut enigo, restore_chars)?;
+    }
+
+    Ok(())
+}
+
+fn apply_text_edit(
+    enigo: &mut Enigo,
+    edit: &TextEdit,
+    settings: &AppSettings,
+    app_handle: &AppHandle,
+) -> Result<(), String> {
+    debug!(
+        "apply_text_edit: left={}, delete={}, insert={} chars",
+        edit.cursor_left,
+        edit.delete_chars,
+        edit.insert.len(),
+    );
+
     let mut used_ax_selection = false;
     #[cfg(target_os = "macos")]
     {
-        match crate::macos_ax::try_select_replace_range_before_cursor(delete_chars, suffix_chars) {
+        match crate::macos_ax::try_select_replace_range_before_cursor(
+            edit.delete_chars,
+            edit.cursor_left,
+        ) {
             Ok(()) => {
                 used_ax_selection = true;
             }
@@ -731,15 +791,15 @@ pub fn apply_text_diff(
     }
 
     if !used_ax_selection {
-        // Step 1: Move cursor left past the unchanged suffix to reach the end of the changed region
-        if suffix_chars > 0 {
-            input::send_left_arrow(&mut enigo, suffix_chars)?;
+        // Step 1: Move cursor left past unchanged text to reach the end of the changed region
+        if edit.cursor_left > 0 {
+            input::send_left_arrow(enigo, edit.cursor_left)?;
             std::thread::sleep(Duration::from_millis(30));
         }
 
         // Step 2: Delete changed region with bounded backspaces at this boundary.
-        if delete_chars > 0 {
-            for _ in 0..delete_chars {
+        if edit.delete_chars > 0 {
+            for _ in 0..edit.delete_chars {
                 enigo
                     .key(Key::Backspace, enigo::Direction::Click)
                     .map_err(|e| format!("Failed to send Backspace: {}", e))?;
@@ -749,7 +809,7 @@ pub fn apply_text_diff(
     }
 
     // Step 3: Type the replacement text at cursor.
-    if !insert_text.is_empty() {
+    if !edit.insert.is_empty() {
         let paste_method = settings.paste_method;
         let paste_delay_ms = settings.paste_delay_ms;
 
@@ -757,24 +817,24 @@ pub fn apply_text_diff(
             PasteMethod::None => {}
             PasteMethod::Direct => {
                 paste_direct(
-                    &mut enigo,
-                    insert_text,
+                    enigo,
+                    &edit.insert,
                     #[cfg(target_os = "linux")]
                     settings.typing_tool,
                 )?;
             }
             PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
                 paste_via_clipboard(
-                    &mut enigo,
-                    insert_text,
-                    &app_handle,
+                    enigo,
+                    &edit.insert,
+                    app_handle,
                     &paste_method,
                     paste_delay_ms,
                 )?;
             }
         }
         std::thread::sleep(Duration::from_millis(30));
-    } else if delete_chars > 0 && used_ax_selection {
+    } else if edit.delete_chars > 0 && used_ax_selection {
         // AX path selected the range; clear it when replacement is empty.
         enigo
             .key(Key::Backspace, enigo::Direction::Click)
@@ -782,11 +842,6 @@ pub fn apply_text_diff(
         std::thread::sleep(Duration::from_millis(30));
     }
 
-    // Step 4: Move cursor back to end (past the unchanged suffix)
-    if suffix_chars > 0 {
-        input::send_right_arrow(&mut enigo, suffix_chars)?;
-    }
-
     Ok(())
 }
 
@@ -1,3 +1,5 @@
+use crate::jargon_learning::{self, JargonProposal};
+use crate::managers::domain_selector;
 use crate::managers::history::{HistoryEntry, HistoryManager};
 use std::sync::Arc;
 use tauri::{AppHandle, State};
@@ -53,6 +55,31 @@ pub async fn delete_history_entry(
         .map_err(|e| e.to_string())
 }
 
+/// Save the user's correction of an entry's transcription and return the
+/// jargon it suggests learning.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_history_entry_text(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    text: String,
+) -> Result<Vec<JargonProposal>, String> {
+    let entry = history_manager
+        .update_entry_text(id, &text)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut settings = crate::settings::get_settings(&app);
+    if settings.domain_selector_learned_weights {
+        domain_selector::record_edit_feedback(&mut settings, &entry.transcription_text, &text);
+        crate::settings::write_settings(&app, settings.clone());
+    }
+
+    let proposals = jargon_learning::propose(&entry.transcription_text, &text);
+    Ok(jargon_learning::filter_known(proposals, &settings))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn update_history_limit(
@@ -0,0 +1,849 @@
+//! Config packs: a versioned JSON bundle of the settings a team shares —
+//! jargon packs, post-process prompts, custom words, corrections, providers
+//! (never their API keys), snippets, templates and style presets.
+//!
+//! Version 1 is the jargon-only format from `docs/jargon-generation.md`
+//! (`{"version": 1, "packs": [...]}`), which still parses since every other
+//! section defaults to empty.
+//!
+//! Importing validates the whole pack and applies it to a copy of the
+//! settings; the caller only writes that copy back when everything
+//! succeeded, so a failed import leaves the settings untouched.
+
+use crate::jargon::JargonCorrection;
+use crate::settings::{AppSettings, JargonPack, LLMPrompt, PostProcessProvider};
+use crate::snippets::{self, Snippet};
+use crate::style_presets::{self, StylePreset};
+use crate::templates::{self, TextTemplate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+
+pub const CONFIG_PACK_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Type)]
+pub struct ConfigPack {
+    #[serde(default)]
+    pub version: u32,
+    /// Jargon packs; named `packs` for compatibility with version 1.
+    #[serde(default)]
+    pub packs: Vec<JargonPack>,
+    #[serde(default)]
+    pub prompts: Vec<LLMPrompt>,
+    #[serde(default)]
+    pub custom_words: Vec<String>,
+    #[serde(default)]
+    pub corrections: Vec<JargonCorrection>,
+    #[serde(default)]
+    pub providers: Vec<PostProcessProvider>,
+    /// Selected model per provider id in `providers`.
+    #[serde(default)]
+    pub provider_models: HashMap<String, String>,
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+    #[serde(default)]
+    pub templates: Vec<TextTemplate>,
+    #[serde(default)]
+    pub style_presets: Vec<StylePreset>,
+}
+
+/// What to do with an imported item.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStrategy {
+    /// Add the item if new, otherwise keep the existing one.
+    KeepExisting,
+    /// Add the item, overwriting an existing one with the same key.
+    Replace,
+    /// Combine with an existing item. Jargon packs get the union of their
+    /// terms and corrections; other items take the imported version.
+    Merge,
+    /// Leave the item out of the import.
+    Skip,
+}
+
+/// One importable entry, keyed `"<section>:<key>"` for per-item strategies.
+#[derive(Serialize, Debug, Clone, PartialEq, Type)]
+pub struct ConfigPackItem {
+    pub key: String,
+    pub section: String,
+    pub label: String,
+    /// An item with the same key is already configured.
+    pub exists: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Type)]
+pub struct ConfigPackPreview {
+    pub version: u32,
+    pub items: Vec<ConfigPackItem>,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq, Type)]
+pub struct ConfigPackImportSummary {
+    pub added: u32,
+    pub replaced: u32,
+    pub merged: u32,
+    pub kept: u32,
+    pub skipped: u32,
+}
+
+// ============================================================================
+// Items
+// ============================================================================
+
+trait PackItem: Clone {
+    const SECTION: &'static str;
+
+    /// Identity used to detect conflicts with existing items.
+    fn key(&self) -> String;
+
+    fn label(&self) -> String {
+        self.key()
+    }
+
+    fn merge(&self, incoming: &Self) -> Self {
+        incoming.clone()
+    }
+}
+
+impl PackItem for JargonPack {
+    const SECTION: &'static str = "packs";
+
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    fn merge(&self, incoming: &Self) -> Self {
+        let mut merged = incoming.clone();
+        let mut terms = self.terms.clone();
+        for term in &incoming.terms {
+            if !terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+                terms.push(term.clone());
+            }
+        }
+        merged.terms = terms;
+
+        let mut corrections = self.corrections.clone();
+        for correction in &incoming.corrections {
+            match corrections
+                .iter_mut()
+                .find(|c| c.variant_key() == correction.variant_key())
+            {
+                Some(existing) => *existing = correction.clone(),
+                None => corrections.push(correction.clone()),
+            }
+        }
+        merged.corrections = corrections;
+        merged
+    }
+}
+
+impl PackItem for LLMPrompt {
+    const SECTION: &'static str = "prompts";
+
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl PackItem for String {
+    const SECTION: &'static str = "custom_words";
+
+    fn key(&self) -> String {
+        self.to_lowercase()
+    }
+
+    fn label(&self) -> String {
+        self.clone()
+    }
+}
+
+impl PackItem for JargonCorrection {
+    const SECTION: &'static str = "corrections";
+
+    /// The phrase, followed by the conditions of a conditional variant, so
+    /// that variants of one phrase get their own keys.
+    fn key(&self) -> String {
+        let (mut key, case_sensitive, near, not_near, whole_utterance) = self.variant_key();
+        if case_sensitive {
+            key.push_str(" [case sensitive]");
+        }
+        if !near.is_empty() {
+            key.push_str(&format!(" [near {}]", near.join(", ")));
+        }
+        if !not_near.is_empty() {
+            key.push_str(&format!(" [not near {}]", not_near.join(", ")));
+        }
+        if whole_utterance {
+            key.push_str(" [whole utterance]");
+        }
+        key
+    }
+
+    fn label(&self) -> String {
+        format!("{} -> {}", self.from, self.to)
+    }
+}
+
+impl PackItem for PostProcessProvider {
+    const SECTION: &'static str = "providers";
+
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+}
+
+impl PackItem for Snippet {
+    const SECTION: &'static str = "snippets";
+
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn label(&self) -> String {
+        self.trigger.clone()
+    }
+}
+
+impl PackItem for TextTemplate {
+    const SECTION: &'static str = "templates";
+
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl PackItem for StylePreset {
+    const SECTION: &'static str = "style_presets";
+
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+}
+
+fn item_key<T: PackItem>(item: &T) -> String {
+    format!("{}:{}", T::SECTION, item.key())
+}
+
+// ============================================================================
+// Parsing and validation
+// ============================================================================
+
+/// Parse and validate a config pack. Every item is checked before anything
+/// is imported.
+pub fn parse(json: &str) -> Result<ConfigPack, String> {
+    let pack: ConfigPack =
+        serde_json::from_str(json).map_err(|e| format!("Invalid config pack JSON: {}", e))?;
+    if pack.version > CONFIG_PACK_VERSION {
+        return Err(format!(
+            "Config pack version {} is newer than this app supports ({})",
+            pack.version, CONFIG_PACK_VERSION
+        ));
+    }
+    validate(&pack)?;
+    Ok(pack)
+}
+
+fn validate(pack: &ConfigPack) -> Result<(), String> {
+    for jargon in &pack.packs {
+        if jargon.id.trim().is_empty() || jargon.label.trim().is_empty() {
+            return Err("Jargon packs need an id and a label".to_string());
+        }
+        if jargon.terms.iter().any(|term| term.trim().is_empty()) {
+            return Err(format!("Jargon pack '{}' has an empty term", jargon.id));
+        }
+        if let Some(correction) = jargon.corrections.iter().find(|c| !is_valid_correction(c)) {
+            return Err(format!(
+                "Jargon pack '{}' has an incomplete correction '{}'",
+                jargon.id, correction.from
+            ));
+        }
+    }
+    for prompt in &pack.prompts {
+        if prompt.id.trim().is_empty()
+            || prompt.name.trim().is_empty()
+            || prompt.prompt.trim().is_empty()
+        {
+            return Err("Prompts need an id, a name and prompt text".to_string());
+        }
+    }
+    if pack.custom_words.iter().any(|word| word.trim().is_empty()) {
+        return Err("Custom words cannot be empty".to_string());
+    }
+    if let Some(correction) = pack.corrections.iter().find(|c| !is_valid_correction(c)) {
+        return Err(format!("Incomplete correction '{}'", correction.from));
+    }
+    for provider in &pack.providers {
+        if provider.id.trim().is_empty() || provider.label.trim().is_empty() {
+            return Err("Providers need an id and a label".to_string());
+        }
+        if !is_allowed_base_url(&provider.base_url) {
+            return Err(format!(
+                "Provider '{}' has an invalid base URL '{}'; use https, or http on localhost",
+                provider.id, provider.base_url
+            ));
+        }
+    }
+    for provider_id in pack.provider_models.keys() {
+        if !pack.providers.iter().any(|p| &p.id == provider_id) {
+            return Err(format!(
+                "Model given for provider '{}', which is not in the pack",
+                provider_id
+            ));
+        }
+    }
+    for snippet in &pack.snippets {
+        if snippet.id.trim().is_empty() || snippet.body.trim().is_empty() {
+            return Err("Snippets need an id and text".to_string());
+        }
+        snippets::validate_trigger(&snippet.trigger)?;
+    }
+    templates::validate_templates(pack.templates.clone())?;
+    style_presets::validate_presets(pack.style_presets.clone())?;
+
+    unique_keys(&pack.packs)?;
+    unique_keys(&pack.prompts)?;
+    unique_keys(&pack.custom_words)?;
+    unique_keys(&pack.corrections)?;
+    unique_keys(&pack.providers)?;
+    unique_keys(&pack.snippets)?;
+    Ok(())
+}
+
+/// A shared pack may only point providers at https endpoints, or at a server
+/// on this machine.
+fn is_allowed_base_url(url: &str) -> bool {
+    if url.starts_with("https://") {
+        return true;
+    }
+    let Some(rest) = url.strip_prefix("http://") else {
+        return false;
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if authority.contains('@') {
+        return false;
+    }
+    let host = if authority.starts_with('[') {
+        authority.split(']').next().map(|h| format!("{}]", h))
+    } else {
+        authority.split(':').next().map(str::to_string)
+    };
+    matches!(
+        host.as_deref().map(str::to_ascii_lowercase).as_deref(),
+        Some("localhost" | "127.0.0.1" | "[::1]")
+    )
+}
+
+fn is_valid_correction(correction: &JargonCorrection) -> bool {
+    !correction.from.trim().is_empty() && !correction.to.trim().is_empty()
+}
+
+fn unique_keys<T: PackItem>(items: &[T]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for item in items {
+        if !seen.insert(item.key()) {
+            return Err(format!("Duplicate entry '{}'", item_key(item)));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Preview, import and export
+// ============================================================================
+
+pub fn preview(settings: &AppSettings, pack: &ConfigPack) -> ConfigPackPreview {
+    let mut items = Vec::new();
+    preview_section(&mut items, &settings.jargon_packs, &pack.packs);
+    preview_section(&mut items, &settings.post_process_prompts, &pack.prompts);
+    preview_section(&mut items, &settings.custom_words, &pack.custom_words);
+    preview_section(
+        &mut items,
+        &settings.jargon_custom_corrections,
+        &pack.corrections,
+    );
+    preview_section(
+        &mut items,
+        &settings.post_process_providers,
+        &pack.providers,
+    );
+    preview_section(&mut items, &settings.snippets, &pack.snippets);
+    preview_section(&mut items, &settings.templates, &pack.templates);
+    preview_section(&mut items, &settings.style_presets, &pack.style_presets);
+    ConfigPackPreview {
+        version: pack.version,
+        items,
+    }
+}
+
+fn preview_section<T: PackItem>(items: &mut Vec<ConfigPackItem>, existing: &[T], incoming: &[T]) {
+    for item in incoming {
+        items.push(ConfigPackItem {
+            key: item_key(item),
+            section: T::SECTION.to_string(),
+            label: item.label(),
+            exists: existing.iter().any(|e| e.key() == item.key()),
+        });
+    }
+}
+
+/// Apply `pack` to a copy of `settings`. `strategy` picks the strategy for
+/// each item key. Returns the updated settings, which are only valid as a
+/// whole, so callers must write all of them or nothing.
+pub fn import(
+    settings: &AppSettings,
+    pack: ConfigPack,
+    strategy: impl Fn(&str) -> ImportStrategy,
+) -> Result<(AppSettings, ConfigPackImportSummary), String> {
+    let mut updated = settings.clone();
+    let mut summary = ConfigPackImportSummary::default();
+    import_section(
+        &mut updated.jargon_packs,
+        pack.packs,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+    import_section(
+        &mut updated.post_process_prompts,
+        pack.prompts,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+    import_section(
+        &mut updated.custom_words,
+        pack.custom_words,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+    import_section(
+        &mut updated.jargon_custom_corrections,
+        pack.corrections,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+
+    // Built-in providers have fixed endpoints and are never overwritten.
+    let builtin: HashSet<String> = settings
+        .post_process_providers
+        .iter()
+        .filter(|p| !p.allow_base_url_edit)
+        .map(|p| p.id.clone())
+        .collect();
+    // Imported providers stay editable, so they are never mistaken for
+    // built-ins (locked on the next import and left out of exports).
+    let providers = pack
+        .providers
+        .into_iter()
+        .map(|provider| PostProcessProvider {
+            allow_base_url_edit: true,
+            ..provider
+        })
+        .collect();
+    let imported_providers = import_section(
+        &mut updated.post_process_providers,
+        providers,
+        &strategy,
+        |p: &PostProcessProvider| builtin.contains(&p.id),
+        &mut summary,
+    );
+    for provider_id in imported_providers {
+        // A stored key was given for the old endpoint; never send it to a
+        // URL that came from the pack.
+        let moved = settings
+            .post_process_provider(&provider_id)
+            .zip(updated.post_process_provider(&provider_id))
+            .is_some_and(|(before, after)| before.base_url != after.base_url);
+        let key = updated
+            .post_process_api_keys
+            .entry(provider_id.clone())
+            .or_default();
+        if moved {
+            key.clear();
+        }
+        let model = pack.provider_models.get(&provider_id).cloned();
+        let entry = updated.post_process_models.entry(provider_id).or_default();
+        if let Some(model) = model {
+            *entry = model;
+        }
+    }
+
+    import_section(
+        &mut updated.snippets,
+        pack.snippets,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+    import_section(
+        &mut updated.templates,
+        pack.templates,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+    import_section(
+        &mut updated.style_presets,
+        pack.style_presets,
+        &strategy,
+        unlocked,
+        &mut summary,
+    );
+
+    // Imported items can still clash with existing ones under another key.
+    updated.templates = templates::validate_templates(updated.templates)?;
+    updated.style_presets = style_presets::validate_presets(updated.style_presets)?;
+    let mut triggers = HashSet::new();
+    for snippet in &mut updated.snippets {
+        snippet.trigger = snippets::validate_trigger(&snippet.trigger)?;
+        if !triggers.insert(snippet.trigger.clone()) {
+            return Err(format!(
+                "Snippet trigger '{}' is already in use",
+                snippet.trigger
+            ));
+        }
+    }
+
+    Ok((updated, summary))
+}
+
+fn unlocked<T>(_: &T) -> bool {
+    false
+}
+
+/// Import one section. Items for which `locked` is true keep their existing
+/// version. Returns the keys of items that were added or changed.
+fn import_section<T: PackItem>(
+    existing: &mut Vec<T>,
+    incoming: Vec<T>,
+    strategy: &impl Fn(&str) -> ImportStrategy,
+    locked: impl Fn(&T) -> bool,
+    summary: &mut ConfigPackImportSummary,
+) -> Vec<String> {
+    let mut changed = Vec::new();
+    for item in incoming {
+        let strategy = strategy(&item_key(&item));
+        if strategy == ImportStrategy::Skip {
+            summary.skipped += 1;
+            continue;
+        }
+        let key = item.key();
+        match existing.iter().position(|e| e.key() == key) {
+            None => {
+                existing.push(item);
+                summary.added += 1;
+                changed.push(key);
+            }
+            Some(index) if locked(&existing[index]) || strategy == ImportStrategy::KeepExisting => {
+                summary.kept += 1;
+            }
+            Some(index) if strategy == ImportStrategy::Merge => {
+                existing[index] = existing[index].merge(&item);
+                summary.merged += 1;
+                changed.push(key);
+            }
+            Some(index) => {
+                existing[index] = item;
+                summary.replaced += 1;
+                changed.push(key);
+            }
+        }
+    }
+    changed
+}
+
+/// Everything shareable in `settings`. Only user-editable providers are
+/// included, and API keys never are.
+pub fn export(settings: &AppSettings) -> ConfigPack {
+    let providers: Vec<PostProcessProvider> = settings
+        .post_process_providers
+        .iter()
+        .filter(|p| p.allow_base_url_edit)
+        .cloned()
+        .collect();
+    let provider_models = providers
+        .iter()
+        .filter_map(|p| {
+            let model = settings.post_process_models.get(&p.id)?;
+            (!model.is_empty()).then(|| (p.id.clone(), model.clone()))
+        })
+        .collect();
+
+    ConfigPack {
+        version: CONFIG_PACK_VERSION,
+        packs: settings.jargon_packs.clone(),
+        prompts: settings.post_process_prompts.clone(),
+        custom_words: settings.custom_words.clone(),
+        corrections: settings.jargon_custom_corrections.clone(),
+        providers,
+        provider_models,
+        snippets: settings.snippets.clone(),
+        templates: settings.templates.clone(),
+        style_presets: settings.style_presets.clone(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    fn jargon(id: &str, terms: &[&str]) -> JargonPack {
+        JargonPack {
+            id: id.to_string(),
+            label: id.to_uppercase(),
+            terms: terms.iter().map(|t| t.to_string()).collect(),
+            corrections: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn jargon_generation_format_is_accepted() {
+        let json = r#"{
+            "version": 1,
+            "packs": [{
+                "id": "example_domain",
+                "label": "Example Domain",
+                "terms": ["Canonical Term"],
+                "corrections": [{ "from": "spoken term", "to": "Canonical Term" }]
+            }]
+        }"#;
+        let pack = parse(json).unwrap();
+        assert_eq!(pack.packs.len(), 1);
+        assert!(pack.prompts.is_empty());
+
+        let (updated, summary) =
+            import(&get_default_settings(), pack, |_| ImportStrategy::Replace).unwrap();
+        assert_eq!(summary.added, 1);
+        assert!(updated
+            .jargon_packs
+            .iter()
+            .any(|p| p.id == "example_domain"));
+    }
+
+    #[test]
+    fn strategies_apply_per_item() {
+        let mut settings = get_default_settings();
+        settings.jargon_packs = vec![jargon("infra", &["Terraform"]), jargon("web", &["React"])];
+        settings.custom_words = vec!["Kubernetes".to_string()];
+
+        let pack = ConfigPack {
+            version: CONFIG_PACK_VERSION,
+            packs: vec![
+                jargon("infra", &["terraform", "Helm"]),
+                jargon("web", &["Svelte"]),
+                jargon("data", &["Spark"]),
+            ],
+            custom_words: vec!["kubernetes".to_string(), "Istio".to_string()],
+            ..Default::default()
+        };
+        let (updated, summary) = import(&settings, pack, |key| match key {
+            "packs:infra" => ImportStrategy::Merge,
+            "packs:web" => ImportStrategy::KeepExisting,
+            "packs:data" => ImportStrategy::Skip,
+            _ => ImportStrategy::Replace,
+        })
+        .unwrap();
+
+        let terms = |id: &str| {
+            updated
+                .jargon_packs
+                .iter()
+                .find(|p| p.id == id)
+                .map(|p| p.terms.clone())
+        };
+        assert_eq!(
+            terms("infra"),
+            Some(vec!["Terraform".into(), "Helm".into()])
+        );
+        assert_eq!(terms("web"), Some(vec!["React".into()]));
+        assert_eq!(terms("data"), None);
+        assert_eq!(updated.custom_words, vec!["kubernetes", "Istio"]);
+        assert_eq!(
+            summary,
+            ConfigPackImportSummary {
+                added: 1,
+                replaced: 1,
+                merged: 1,
+                kept: 1,
+                skipped: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_packs_are_rejected_before_import() {
+        assert!(parse(r#"{"version": 1, "packs": [{"id": "", "label": "x"}]}"#).is_err());
+        assert!(parse(r#"{"version": 99}"#).is_err());
+        assert!(
+            parse(r#"{"providers": [{"id": "p", "label": "P", "base_url": "ftp://x"}]}"#).is_err()
+        );
+        assert!(parse(r#"{"custom_words": ["a", "A"]}"#).is_err());
+        for url in [
+            "http://example.com/v1",
+            "http://localhost.example.com/v1",
+            "http://localhost@example.com/v1",
+        ] {
+            let json = format!(
+                r#"{{"providers": [{{"id": "p", "label": "P", "base_url": "{}"}}]}}"#,
+                url
+            );
+            assert!(parse(&json).is_err(), "{} should be rejected", url);
+        }
+        for url in ["https://example.com/v1", "http://localhost:11434/v1"] {
+            let json = format!(
+                r#"{{"providers": [{{"id": "p", "label": "P", "base_url": "{}"}}]}}"#,
+                url
+            );
+            assert!(parse(&json).is_ok(), "{} should be accepted", url);
+        }
+    }
+
+    #[test]
+    fn imported_providers_never_inherit_keys_for_a_new_url() {
+        let mut settings = get_default_settings();
+        settings
+            .post_process_api_keys
+            .insert("custom".to_string(), "secret-key".to_string());
+        let custom = settings.post_process_provider("custom").unwrap().clone();
+
+        let same_url = ConfigPack {
+            providers: vec![PostProcessProvider {
+                label: "Team".to_string(),
+                ..custom.clone()
+            }],
+            ..Default::default()
+        };
+        let (updated, _) = import(&settings, same_url, |_| ImportStrategy::Replace).unwrap();
+        assert_eq!(updated.post_process_api_keys["custom"], "secret-key");
+
+        let moved = ConfigPack {
+            providers: vec![PostProcessProvider {
+                base_url: "https://llm.example.com/v1".to_string(),
+                allow_base_url_edit: false,
+                ..custom
+            }],
+            ..Default::default()
+        };
+        let (updated, _) = import(&settings, moved, |_| ImportStrategy::Replace).unwrap();
+        assert_eq!(updated.post_process_api_keys["custom"], "");
+        assert!(
+            updated
+                .post_process_provider("custom")
+                .unwrap()
+                .allow_base_url_edit
+        );
+    }
+
+    #[test]
+    fn conflicts_with_existing_items_fail_the_whole_import() {
+        let mut settings = get_default_settings();
+        settings.snippets = vec![Snippet {
+            id: "sig".to_string(),
+            trigger: "insert my signature".to_string(),
+            body: "Ada".to_string(),
+            enabled: true,
+        }];
+        let pack = ConfigPack {
+            packs: vec![jargon("infra", &["Terraform"])],
+            snippets: vec![Snippet {
+                id: "team_sig".to_string(),
+                trigger: "Insert my signature".to_string(),
+                body: "Team".to_string(),
+                enabled: true,
+            }],
+            ..Default::default()
+        };
+        assert!(import(&settings, pack, |_| ImportStrategy::Replace).is_err());
+    }
+
+    #[test]
+    fn correction_variants_round_trip() {
+        let mut settings = get_default_settings();
+        let plain = JargonCorrection::new("cube control", "kubectl");
+        let near = JargonCorrection {
+            near: vec!["game".to_string()],
+            ..JargonCorrection::new("cube control", "Cube Control")
+        };
+        settings.jargon_custom_corrections = vec![plain.clone(), near.clone()];
+
+        let json = serde_json::to_string(&export(&settings)).unwrap();
+        let pack = parse(&json).unwrap();
+        assert_eq!(pack.corrections.len(), 2);
+
+        let mut existing = get_default_settings();
+        existing.jargon_custom_corrections = vec![JargonCorrection::new("cube control", "cubectl")];
+        let (updated, summary) = import(&existing, pack, |_| ImportStrategy::Merge).unwrap();
+        assert_eq!(summary.merged, 1);
+        assert_eq!(summary.added, 1);
+        let corrections = &updated.jargon_custom_corrections;
+        assert_eq!(corrections.len(), 2);
+        assert!(corrections
+            .iter()
+            .any(|c| c.variant_key() == plain.variant_key() && c.to == "kubectl"));
+        assert!(corrections
+            .iter()
+            .any(|c| c.variant_key() == near.variant_key() && c.to == "Cube Control"));
+    }
+
+    #[test]
+    fn export_round_trips_without_api_keys() {
+        let mut settings = get_default_settings();
+        settings.jargon_packs = vec![jargon("infra", &["Terraform"])];
+        settings
+            .post_process_api_keys
+            .insert("custom".to_string(), "secret-key".to_string());
+        settings
+            .post_process_models
+            .insert("custom".to_string(), "llama3".to_string());
+
+        let json = serde_json::to_string(&export(&settings)).unwrap();
+        assert!(!json.contains("secret-key"));
+        assert!(!json.contains("\"openai\""));
+
+        let pack = parse(&json).unwrap();
+        assert_eq!(
+            pack.provider_models.get("custom"),
+            Some(&"llama3".to_string())
+        );
+        let (updated, _) =
+            import(&get_default_settings(), pack, |_| ImportStrategy::Replace).unwrap();
+        assert_eq!(updated.jargon_packs.len(), 1);
+        assert_eq!(updated.jargon_packs[0].terms, vec!["Terraform"]);
+        assert_eq!(
+            updated.post_process_models.get("custom"),
+            Some(&"llama3".to_string())
+        );
+        assert_eq!(
+            updated.post_process_api_keys.get("custom"),
+            Some(&String::new())
+        );
+    }
+}
@@ -155,6 +155,115 @@ pub fn update_mru(app: &tauri::AppHandle, workspace_root: &std::path::Path) {
     crate::settings::write_settings(app, settings);
 }
 
+/// Identifies the app (macOS) or window (Windows, X11) that has keyboard
+/// focus, so callers can tell whether focus moved between two points in time.
+/// Returns `None` where this cannot be determined, e.g. on Wayland.
+#[cfg(target_os = "macos")]
+pub fn focused_app_id() -> Option<String> {
+    get_frontmost_app_bundle_id()
+}
+
+#[cfg(target_os = "windows")]
+pub fn focused_app_id() -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+    Some(format!("{:p}", hwnd.0))
+}
+
+#[cfg(target_os = "linux")]
+pub fn focused_app_id() -> Option<String> {
+    if crate::utils::is_wayland() {
+        return None;
+    }
+    let output = std::process::Command::new("xdotool")
+        .arg("getactivewindow")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!id.is_empty()).then_some(id)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn focused_app_id() -> Option<String> {
+    None
+}
+
+/// Names the frontmost application so settings can refer to it: the bundle
+/// id on macOS, the executable name on Windows and the window class on X11.
+/// Returns `None` where this cannot be determined, e.g. on Wayland.
+#[cfg(target_os = "macos")]
+pub fn frontmost_app_name() -> Option<String> {
+    get_frontmost_app_bundle_id()
+}
+
+#[cfg(target_os = "windows")]
+pub fn frontmost_app_name() -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+    let mut pid = 0u32;
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+    if pid == 0 {
+        return None;
+    }
+
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let mut buffer = [0u16; 1024];
+    let mut len = buffer.len() as u32;
+    let queried = unsafe {
+        QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut len,
+        )
+    };
+    let _ = unsafe { CloseHandle(process) };
+    queried.ok()?;
+
+    let path = String::from_utf16_lossy(&buffer[..len as usize]);
+    std::path::Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "linux")]
+pub fn frontmost_app_name() -> Option<String> {
+    if crate::utils::is_wayland() {
+        return None;
+    }
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowclassname"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn frontmost_app_name() -> Option<String> {
+    None
+}
+
 #[cfg(not(target_os = "macos"))]
 pub fn get_workspace_root(_settings: &AppSettings) -> Option<PathBuf> {
     None
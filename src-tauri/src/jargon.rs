@@ -1,17 +1,107 @@
+use crate::jargon_sources::JargonScope;
+use crate::phonetic::PhoneticIndex;
+use crate::settings::JargonPack;
+use aho_corasick::AhoCorasick;
 use log::warn;
+use once_cell::sync::{Lazy, OnceCell};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
-#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+/// Words either side of a match that count as its neighbours.
+const NEIGHBOR_WINDOW: usize = 6;
+
+/// Built-in profile for dictating code.
+pub const CODING_PROFILE_ID: &str = "coding";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Type)]
 pub struct JargonCorrection {
     pub from: String,
     pub to: String,
+    /// Only apply when one of these words is a neighbour; empty means always.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub near: Vec<String>,
+    /// Never apply when one of these words is a neighbour.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_near: Vec<String>,
+    /// Match `from` with its exact casing instead of ignoring case.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub case_sensitive: bool,
+    /// Only apply when `from` is all that was said.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub whole_utterance: bool,
+}
+
+impl JargonCorrection {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the correction depends on more than the phrase being said.
+    pub fn is_conditional(&self) -> bool {
+        !self.near.is_empty()
+            || !self.not_near.is_empty()
+            || self.case_sensitive
+            || self.whole_utterance
+    }
+
+    /// Identifies the variant of a phrase this correction is: corrections of
+    /// the same phrase with different conditions are kept side by side, and
+    /// one only overrides another with the same key.
+    pub(crate) fn variant_key(&self) -> (String, bool, Vec<String>, Vec<String>, bool) {
+        let words = |words: &[String]| {
+            let mut words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+            words.sort();
+            words.dedup();
+            words
+        };
+        let from = if self.case_sensitive {
+            self.from.clone()
+        } else {
+            self.from.to_lowercase()
+        };
+        (
+            from,
+            self.case_sensitive,
+            words(&self.near),
+            words(&self.not_near),
+            self.whole_utterance,
+        )
+    }
+
+    /// Trimmed copy without blank neighbour words, or `None` if `from` or
+    /// `to` is blank.
+    pub fn sanitized(&self) -> Option<Self> {
+        let words = |words: &[String]| -> Vec<String> {
+            words
+                .iter()
+                .map(|word| word.trim().to_string())
+                .filter(|word| !word.is_empty())
+                .collect()
+        };
+        let from = self.from.trim();
+        let to = self.to.trim();
+        if from.is_empty() || to.is_empty() {
+            return None;
+        }
+        Some(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            near: words(&self.near),
+            not_near: words(&self.not_near),
+            ..self.clone()
+        })
+    }
 }
 
 #[derive(Serialize, Debug, Clone, Type)]
@@ -25,6 +115,9 @@ pub struct JargonSettings {
     pub enabled_profiles: Vec<String>,
     pub custom_terms: Vec<String>,
     pub custom_corrections: Vec<JargonCorrection>,
+    /// Loaded external sources, highest priority first. They rank below
+    /// custom entries and above profiles.
+    pub source_profiles: Vec<JargonProfile>,
 }
 
 pub struct ActiveDictionary {
@@ -83,38 +176,14 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "next js".into(),
-                    to: "Next.js".into(),
-                },
-                JargonCorrection {
-                    from: "post gres".into(),
-                    to: "PostgreSQL".into(),
-                },
-                JargonCorrection {
-                    from: "type script".into(),
-                    to: "TypeScript".into(),
-                },
-                JargonCorrection {
-                    from: "java script".into(),
-                    to: "JavaScript".into(),
-                },
-                JargonCorrection {
-                    from: "web socket".into(),
-                    to: "WebSocket".into(),
-                },
-                JargonCorrection {
-                    from: "graph QL".into(),
-                    to: "GraphQL".into(),
-                },
-                JargonCorrection {
-                    from: "tail wind".into(),
-                    to: "Tailwind".into(),
-                },
-                JargonCorrection {
-                    from: "web pack".into(),
-                    to: "Webpack".into(),
-                },
+                JargonCorrection::new("next js", "Next.js"),
+                JargonCorrection::new("post gres", "PostgreSQL"),
+                JargonCorrection::new("type script", "TypeScript"),
+                JargonCorrection::new("java script", "JavaScript"),
+                JargonCorrection::new("web socket", "WebSocket"),
+                JargonCorrection::new("graph QL", "GraphQL"),
+                JargonCorrection::new("tail wind", "Tailwind"),
+                JargonCorrection::new("web pack", "Webpack"),
             ],
         },
     );
@@ -156,34 +225,13 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "I two C".into(),
-                    to: "I2C".into(),
-                },
-                JargonCorrection {
-                    from: "risk five".into(),
-                    to: "RISC-V".into(),
-                },
-                JargonCorrection {
-                    from: "S T M 32".into(),
-                    to: "STM32".into(),
-                },
-                JargonCorrection {
-                    from: "E S P 32".into(),
-                    to: "ESP32".into(),
-                },
-                JargonCorrection {
-                    from: "you art".into(),
-                    to: "UART".into(),
-                },
-                JargonCorrection {
-                    from: "G P I O".into(),
-                    to: "GPIO".into(),
-                },
-                JargonCorrection {
-                    from: "jay tag".into(),
-                    to: "JTAG".into(),
-                },
+                JargonCorrection::new("I two C", "I2C"),
+                JargonCorrection::new("risk five", "RISC-V"),
+                JargonCorrection::new("S T M 32", "STM32"),
+                JargonCorrection::new("E S P 32", "ESP32"),
+                JargonCorrection::new("you art", "UART"),
+                JargonCorrection::new("G P I O", "GPIO"),
+                JargonCorrection::new("jay tag", "JTAG"),
             ],
         },
     );
@@ -222,30 +270,12 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "tensor flow".into(),
-                    to: "TensorFlow".into(),
-                },
-                JargonCorrection {
-                    from: "pie torch".into(),
-                    to: "PyTorch".into(),
-                },
-                JargonCorrection {
-                    from: "num pie".into(),
-                    to: "NumPy".into(),
-                },
-                JargonCorrection {
-                    from: "hugging face".into(),
-                    to: "Hugging Face".into(),
-                },
-                JargonCorrection {
-                    from: "sick it learn".into(),
-                    to: "Scikit-learn".into(),
-                },
-                JargonCorrection {
-                    from: "L L M".into(),
-                    to: "LLM".into(),
-                },
+                JargonCorrection::new("tensor flow", "TensorFlow"),
+                JargonCorrection::new("pie torch", "PyTorch"),
+                JargonCorrection::new("num pie", "NumPy"),
+                JargonCorrection::new("hugging face", "Hugging Face"),
+                JargonCorrection::new("sick it learn", "Scikit-learn"),
+                JargonCorrection::new("L L M", "LLM"),
             ],
         },
     );
@@ -282,40 +312,22 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "engine X".into(),
-                    to: "Nginx".into(),
-                },
-                JargonCorrection {
-                    from: "terra form".into(),
-                    to: "Terraform".into(),
-                },
-                JargonCorrection {
-                    from: "cube CTL".into(),
-                    to: "kubectl".into(),
-                },
-                JargonCorrection {
-                    from: "G R P C".into(),
-                    to: "gRPC".into(),
-                },
-                JargonCorrection {
-                    from: "E K S".into(),
-                    to: "EKS".into(),
-                },
-                JargonCorrection {
-                    from: "E C S".into(),
-                    to: "ECS".into(),
-                },
-                JargonCorrection {
-                    from: "E C two".into(),
-                    to: "EC2".into(),
-                },
+                JargonCorrection::new("engine X", "Nginx"),
+                JargonCorrection::new("terra form", "Terraform"),
+                JargonCorrection::new("cube CTL", "kubectl"),
+                // Too far from "kubectl" in sound and spelling for the
+                // phonetic layer, so it needs its own correction.
+                JargonCorrection::new("cube control", "kubectl"),
+                JargonCorrection::new("G R P C", "gRPC"),
+                JargonCorrection::new("E K S", "EKS"),
+                JargonCorrection::new("E C S", "ECS"),
+                JargonCorrection::new("E C two", "EC2"),
             ],
         },
     );
 
     profiles.insert(
-        "coding".to_string(),
+        CODING_PROFILE_ID.to_string(),
         JargonProfile {
             label: "Coding".to_string(),
             terms: vec![
@@ -344,30 +356,12 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "type script".into(),
-                    to: "TypeScript".into(),
-                },
-                JargonCorrection {
-                    from: "java script".into(),
-                    to: "JavaScript".into(),
-                },
-                JargonCorrection {
-                    from: "post gres".into(),
-                    to: "PostgreSQL".into(),
-                },
-                JargonCorrection {
-                    from: "G R P C".into(),
-                    to: "gRPC".into(),
-                },
-                JargonCorrection {
-                    from: "graph Q L".into(),
-                    to: "GraphQL".into(),
-                },
-                JargonCorrection {
-                    from: "pull request".into(),
-                    to: "Pull Request".into(),
-                },
+                JargonCorrection::new("type script", "TypeScript"),
+                JargonCorrection::new("java script", "JavaScript"),
+                JargonCorrection::new("post gres", "PostgreSQL"),
+                JargonCorrection::new("G R P C", "gRPC"),
+                JargonCorrection::new("graph Q L", "GraphQL"),
+                JargonCorrection::new("pull request", "Pull Request"),
             ],
         },
     );
@@ -402,38 +396,14 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "A R R".into(),
-                    to: "ARR".into(),
-                },
-                JargonCorrection {
-                    from: "M R R".into(),
-                    to: "MRR".into(),
-                },
-                JargonCorrection {
-                    from: "K P I".into(),
-                    to: "KPI".into(),
-                },
-                JargonCorrection {
-                    from: "O K R".into(),
-                    to: "OKR".into(),
-                },
-                JargonCorrection {
-                    from: "go to market".into(),
-                    to: "Go-to-market".into(),
-                },
-                JargonCorrection {
-                    from: "R O I".into(),
-                    to: "ROI".into(),
-                },
-                JargonCorrection {
-                    from: "C A C".into(),
-                    to: "CAC".into(),
-                },
-                JargonCorrection {
-                    from: "L T V".into(),
-                    to: "LTV".into(),
-                },
+                JargonCorrection::new("A R R", "ARR"),
+                JargonCorrection::new("M R R", "MRR"),
+                JargonCorrection::new("K P I", "KPI"),
+                JargonCorrection::new("O K R", "OKR"),
+                JargonCorrection::new("go to market", "Go-to-market"),
+                JargonCorrection::new("R O I", "ROI"),
+                JargonCorrection::new("C A C", "CAC"),
+                JargonCorrection::new("L T V", "LTV"),
             ],
         },
     );
@@ -468,30 +438,12 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
             .map(String::from)
             .collect(),
             corrections: vec![
-                JargonCorrection {
-                    from: "B O L O".into(),
-                    to: "BOLO".into(),
-                },
-                JargonCorrection {
-                    from: "miranda rights".into(),
-                    to: "Miranda".into(),
-                },
-                JargonCorrection {
-                    from: "chain of custody".into(),
-                    to: "Chain of Custody".into(),
-                },
-                JargonCorrection {
-                    from: "body cam".into(),
-                    to: "Body Camera".into(),
-                },
-                JargonCorrection {
-                    from: "use of force".into(),
-                    to: "Use of Force".into(),
-                },
-                JargonCorrection {
-                    from: "de escalation".into(),
-                    to: "De-escalation".into(),
-                },
+                JargonCorrection::new("B O L O", "BOLO"),
+                JargonCorrection::new("miranda rights", "Miranda"),
+                JargonCorrection::new("chain of custody", "Chain of Custody"),
+                JargonCorrection::new("body cam", "Body Camera"),
+                JargonCorrection::new("use of force", "Use of Force"),
+                JargonCorrection::new("de escalation", "De-escalation"),
             ],
         },
     );
@@ -499,6 +451,23 @@ pub fn builtin_profiles() -> HashMap<String, JargonProfile> {
     profiles
 }
 
+/// Built-in profiles plus user packs, keyed by id. A pack with a built-in
+/// id replaces that profile.
+pub fn profiles_with_packs(packs: &[JargonPack]) -> HashMap<String, JargonProfile> {
+    let mut profiles = builtin_profiles();
+    for pack in packs {
+        profiles.insert(
+            pack.id.clone(),
+            JargonProfile {
+                label: pack.label.clone(),
+                terms: pack.terms.clone(),
+                corrections: pack.corrections.clone(),
+            },
+        );
+    }
+    profiles
+}
+
 // ============================================================================
 // Active Dictionary Computation
 // ============================================================================
@@ -507,7 +476,7 @@ pub fn compute_active_dictionary(
     settings: &JargonSettings,
     profiles: &HashMap<String, JargonProfile>,
 ) -> ActiveDictionary {
-    // Collect terms: custom first, then profiles in alphabetical order
+    // Collect terms: custom first, then sources, then profiles in alphabetical order
     let mut terms_map: HashMap<String, String> = HashMap::new();
 
     // Add custom terms first (they win on casing)
@@ -515,6 +484,15 @@ pub fn compute_active_dictionary(
         terms_map.insert(term.to_lowercase(), term.clone());
     }
 
+    // Then external sources in priority order
+    for source in &settings.source_profiles {
+        for term in &source.terms {
+            terms_map
+                .entry(term.to_lowercase())
+                .or_insert_with(|| term.clone());
+        }
+    }
+
     // Add profile terms in alphabetical order by profile id
     let mut profile_ids: Vec<&String> = settings
         .enabled_profiles
@@ -537,7 +515,11 @@ pub fn compute_active_dictionary(
     let mut terms = Vec::new();
     let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
 
-    for term in &settings.custom_terms {
+    let source_terms = settings
+        .source_profiles
+        .iter()
+        .flat_map(|source| source.terms.iter());
+    for term in settings.custom_terms.iter().chain(source_terms) {
         let key = term.to_lowercase();
         if seen.insert(key.clone()) {
             if let Some(t) = terms_map.get(&key) {
@@ -558,35 +540,113 @@ pub fn compute_active_dictionary(
         }
     }
 
-    // Collect corrections: custom corrections override profile corrections
-    let mut corrections_map: HashMap<String, JargonCorrection> = HashMap::new();
+    // Collect corrections: custom corrections override profile corrections.
+    // Only the same variant of a phrase is overridden; conditional variants
+    // of one phrase all stay.
+    let mut corrections_map = std::collections::BTreeMap::new();
 
     // Add profile corrections first
     for profile_id in &profile_ids {
         if let Some(profile) = profiles.get(profile_id.as_str()) {
             for correction in &profile.corrections {
-                corrections_map.insert(correction.from.to_lowercase(), correction.clone());
+                corrections_map.insert(correction.variant_key(), correction.clone());
             }
         }
     }
 
-    // Custom corrections override profile corrections
+    // Sources override profiles; earlier sources override later ones
+    for source in settings.source_profiles.iter().rev() {
+        for correction in &source.corrections {
+            corrections_map.insert(correction.variant_key(), correction.clone());
+        }
+    }
+
+    // Custom corrections override profile and source corrections
     for correction in &settings.custom_corrections {
-        corrections_map.insert(correction.from.to_lowercase(), correction.clone());
+        corrections_map.insert(correction.variant_key(), correction.clone());
     }
 
-    // Sort corrections longest-phrase-first
+    // Sort corrections longest-phrase-first. For one phrase, conditional
+    // variants go first so they win over the unconditional one when they hold.
     let mut corrections: Vec<JargonCorrection> = corrections_map.into_values().collect();
     corrections.sort_by(|a, b| {
         b.from
             .len()
             .cmp(&a.from.len())
-            .then_with(|| a.from.cmp(&b.from))
+            .then_with(|| a.from.to_lowercase().cmp(&b.from.to_lowercase()))
+            .then_with(|| b.is_conditional().cmp(&a.is_conditional()))
     });
 
     ActiveDictionary { terms, corrections }
 }
 
+// ============================================================================
+// Dictionary Cache
+// ============================================================================
+
+/// Everything a cached dictionary was built from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryKey {
+    pub settings_revision: u64,
+    pub sources_revision: u64,
+    pub workspace_revision: u64,
+    /// Effective profile ids, sorted and deduplicated.
+    pub profiles: Vec<String>,
+    pub scope: JargonScope,
+}
+
+/// An active dictionary with its corrections compiled for matching.
+pub struct CompiledDictionary {
+    pub dictionary: ActiveDictionary,
+    pub corrections: CorrectionMatcher,
+    phonetic: OnceCell<PhoneticIndex>,
+}
+
+impl CompiledDictionary {
+    /// The phonetic index over the dictionary and `custom_words`, built on
+    /// first use. Custom words are settings, so the settings revision in the
+    /// cache key already covers them.
+    pub fn phonetic_index(&self, custom_words: &[String]) -> &PhoneticIndex {
+        self.phonetic
+            .get_or_init(|| PhoneticIndex::new(&self.dictionary, custom_words))
+    }
+}
+
+/// ASR and post-processing ask for different scopes, and the domain
+/// selector can flip between a couple of profile sets.
+const DICTIONARY_CACHE_SIZE: usize = 4;
+
+type CacheEntry = (DictionaryKey, Arc<CompiledDictionary>);
+
+static DICTIONARY_CACHE: Lazy<Mutex<Vec<CacheEntry>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Return the dictionary cached under `key`, or `build` and compile it.
+pub fn cached_dictionary(
+    key: DictionaryKey,
+    build: impl FnOnce() -> ActiveDictionary,
+) -> Arc<CompiledDictionary> {
+    let mut cache = DICTIONARY_CACHE.lock().unwrap();
+    if let Some(position) = cache.iter().position(|(cached, _)| cached == &key) {
+        // Move to the back so the least recently used entry goes first.
+        let entry = cache.remove(position);
+        let compiled = entry.1.clone();
+        cache.push(entry);
+        return compiled;
+    }
+
+    let dictionary = build();
+    let compiled = Arc::new(CompiledDictionary {
+        corrections: CorrectionMatcher::new(&dictionary.corrections),
+        dictionary,
+        phonetic: OnceCell::new(),
+    });
+    if cache.len() >= DICTIONARY_CACHE_SIZE {
+        cache.remove(0);
+    }
+    cache.push((key, compiled.clone()));
+    compiled
+}
+
 // ============================================================================
 // Initial Prompt Builder
 // ============================================================================
@@ -630,12 +690,12 @@ pub fn build_initial_prompt(dictionary: &ActiveDictionary) -> String {
 // Protected Span Masking
 // ============================================================================
 
-struct ProtectedSpan {
-    placeholder: String,
-    original: String,
+pub(crate) struct ProtectedSpan {
+    pub(crate) placeholder: String,
+    pub(crate) original: String,
 }
 
-fn mask_protected_spans(text: &str) -> (String, Vec<ProtectedSpan>) {
+pub(crate) fn mask_protected_spans(text: &str) -> (String, Vec<ProtectedSpan>) {
     let patterns = [
         r"@[\w\-./]+",                         // @tokens like @file.rs
         r"`[^`]+`",                            // backtick code
@@ -667,7 +727,7 @@ fn mask_protected_spans(text: &str) -> (String, Vec<ProtectedSpan>) {
     (masked, spans)
 }
 
-fn restore_protected_spans(text: &str, spans: &[ProtectedSpan]) -> String {
+pub(crate) fn restore_protected_spans(text: &str, spans: &[ProtectedSpan]) -> String {
     let mut result = text.to_string();
     for span in spans {
         result = result.replace(&span.placeholder, &span.original);
@@ -679,37 +739,193 @@ fn restore_protected_spans(text: &str, spans: &[ProtectedSpan]) -> String {
 // Correction Application
 // ============================================================================
 
-pub fn apply_corrections(text: &str, corrections: &[JargonCorrection]) -> String {
-    if corrections.is_empty() || text.is_empty() {
-        return text.to_string();
-    }
+/// All corrections compiled into one Aho-Corasick automaton.
+///
+/// Phrases match case-insensitively (Unicode lowercase) on word boundaries,
+/// unless the correction is case-sensitive. A match whose conditions
+/// (neighbour words, whole utterance) do not hold is dropped before overlaps
+/// are resolved.
+/// Where matches overlap, the longer phrase wins (ties go to the earlier
+/// correction), as if each correction were applied in turn, longest first.
+/// Text inserted by a correction is never matched again.
+pub struct CorrectionMatcher {
+    automaton: Option<AhoCorasick>,
+    /// Correction per pattern id, longest first, with lowercase neighbour
+    /// words.
+    corrections: Vec<JargonCorrection>,
+}
 
-    // Mask protected spans
-    let (mut masked, spans) = mask_protected_spans(text);
+impl CorrectionMatcher {
+    pub fn new(corrections: &[JargonCorrection]) -> Self {
+        let mut ordered: Vec<&JargonCorrection> = corrections
+            .iter()
+            .filter(|correction| !correction.from.is_empty())
+            .collect();
+        ordered.sort_by(|a, b| b.from.len().cmp(&a.from.len()));
 
-    // Apply corrections (longest first, already sorted)
-    for correction in corrections {
-        let pattern = format!(r"(?i)\b{}\b", regex::escape(&correction.from));
-        if let Ok(re) = Regex::new(&pattern) {
-            masked = re.replace_all(&masked, correction.to.as_str()).to_string();
+        let automaton = if ordered.is_empty() {
+            None
+        } else {
+            AhoCorasick::new(
+                ordered
+                    .iter()
+                    .map(|correction| lowercase_with_offsets(&correction.from).0),
+            )
+            .map_err(|e| warn!("Failed to compile jargon corrections: {}", e))
+            .ok()
+        };
+        let lowercase = |words: &[String]| words.iter().map(|w| w.to_lowercase()).collect();
+        let corrections = ordered
+            .into_iter()
+            .map(|correction| JargonCorrection {
+                near: lowercase(&correction.near),
+                not_near: lowercase(&correction.not_near),
+                ..correction.clone()
+            })
+            .collect();
+        Self {
+            automaton,
+            corrections,
         }
     }
 
-    // Restore protected spans
-    let restored = restore_protected_spans(&masked, &spans);
+    pub fn is_empty(&self) -> bool {
+        self.automaton.is_none()
+    }
 
-    // Safety check: verify all placeholders were restored
-    for span in &spans {
-        if restored.contains(&span.placeholder) {
-            warn!(
-                "Placeholder {} was not properly restored, returning original text",
-                span.placeholder
-            );
+    /// Apply every correction in one pass, leaving protected spans alone.
+    pub fn apply(&self, text: &str) -> String {
+        if self.is_empty() || text.is_empty() {
             return text.to_string();
         }
+
+        let (masked, spans) = mask_protected_spans(text);
+        let restored = restore_protected_spans(&self.replace(&masked), &spans);
+
+        // Safety check: verify all placeholders were restored
+        for span in &spans {
+            if restored.contains(&span.placeholder) {
+                warn!(
+                    "Placeholder {} was not properly restored, returning original text",
+                    span.placeholder
+                );
+                return text.to_string();
+            }
+        }
+
+        restored
+    }
+
+    fn replace(&self, text: &str) -> String {
+        let Some(automaton) = &self.automaton else {
+            return text.to_string();
+        };
+
+        // (pattern, start, end) in `text`, sorted by priority, then position
+        let (lowered, offsets) = lowercase_with_offsets(text);
+        let mut found: Vec<(usize, usize, usize)> = automaton
+            .find_overlapping_iter(&lowered)
+            .filter_map(|m| {
+                Some((
+                    m.pattern().as_usize(),
+                    offsets[m.start()]?,
+                    offsets[m.end()]?,
+                ))
+            })
+            .filter(|&(_, start, end)| is_word_boundary(text, start) && is_word_boundary(text, end))
+            .filter(|&(pattern, start, end)| self.conditions_hold(text, pattern, start, end))
+            .collect();
+        found.sort_unstable();
+
+        let mut chosen: Vec<(usize, usize, usize)> = Vec::new();
+        for (pattern, start, end) in found {
+            if chosen.iter().all(|&(_, s, e)| end <= s || start >= e) {
+                chosen.push((pattern, start, end));
+            }
+        }
+        chosen.sort_unstable_by_key(|&(_, start, _)| start);
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for (pattern, start, end) in chosen {
+            result.push_str(&text[last..start]);
+            result.push_str(&self.corrections[pattern].to);
+            last = end;
+        }
+        result.push_str(&text[last..]);
+        result
     }
 
-    restored
+    fn conditions_hold(&self, text: &str, pattern: usize, start: usize, end: usize) -> bool {
+        let correction = &self.corrections[pattern];
+        if correction.case_sensitive && text[start..end] != correction.from {
+            return false;
+        }
+        if correction.whole_utterance && !(is_blank(&text[..start]) && is_blank(&text[end..])) {
+            return false;
+        }
+        if correction.near.is_empty() && correction.not_near.is_empty() {
+            return true;
+        }
+
+        let neighbors = neighbor_words(text, start, end);
+        let is_neighbor = |word: &String| neighbors.contains(word);
+        (correction.near.is_empty() || correction.near.iter().any(is_neighbor))
+            && !correction.not_near.iter().any(is_neighbor)
+    }
+}
+
+/// `text` lowercased, with the offset in `text` of each lowercased byte that
+/// starts a character's lowercase form (`None` inside one). Lowercasing can
+/// change a character's length ("ẞ" -> "ß"), so matches are mapped back.
+fn lowercase_with_offsets(text: &str) -> (String, Vec<Option<usize>>) {
+    let mut lowered = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (i, c) in text.char_indices() {
+        offsets.push(Some(i));
+        lowered.extend(c.to_lowercase());
+        offsets.resize(lowered.len(), None);
+    }
+    offsets.push(Some(text.len()));
+    (lowered, offsets)
+}
+
+/// Whether `text` has no word characters, only spaces and punctuation.
+fn is_blank(text: &str) -> bool {
+    !text.chars().any(is_word_char)
+}
+
+/// Up to `NEIGHBOR_WINDOW` lowercase words on each side of `start..end`.
+fn neighbor_words(text: &str, start: usize, end: usize) -> Vec<String> {
+    let words = |text: &str| {
+        text.split(|c: char| !is_word_char(c))
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+    };
+    let before = words(&text[..start]);
+    let after = words(&text[end..]);
+    before
+        .into_iter()
+        .rev()
+        .take(NEIGHBOR_WINDOW)
+        .chain(after.into_iter().take(NEIGHBOR_WINDOW))
+        .collect()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Same rule as regex `\b`: a word character on exactly one side.
+fn is_word_boundary(text: &str, at: usize) -> bool {
+    let before = text[..at].chars().next_back().is_some_and(is_word_char);
+    let after = text[at..].chars().next().is_some_and(is_word_char);
+    before != after
+}
+
+pub fn apply_corrections(text: &str, corrections: &[JargonCorrection]) -> String {
+    CorrectionMatcher::new(corrections).apply(text)
 }
 
 // ============================================================================
@@ -730,11 +946,9 @@ mod tests {
             custom_terms: terms.into_iter().map(String::from).collect(),
             custom_corrections: corrections
                 .into_iter()
-                .map(|(f, t)| JargonCorrection {
-                    from: f.to_string(),
-                    to: t.to_string(),
-                })
+                .map(|(f, t)| JargonCorrection::new(f, t))
                 .collect(),
+            source_profiles: Vec::new(),
         }
     }
 
@@ -748,6 +962,16 @@ mod tests {
         assert!(dict.terms.iter().any(|t| t == "Terraform"));
     }
 
+    #[test]
+    fn test_devops_profile_correction_handles_cube_control() {
+        let settings = make_settings(vec!["devops"], vec![], vec![]);
+        let dict = compute_active_dictionary(&settings, &builtin_profiles());
+        assert_eq!(
+            apply_corrections("run cube control get pods", &dict.corrections),
+            "run kubectl get pods"
+        );
+    }
+
     #[test]
     fn test_correction_override_priority() {
         let profiles = builtin_profiles();
@@ -762,6 +986,32 @@ mod tests {
         assert_eq!(correction.unwrap().to, "NextJS");
     }
 
+    #[test]
+    fn test_source_priority() {
+        let profiles = builtin_profiles();
+        let source = |terms: &[&str], from: &str, to: &str| JargonProfile {
+            label: "Source".to_string(),
+            terms: terms.iter().map(|t| t.to_string()).collect(),
+            corrections: vec![JargonCorrection::new(from, to)],
+        };
+        let mut settings = make_settings(vec!["web_dev"], vec!["graphql"], vec![]);
+        settings.source_profiles = vec![
+            source(&["GraphQL", "typescript"], "next js", "NextJS"),
+            source(&["TypeScript!"], "next js", "Next"),
+        ];
+        let dict = compute_active_dictionary(&settings, &profiles);
+        // Custom casing wins, then the first source, then the profile
+        assert_eq!(dict.terms[0], "graphql");
+        assert_eq!(dict.terms[1], "typescript");
+        assert!(!dict.terms.iter().any(|t| t == "TypeScript"));
+        let correction = dict
+            .corrections
+            .iter()
+            .find(|c| c.from == "next js")
+            .unwrap();
+        assert_eq!(correction.to, "NextJS");
+    }
+
     #[test]
     fn test_case_insensitive_dedup() {
         let profiles = builtin_profiles();
@@ -781,10 +1031,7 @@ mod tests {
     fn test_protected_span_at_refs() {
         let result = apply_corrections(
             "Check @file.rs for type script code",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert!(result.contains("@file.rs"));
         assert!(result.contains("TypeScript"));
@@ -794,10 +1041,7 @@ mod tests {
     fn test_protected_span_backticks() {
         let result = apply_corrections(
             "Run `type script build` with type script",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert!(result.contains("`type script build`"));
         assert!(result.contains("TypeScript"));
@@ -807,10 +1051,7 @@ mod tests {
     fn test_protected_span_urls() {
         let result = apply_corrections(
             "Visit https://type-script.org for type script docs",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert!(result.contains("https://type-script.org"));
         assert!(result.contains("TypeScript"));
@@ -820,10 +1061,7 @@ mod tests {
     fn test_protected_span_paths() {
         let result = apply_corrections(
             "Open /usr/local/bin/app and type script",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert!(result.contains("/usr/local/bin/app"));
         assert!(result.contains("TypeScript"));
@@ -833,10 +1071,7 @@ mod tests {
     fn test_protected_span_cli_flags() {
         let result = apply_corrections(
             "Use --verbose and type script",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert!(result.contains("--verbose"));
         assert!(result.contains("TypeScript"));
@@ -847,10 +1082,7 @@ mod tests {
         // "script" alone should not be replaced by a "type script" correction
         let result = apply_corrections(
             "This script is good",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert_eq!(result, "This script is good");
     }
@@ -893,15 +1125,35 @@ mod tests {
         assert_eq!(dict.corrections[1].from, "E C");
     }
 
+    #[test]
+    fn test_conditional_variants_of_one_phrase_are_kept() {
+        let profiles = builtin_profiles();
+        let mut settings = make_settings(vec![], vec![], vec![("us", "us all")]);
+        let mut us = JargonCorrection::new("US", "U.S.");
+        us.case_sensitive = true;
+        let mut near_ci = JargonCorrection::new("us", "US");
+        near_ci.near = vec!["pipeline".to_string()];
+        let mut near_cd = JargonCorrection::new("us", "Us");
+        near_cd.near = vec!["band".to_string()];
+        settings.custom_corrections.extend([us, near_ci, near_cd]);
+        // Same variant as the custom one: overridden, not added.
+        settings
+            .custom_corrections
+            .push(JargonCorrection::new("Us", "us too"));
+
+        let dict = compute_active_dictionary(&settings, &profiles);
+        assert_eq!(dict.corrections.len(), 4);
+        assert!(!dict.corrections.last().unwrap().is_conditional());
+        let apply = |text: &str| apply_corrections(text, &dict.corrections);
+        assert_eq!(apply("the US team"), "the U.S. team");
+        assert_eq!(apply("run the us pipeline"), "run the US pipeline");
+        assert_eq!(apply("the us band"), "the Us band");
+        assert_eq!(apply("thanks from us"), "thanks from us too");
+    }
+
     #[test]
     fn test_empty_input() {
-        let result = apply_corrections(
-            "",
-            &[JargonCorrection {
-                from: "test".into(),
-                to: "Test".into(),
-            }],
-        );
+        let result = apply_corrections("", &[JargonCorrection::new("test", "Test")]);
         assert_eq!(result, "");
     }
 
@@ -915,32 +1167,130 @@ mod tests {
     fn test_case_insensitive_correction() {
         let result = apply_corrections(
             "I use Type Script and TYPE SCRIPT",
-            &[JargonCorrection {
-                from: "type script".into(),
-                to: "TypeScript".into(),
-            }],
+            &[JargonCorrection::new("type script", "TypeScript")],
         );
         assert_eq!(result, "I use TypeScript and TypeScript");
     }
 
+    #[test]
+    fn test_non_ascii_case_insensitive_correction() {
+        let result = apply_corrections(
+            "Die STRAẞE zur ÉCOLE, dann école.",
+            &[
+                JargonCorrection::new("straße", "Straße"),
+                JargonCorrection::new("école", "École"),
+            ],
+        );
+        assert_eq!(result, "Die Straße zur École, dann École.");
+    }
+
     #[test]
     fn test_multiple_corrections() {
         let result = apply_corrections(
             "I use type script with next js",
             &[
-                JargonCorrection {
-                    from: "type script".into(),
-                    to: "TypeScript".into(),
-                },
-                JargonCorrection {
-                    from: "next js".into(),
-                    to: "Next.js".into(),
-                },
+                JargonCorrection::new("type script", "TypeScript"),
+                JargonCorrection::new("next js", "Next.js"),
             ],
         );
         assert_eq!(result, "I use TypeScript with Next.js");
     }
 
+    #[test]
+    fn test_overlapping_corrections_prefer_longest() {
+        let corrections = [
+            JargonCorrection::new("E C", "EC"),
+            JargonCorrection::new("E C two", "EC2"),
+            JargonCorrection::new("two", "2"),
+        ];
+        assert_eq!(
+            apply_corrections("spin up E C two and E C, then two more", &corrections),
+            "spin up EC2 and EC, then 2 more"
+        );
+    }
+
+    #[test]
+    fn test_corrections_need_word_boundaries_and_insert_literally() {
+        let corrections = [
+            JargonCorrection::new("home dir", "$HOME"),
+            JargonCorrection::new("ops", "Ops"),
+        ];
+        assert_eq!(
+            apply_corrections("cd to home dir for devops, ops", &corrections),
+            "cd to $HOME for devops, Ops"
+        );
+    }
+
+    #[test]
+    fn test_corrections_follow_neighbor_words() {
+        let mut rust = JargonCorrection::new("rust", "Rust");
+        rust.near = vec!["Write".to_string(), "compiler".to_string()];
+        rust.not_near = vec!["car".to_string()];
+        let corrections = [rust];
+        assert_eq!(
+            apply_corrections("write it in rust please", &corrections),
+            "write it in Rust please"
+        );
+        assert_eq!(
+            apply_corrections("there is rust on the car", &corrections),
+            "there is rust on the car"
+        );
+        assert_eq!(
+            apply_corrections("write about the rust on my car", &corrections),
+            "write about the rust on my car"
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive_and_whole_utterance_corrections() {
+        let mut us = JargonCorrection::new("US", "U.S.");
+        us.case_sensitive = true;
+        let mut next = JargonCorrection::new("next", "Next slide");
+        next.whole_utterance = true;
+        let corrections = [us, next];
+        assert_eq!(
+            apply_corrections("tell us about the US market", &corrections),
+            "tell us about the U.S. market"
+        );
+        assert_eq!(apply_corrections(" Next. ", &corrections), " Next slide. ");
+        assert_eq!(
+            apply_corrections("the next one", &corrections),
+            "the next one"
+        );
+    }
+
+    #[test]
+    fn test_corrections_without_conditions_stay_compact() {
+        let old: JargonCorrection =
+            serde_json::from_str(r#"{"from": "post gres", "to": "PostgreSQL"}"#).unwrap();
+        assert!(!old.is_conditional());
+        assert_eq!(
+            serde_json::to_string(&old).unwrap(),
+            r#"{"from":"post gres","to":"PostgreSQL"}"#
+        );
+    }
+
+    #[test]
+    fn test_dictionary_cache_reuses_until_key_changes() {
+        let key = |revision| DictionaryKey {
+            settings_revision: revision,
+            sources_revision: 0,
+            workspace_revision: 0,
+            profiles: vec!["cache_test".to_string()],
+            scope: JargonScope::default(),
+        };
+        let build = || ActiveDictionary {
+            terms: vec!["Kafka".to_string()],
+            corrections: vec![],
+        };
+
+        let first = cached_dictionary(key(u64::MAX), build);
+        let second = cached_dictionary(key(u64::MAX), || panic!("should be cached"));
+        assert!(Arc::ptr_eq(&first, &second));
+        let third = cached_dictionary(key(u64::MAX - 1), build);
+        assert!(!Arc::ptr_eq(&first, &third));
+    }
+
     #[test]
     fn test_empty_dictionary_prompt() {
         let dict = ActiveDictionary {
@@ -0,0 +1,300 @@
+//! Learns jargon from the user's edits of history entries.
+//!
+//! The original transcription is diffed word by word against the edit, and
+//! each short substituted span becomes a proposal: a custom word when only
+//! the spelling changed ("github" -> "GitHub"), or a correction when the
+//! replacement sounds like what was recognized ("cube control" ->
+//! "kubectl"). Rewordings that sound different are not proposed.
+//!
+//! Accepted proposals go into the `learned` jargon pack. Rejected ones are
+//! kept in the settings so they are not proposed again.
+
+use crate::jargon::JargonCorrection;
+use crate::phonetic::sound_similarity;
+use crate::settings::{AppSettings, JargonPack};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+pub const LEARNED_PACK_ID: &str = "learned";
+const LEARNED_PACK_LABEL: &str = "Learned";
+
+/// Longer substitutions are rewrites, not misrecognitions.
+const MAX_SPAN_WORDS: usize = 4;
+const MAX_SPAN_CHARS: usize = 40;
+/// How alike a correction's two sides must sound.
+const MIN_SOUND_SIMILARITY: f64 = 0.5;
+/// Oldest rejections are forgotten past this many.
+const MAX_REJECTED: usize = 500;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalKind {
+    /// Replace `from` with `to` in future transcriptions.
+    Correction,
+    /// Add `to` as a term, spelled the way it was typed.
+    CustomWord,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct JargonProposal {
+    pub kind: ProposalKind,
+    /// The span as it was transcribed.
+    pub from: String,
+    /// The span as the user edited it.
+    pub to: String,
+}
+
+impl JargonProposal {
+    fn same_as(&self, other: &JargonProposal) -> bool {
+        self.kind == other.kind
+            && self.from.eq_ignore_ascii_case(&other.from)
+            && self.to == other.to
+    }
+}
+
+/// Proposals for the spans substituted between `original` and `edited`.
+pub fn propose(original: &str, edited: &str) -> Vec<JargonProposal> {
+    let old: Vec<&str> = original.split_whitespace().collect();
+    let new: Vec<&str> = edited.split_whitespace().collect();
+    let ops = similar::capture_diff_slices(similar::Algorithm::Patience, &old, &new);
+
+    let mut proposals: Vec<JargonProposal> = Vec::new();
+    for op in ops {
+        let similar::DiffOp::Replace {
+            old_index,
+            old_len,
+            new_index,
+            new_len,
+        } = op
+        else {
+            continue;
+        };
+        if old_len > MAX_SPAN_WORDS || new_len > MAX_SPAN_WORDS {
+            continue;
+        }
+        let from = trim_span(&old[old_index..old_index + old_len]);
+        let to = trim_span(&new[new_index..new_index + new_len]);
+        let starts_sentence =
+            old_index == 0 || old[old_index - 1].ends_with(|c: char| matches!(c, '.' | '!' | '?'));
+        if let Some(proposal) = proposal_for(from, to, starts_sentence) {
+            if !proposals.iter().any(|p| p.same_as(&proposal)) {
+                proposals.push(proposal);
+            }
+        }
+    }
+    proposals
+}
+
+/// Join a span and strip the sentence punctuation around it.
+fn trim_span(words: &[&str]) -> String {
+    words
+        .join(" ")
+        .trim_start_matches(['"', '\'', '(', '['])
+        .trim_end_matches(['.', ',', ';', ':', '!', '?', '"', '\'', ')', ']'])
+        .to_string()
+}
+
+fn proposal_for(from: String, to: String, starts_sentence: bool) -> Option<JargonProposal> {
+    if from.is_empty()
+        || to.is_empty()
+        || from == to
+        || from.chars().count() > MAX_SPAN_CHARS
+        || to.chars().count() > MAX_SPAN_CHARS
+    {
+        return None;
+    }
+
+    let kind = if letters(&from) == letters(&to) {
+        // Capitalizing the first word of a sentence is not jargon.
+        let lower = to.to_lowercase();
+        let mut chars = lower.chars();
+        let sentence_case = chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>());
+        if to == lower || (starts_sentence && Some(&to) == sentence_case.as_ref()) {
+            return None;
+        }
+        ProposalKind::CustomWord
+    } else if sound_similarity(&from, &to) >= MIN_SOUND_SIMILARITY {
+        ProposalKind::Correction
+    } else {
+        return None;
+    };
+    Some(JargonProposal { kind, from, to })
+}
+
+/// Lowercase letters and digits only.
+fn letters(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Drop proposals that were rejected before or are already known.
+pub fn filter_known(proposals: Vec<JargonProposal>, settings: &AppSettings) -> Vec<JargonProposal> {
+    let learned = settings
+        .jargon_packs
+        .iter()
+        .find(|pack| pack.id == LEARNED_PACK_ID);
+    let has_correction = |from: &str, to: &str| {
+        settings
+            .jargon_custom_corrections
+            .iter()
+            .chain(learned.iter().flat_map(|pack| &pack.corrections))
+            .any(|c| c.from.eq_ignore_ascii_case(from) && c.to == to)
+    };
+    let has_word = |word: &str| {
+        settings
+            .custom_words
+            .iter()
+            .chain(settings.jargon_custom_terms.iter())
+            .chain(learned.iter().flat_map(|pack| &pack.terms))
+            .any(|known| known == word)
+    };
+
+    proposals
+        .into_iter()
+        .filter(|proposal| {
+            let rejected = settings
+                .jargon_rejected_proposals
+                .iter()
+                .any(|rejected| rejected.same_as(proposal));
+            let known = match proposal.kind {
+                ProposalKind::Correction => has_correction(&proposal.from, &proposal.to),
+                ProposalKind::CustomWord => has_word(&proposal.to),
+            };
+            !rejected && !known
+        })
+        .collect()
+}
+
+/// Add `proposals` to the learned pack, creating and enabling it if needed.
+pub fn accept(settings: &mut AppSettings, proposals: &[JargonProposal]) {
+    let index = match settings
+        .jargon_packs
+        .iter()
+        .position(|pack| pack.id == LEARNED_PACK_ID)
+    {
+        Some(index) => index,
+        None => {
+            settings.jargon_packs.push(JargonPack {
+                id: LEARNED_PACK_ID.to_string(),
+                label: LEARNED_PACK_LABEL.to_string(),
+                terms: Vec::new(),
+                corrections: Vec::new(),
+            });
+            if !settings
+                .jargon_enabled_profiles
+                .iter()
+                .any(|id| id == LEARNED_PACK_ID)
+            {
+                settings
+                    .jargon_enabled_profiles
+                    .push(LEARNED_PACK_ID.to_string());
+            }
+            settings.jargon_packs.len() - 1
+        }
+    };
+
+    let pack = &mut settings.jargon_packs[index];
+    for proposal in proposals {
+        match proposal.kind {
+            ProposalKind::Correction => {
+                let correction = JargonCorrection::new(&proposal.from, &proposal.to);
+                pack.corrections
+                    .retain(|c| c.variant_key() != correction.variant_key());
+                pack.corrections.push(correction);
+            }
+            ProposalKind::CustomWord => {
+                if !pack.terms.contains(&proposal.to) {
+                    pack.terms.push(proposal.to.clone());
+                }
+            }
+        }
+    }
+    settings
+        .jargon_rejected_proposals
+        .retain(|rejected| !proposals.iter().any(|p| p.same_as(rejected)));
+}
+
+/// Remember `proposals` so they are not proposed again.
+pub fn reject(settings: &mut AppSettings, proposals: &[JargonProposal]) {
+    for proposal in proposals {
+        let rejected = &mut settings.jargon_rejected_proposals;
+        if !rejected.iter().any(|r| r.same_as(proposal)) {
+            rejected.push(proposal.clone());
+        }
+    }
+    let rejected = &mut settings.jargon_rejected_proposals;
+    if rejected.len() > MAX_REJECTED {
+        rejected.drain(..rejected.len() - MAX_REJECTED);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(kind: ProposalKind, from: &str, to: &str) -> JargonProposal {
+        JargonProposal {
+            kind,
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn substitutions_become_corrections_and_words() {
+        let proposals = propose(
+            "Run cube control on the cluster, then push to github.",
+            "Run kubectl on the cluster, then push to GitHub.",
+        );
+        assert_eq!(
+            proposals,
+            vec![
+                proposal(ProposalKind::Correction, "cube control", "kubectl"),
+                proposal(ProposalKind::CustomWord, "github", "GitHub"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrites_and_sentence_case_are_not_proposed() {
+        assert!(propose("I think we should ship", "I believe we should ship").is_empty());
+        assert!(propose("ok. the build passed", "ok. The build passed").is_empty());
+        assert!(propose("the build passed,", "the build passed.").is_empty());
+        assert_eq!(
+            propose("we write it in rust", "we write it in Rust"),
+            vec![proposal(ProposalKind::CustomWord, "rust", "Rust")]
+        );
+    }
+
+    #[test]
+    fn accepted_go_to_learned_pack_and_rejected_are_remembered() {
+        let mut settings = crate::settings::get_default_settings();
+        let correction = proposal(ProposalKind::Correction, "cube control", "kubectl");
+        let word = proposal(ProposalKind::CustomWord, "github", "GitHub");
+
+        reject(&mut settings, &[word.clone()]);
+        assert!(filter_known(vec![word.clone()], &settings).is_empty());
+
+        accept(&mut settings, &[correction.clone(), word.clone()]);
+        let pack = settings
+            .jargon_packs
+            .iter()
+            .find(|pack| pack.id == LEARNED_PACK_ID)
+            .unwrap();
+        assert_eq!(pack.terms, vec!["GitHub"]);
+        assert_eq!(pack.corrections[0].to, "kubectl");
+        assert!(settings
+            .jargon_enabled_profiles
+            .contains(&LEARNED_PACK_ID.to_string()));
+        assert!(settings.jargon_rejected_proposals.is_empty());
+        assert!(filter_known(vec![correction, word], &settings).is_empty());
+    }
+}
@@ -0,0 +1,280 @@
+//! External jargon sources: local files or folders whose terms and
+//! corrections are merged into the active dictionary.
+//!
+//! Files are read by extension: `.json` uses the jargon pack format from
+//! `docs/jargon-generation.md`, `.csv` holds `from,to` corrections, and
+//! anything else is a term list with one term per line (lines starting
+//! with `#` are comments). A folder source reads the `.json`, `.csv` and
+//! `.txt` files directly inside it.
+//!
+//! Priority, highest first: custom terms and corrections, then sources in
+//! the order they are listed, then packs and built-in profiles. Sources are
+//! reloaded by `JargonSourceManager` when their files change.
+
+use crate::jargon::{JargonCorrection, JargonProfile};
+use crate::settings::JargonPack;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Folder sources skip anything larger; jargon lists are small.
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct JargonSource {
+    pub id: String,
+    pub label: String,
+    /// File or folder on disk.
+    pub path: String,
+    #[serde(default = "default_source_enabled")]
+    pub enabled: bool,
+    /// Only used for these bindings; empty means every binding.
+    #[serde(default)]
+    pub binding_ids: Vec<String>,
+    /// Only used when post-processing with these prompts; empty means always.
+    #[serde(default)]
+    pub prompt_ids: Vec<String>,
+}
+
+fn default_source_enabled() -> bool {
+    true
+}
+
+/// The binding and prompt a dictionary is being built for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JargonScope {
+    pub binding_id: Option<String>,
+    /// Set only while building the post-processing prompt.
+    pub prompt_id: Option<String>,
+}
+
+impl JargonSource {
+    pub fn applies_to(&self, scope: &JargonScope) -> bool {
+        fn matches(ids: &[String], id: &Option<String>) -> bool {
+            ids.is_empty() || id.as_ref().is_some_and(|id| ids.contains(id))
+        }
+        self.enabled
+            && matches(&self.binding_ids, &scope.binding_id)
+            && matches(&self.prompt_ids, &scope.prompt_id)
+    }
+}
+
+#[derive(Deserialize)]
+struct PackFile {
+    #[serde(default)]
+    packs: Vec<JargonPack>,
+}
+
+/// Files a source reads, with their modification time and size. A change
+/// in this list means the source needs reloading.
+pub type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+pub fn fingerprint(path: &Path) -> Result<Fingerprint, String> {
+    let files = source_files(path)?;
+    Ok(files
+        .into_iter()
+        .map(|file| {
+            let metadata = std::fs::metadata(&file).ok();
+            let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+            let len = metadata.map(|m| m.len()).unwrap_or(0);
+            (file, modified, len)
+        })
+        .collect())
+}
+
+fn source_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(format!("'{}' does not exist", path.display()));
+    }
+    let entries =
+        std::fs::read_dir(path).map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| {
+            file.is_file()
+                && matches!(extension(file).as_str(), "json" | "csv" | "txt")
+                && !file
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+                && std::fs::metadata(file).is_ok_and(|m| m.len() <= MAX_FILE_BYTES)
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Read every file of a source into one profile.
+pub fn load(source: &JargonSource) -> Result<JargonProfile, String> {
+    let mut profile = JargonProfile {
+        label: source.label.clone(),
+        terms: Vec::new(),
+        corrections: Vec::new(),
+    };
+    for file in source_files(Path::new(&source.path))? {
+        let content = std::fs::read_to_string(&file)
+            .map_err(|e| format!("Cannot read '{}': {}", file.display(), e))?;
+        match extension(&file).as_str() {
+            "json" => {
+                let parsed: PackFile = serde_json::from_str(&content)
+                    .map_err(|e| format!("Invalid jargon pack '{}': {}", file.display(), e))?;
+                for pack in parsed.packs {
+                    profile.terms.extend(pack.terms);
+                    profile.corrections.extend(pack.corrections);
+                }
+            }
+            "csv" => profile.corrections.extend(parse_corrections_csv(&content)),
+            _ => profile.terms.extend(parse_term_list(&content)),
+        }
+    }
+    profile.terms.retain(|term| !term.trim().is_empty());
+    profile.corrections = profile
+        .corrections
+        .iter()
+        .filter_map(JargonCorrection::sanitized)
+        .collect();
+    Ok(profile)
+}
+
+pub fn parse_term_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches("- ").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `from,to` per line. Fields may be double-quoted; a `from,to` header row
+/// is skipped.
+pub fn parse_corrections_csv(content: &str) -> Vec<JargonCorrection> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (from, to) = split_csv_pair(line.trim())?;
+            if from.eq_ignore_ascii_case("from") && to.eq_ignore_ascii_case("to") {
+                return None;
+            }
+            Some(JargonCorrection::new(from, to))
+        })
+        .collect()
+}
+
+fn split_csv_pair(line: &str) -> Option<(String, String)> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    match fields.as_slice() {
+        [from, to, ..] if !from.trim().is_empty() && !to.trim().is_empty() => {
+            Some((from.trim().to_string(), to.trim().to_string()))
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn source(path: &Path) -> JargonSource {
+        JargonSource {
+            id: "team".to_string(),
+            label: "Team".to_string(),
+            path: path.to_string_lossy().into_owned(),
+            enabled: true,
+            binding_ids: Vec::new(),
+            prompt_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn term_lists_and_csv_corrections_parse() {
+        assert_eq!(
+            parse_term_list("# team terms\nKubernetes\n- Terraform\n\nC#\n"),
+            vec!["Kubernetes", "Terraform", "C#"]
+        );
+        let corrections = parse_corrections_csv(
+            "from,to\ncube control,kubectl\n\"post gress\",\"Postgres, the DB\"\nbroken",
+        );
+        assert_eq!(corrections.len(), 2);
+        assert_eq!(corrections[0].to, "kubectl");
+        assert_eq!(corrections[1].from, "post gress");
+        assert_eq!(corrections[1].to, "Postgres, the DB");
+    }
+
+    #[test]
+    fn folder_sources_read_every_format() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().to_path_buf();
+        std::fs::write(dir.join("terms.txt"), "Helm\n").unwrap();
+        std::fs::write(dir.join("README.md"), "# Team jargon\nSee the wiki.\n").unwrap();
+        std::fs::write(dir.join("LICENSE"), "MIT License\n").unwrap();
+        std::fs::write(dir.join("fixes.csv"), "home,Helm\n").unwrap();
+        std::fs::write(
+            dir.join("pack.json"),
+            r#"{"version": 1, "packs": [{"id": "x", "label": "X", "terms": ["Istio"]}]}"#,
+        )
+        .unwrap();
+
+        let profile = load(&source(&dir)).unwrap();
+        let before = fingerprint(&dir).unwrap();
+        assert_eq!(profile.terms, vec!["Istio", "Helm"]);
+        assert_eq!(profile.corrections.len(), 1);
+
+        std::fs::write(dir.join("terms.txt"), "Helm\nArgo\n").unwrap();
+        assert_ne!(fingerprint(&dir).unwrap(), before);
+        drop(temp);
+
+        assert!(load(&source(&dir)).is_err());
+    }
+
+    #[test]
+    fn scope_limits_sources() {
+        let mut source = source(Path::new("/tmp"));
+        let scope = |binding: &str, prompt: Option<&str>| JargonScope {
+            binding_id: Some(binding.to_string()),
+            prompt_id: prompt.map(str::to_string),
+        };
+        assert!(source.applies_to(&scope("transcribe", None)));
+
+        source.binding_ids = vec!["transcribe_with_post_process".to_string()];
+        assert!(!source.applies_to(&scope("transcribe", None)));
+        assert!(source.applies_to(&scope("transcribe_with_post_process", None)));
+
+        source.binding_ids.clear();
+        source.prompt_ids = vec!["commit".to_string()];
+        assert!(!source.applies_to(&scope("transcribe", None)));
+        assert!(source.applies_to(&scope("transcribe", Some("commit"))));
+
+        source.prompt_ids.clear();
+        source.enabled = false;
+        assert!(!source.applies_to(&scope("transcribe", None)));
+    }
+}
@@ -0,0 +1,144 @@
+//! Remembers the text the last pipeline run inserted so the
+//! `undo_last_dictation` shortcut can take it back.
+//!
+//! Removal goes through `apply_text_diff` as a single delete-only edit, the
+//! same path post-processing uses to rewrite pasted text. It is refused when
+//! the focused app changed since the paste, since the characters before the
+//! cursor would then belong to something else.
+
+use crate::clipboard::TextEdit;
+use crate::settings::{AppSettings, PasteMethod};
+use crate::utils;
+use log::{debug, info, warn};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+struct InsertedDictation {
+    /// Exactly what was typed, including any trailing space.
+    text: String,
+    /// `context_providers::focused_app_id` at the time of the paste.
+    focused_app: String,
+}
+
+#[derive(Default)]
+pub struct LastDictationState(Mutex<Option<InsertedDictation>>);
+
+/// The text left in the focused app after pasting `pasted` with `settings`,
+/// or `None` when it cannot safely be taken back: nothing was pasted, or
+/// auto-submit already sent it on.
+fn inserted_text(settings: &AppSettings, pasted: &str) -> Option<String> {
+    if pasted.is_empty() || settings.paste_method == PasteMethod::None || settings.auto_submit {
+        return None;
+    }
+    if settings.append_trailing_space {
+        Some(format!("{} ", pasted))
+    } else {
+        Some(pasted.to_string())
+    }
+}
+
+/// Record that `pasted` was just written out, followed by the trailing space
+/// `settings` adds. Call on the thread that pasted, right after the paste.
+pub fn record(app: &AppHandle, settings: &AppSettings, pasted: &str) {
+    let entry = inserted_text(settings, pasted).and_then(|text| {
+        let focused_app = crate::context_providers::focused_app_id()?;
+        Some(InsertedDictation { text, focused_app })
+    });
+    if entry.is_none() {
+        debug!("Last dictation cannot be undone; not recording it");
+    }
+    set(app, entry);
+}
+
+/// Forget the last dictation, e.g. when a new run starts or its output could
+/// not be tracked exactly.
+pub fn clear(app: &AppHandle) {
+    set(app, None);
+}
+
+fn set(app: &AppHandle, entry: Option<InsertedDictation>) {
+    if let Some(state) = app.try_state::<LastDictationState>() {
+        if let Ok(mut last) = state.0.lock() {
+            *last = entry;
+        }
+    }
+}
+
+/// Delete the last dictation from the focused app.
+pub fn undo(app: &AppHandle) -> Result<(), String> {
+    let state = app
+        .try_state::<LastDictationState>()
+        .ok_or("Last dictation state not initialized")?;
+    let mut last = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock last dictation: {}", e))?;
+
+    let entry = last.as_ref().ok_or("Nothing to undo")?;
+    match crate::context_providers::focused_app_id() {
+        Some(current) if current == entry.focused_app => {}
+        Some(current) => {
+            return Err(format!(
+                "Focused app changed since the paste ('{}' -> '{}')",
+                entry.focused_app, current
+            ));
+        }
+        None => return Err("Cannot determine the focused app".to_string()),
+    }
+
+    let entry = last.take().expect("checked above");
+    drop(last);
+
+    let edit = TextEdit {
+        cursor_left: 0,
+        delete_chars: entry.text.chars().count(),
+        insert: String::new(),
+    };
+    info!("Undoing last dictation ({} chars)", edit.delete_chars);
+    let ah = app.clone();
+    app.run_on_main_thread(move || {
+        if let Err(e) = utils::apply_text_diff(&[edit], 0, ah) {
+            warn!("Failed to undo last dictation: {}", e);
+        }
+    })
+    .map_err(|e| format!("Failed to run undo on main thread: {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn inserted_text_includes_trailing_space() {
+        let mut settings = get_default_settings();
+        settings.paste_method = PasteMethod::CtrlV;
+        settings.auto_submit = false;
+        settings.append_trailing_space = true;
+        assert_eq!(
+            inserted_text(&settings, "hello world"),
+            Some("hello world ".to_string())
+        );
+
+        settings.append_trailing_space = false;
+        assert_eq!(
+            inserted_text(&settings, "hello world"),
+            Some("hello world".to_string())
+        );
+    }
+
+    #[test]
+    fn inserted_text_is_untracked_when_nothing_stays_in_place() {
+        let mut settings = get_default_settings();
+        settings.paste_method = PasteMethod::CtrlV;
+        settings.auto_submit = false;
+        assert_eq!(inserted_text(&settings, ""), None);
+
+        settings.auto_submit = true;
+        assert_eq!(inserted_text(&settings, "sent"), None);
+
+        settings.auto_submit = false;
+        settings.paste_method = PasteMethod::None;
+        assert_eq!(inserted_text(&settings, "not pasted"), None);
+    }
+}
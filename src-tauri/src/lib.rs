@@ -5,26 +5,41 @@ mod application;
 mod at_file_expansion;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod cleanup;
+mod cli_mode;
 mod clipboard;
 mod commands;
+mod config_pack;
 mod context_providers;
 mod domain;
 mod helpers;
 mod input;
 pub mod jargon;
+mod jargon_learning;
+mod jargon_sources;
+mod last_dictation;
+mod live_segments;
 mod llm_client;
 #[cfg(target_os = "macos")]
 mod macos_ax;
 mod managers;
 mod overlay;
+mod phonetic;
 mod pipeline;
+mod pipeline_stages;
 pub mod rolling_harness;
 mod settings;
 mod shortcut;
 mod signal_handle;
+mod snippets;
+mod style_presets;
+mod templates;
+mod text_model;
 mod tray;
 mod tray_i18n;
 mod utils;
+mod voice_commands;
+mod workspace_jargon;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use tauri_specta::{collect_commands, Builder};
 
@@ -265,6 +280,8 @@ pub fn run() {
         shortcut::change_overlay_position_setting,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
+        shortcut::change_phonetic_matching_setting,
+        shortcut::change_phonetic_match_threshold_setting,
         shortcut::change_paste_method_setting,
         shortcut::get_available_typing_tools,
         shortcut::change_typing_tool_setting,
@@ -273,26 +290,53 @@ pub fn run() {
         shortcut::change_auto_submit_key_setting,
         shortcut::change_post_process_enabled_setting,
         shortcut::change_post_process_auto_prompt_selection_setting,
+        shortcut::change_post_process_streaming_setting,
+        shortcut::change_post_process_divergence_guard_setting,
+        shortcut::change_voice_commands_enabled_setting,
+        shortcut::update_voice_commands,
+        shortcut::change_templates_enabled_setting,
+        shortcut::change_template_llm_fill_setting,
+        shortcut::update_templates,
+        shortcut::change_snippets_enabled_setting,
+        shortcut::add_snippet,
+        shortcut::update_snippet,
+        shortcut::delete_snippet,
+        shortcut::update_style_presets,
+        shortcut::update_style_preset_bindings,
+        shortcut::update_style_preset_app_rules,
+        shortcut::change_cli_mode_enabled_setting,
+        shortcut::change_cli_mode_llm_assist_setting,
+        shortcut::update_cli_mode_deny_patterns,
+        shortcut::update_post_process_fallbacks,
+        shortcut::change_post_process_timeout_ms_setting,
         shortcut::change_experimental_enabled_setting,
         shortcut::change_post_process_base_url_setting,
+        shortcut::change_post_process_wire_protocol_setting,
+        shortcut::change_local_cleanup_model_dir_setting,
         shortcut::change_post_process_api_key_setting,
         shortcut::change_post_process_model_setting,
         shortcut::set_post_process_provider,
         shortcut::fetch_post_process_models,
         shortcut::add_post_process_prompt,
         shortcut::update_post_process_prompt,
+        shortcut::update_post_process_prompt_routing,
+        shortcut::change_post_process_prompt_restructures,
+        shortcut::explain_prompt_route,
         shortcut::delete_post_process_prompt,
         shortcut::set_post_process_selected_prompt,
         shortcut::update_custom_words,
         shortcut::suspend_binding,
         shortcut::resume_binding,
         shortcut::change_mute_while_recording_setting,
-        shortcut::change_audio_segment_size_seconds_setting,
+        shortcut::change_segment_on_silence_ms_setting,
         shortcut::change_append_trailing_space_setting,
         shortcut::change_at_file_expansion_setting,
+        shortcut::get_available_pipeline_stages,
+        shortcut::update_pipeline_stages,
         shortcut::update_jargon_profiles,
         shortcut::update_jargon_custom_terms,
         shortcut::update_jargon_custom_corrections,
+        shortcut::update_workspace_jargon_enabled_setting,
         shortcut::get_jargon_builtin_profiles,
         shortcut::update_domain_selector_enabled_setting,
         shortcut::update_domain_selector_timeout_ms_setting,
@@ -300,10 +344,27 @@ pub fn run() {
         shortcut::update_domain_selector_min_score_setting,
         shortcut::update_domain_selector_hysteresis_setting,
         shortcut::update_domain_selector_blend_manual_profiles_setting,
+        shortcut::update_domain_selector_learned_weights_setting,
+        shortcut::reset_domain_selector_feedback,
+        shortcut::update_intent_router_enabled_setting,
+        shortcut::change_intent_router_model_dir_setting,
+        shortcut::update_intent_router_timeout_ms_setting,
+        shortcut::update_intent_router_min_confidence_setting,
+        shortcut::update_reranker_enabled_setting,
+        shortcut::change_reranker_model_dir_setting,
+        shortcut::change_reranker_second_prompt_setting,
+        shortcut::update_reranker_timeout_ms_setting,
         shortcut::get_jargon_packs,
+        shortcut::accept_jargon_proposals,
+        shortcut::reject_jargon_proposals,
         shortcut::update_jargon_packs,
         shortcut::import_jargon_packs_json,
         shortcut::export_jargon_packs_json,
+        shortcut::preview_config_pack,
+        shortcut::import_config_pack,
+        shortcut::export_config_pack,
+        shortcut::update_jargon_sources,
+        shortcut::get_jargon_source_statuses,
         shortcut::change_app_language_setting,
         shortcut::change_update_checks_setting,
         shortcut::change_keyboard_implementation_setting,
@@ -355,6 +416,7 @@ pub fn run() {
         commands::history::toggle_history_entry_saved,
         commands::history::get_audio_file_path,
         commands::history::delete_history_entry,
+        commands::history::update_history_entry_text,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
         helpers::clamshell::is_laptop,
@@ -417,6 +479,8 @@ pub fn run() {
             Some(vec![]),
         ))
         .manage(Mutex::new(ShortcutToggleStates::default()))
+        .manage(last_dictation::LastDictationState::default())
+        .manage(templates::TemplateNavigationState::default())
         .setup(move |app| {
             let settings = get_settings(&app.handle());
             let tauri_log_level: tauri_plugin_log::LogLevel = settings.log_level.into();
@@ -0,0 +1,271 @@
+//! Segment-on-silence: while a recording is running, every stretch of speech
+//! the recorder cuts off at a pause is transcribed and pasted right away.
+//!
+//! Each segment goes through the steps that only change text: voice
+//! commands that type something, the style preset and snippets. Key
+//! commands stay as spoken words, since the user is still talking.
+//!
+//! The pasted texts and their audio are handed to `TranscriptionPipeline` when
+//! the recording stops, as its `pasted_segments`, so only the speech after the
+//! last pause is transcribed then and post-processing can diff over the whole
+//! dictation.
+
+use crate::audio_toolkit::SegmentSink;
+use crate::jargon_sources::JargonScope;
+use crate::managers::transcription::TranscriptionManager;
+use crate::pipeline::{normalize_segment_text_for_post_process, resolve_style_preset};
+use crate::settings::AppSettings;
+use crate::snippets;
+use crate::style_presets::{self, StylePreset};
+use crate::utils;
+use crate::voice_commands;
+use log::{debug, error, warn};
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Valid range for `segment_on_silence_ms` when it is not 0 (off).
+pub const MIN_SILENCE_MS: u64 = 250;
+pub const MAX_SILENCE_MS: u64 = 5000;
+
+struct LiveSegments {
+    cancelled: Arc<AtomicBool>,
+    worker: JoinHandle<FinishedSegments>,
+}
+
+static ACTIVE: Lazy<Mutex<Option<LiveSegments>>> = Lazy::new(|| Mutex::new(None));
+
+/// What was pasted while recording, in order.
+#[derive(Default)]
+pub struct FinishedSegments {
+    /// Pasted texts, each with the space that separates it from the next.
+    pub texts: Vec<String>,
+    /// Audio of the pasted segments, for history.
+    pub samples: Vec<f32>,
+    /// Snippet bodies expanded into the pasted texts, which post-processing
+    /// must leave alone.
+    pub snippet_expansions: Vec<String>,
+}
+
+/// Clamp a nonzero `segment_on_silence_ms` to the supported range.
+pub fn clamp_silence_ms(ms: u64) -> u64 {
+    if ms == 0 {
+        0
+    } else {
+        ms.clamp(MIN_SILENCE_MS, MAX_SILENCE_MS)
+    }
+}
+
+/// Start transcribing segments for the recording `binding_id` is about to
+/// start. Returns the sink to record into, or `None` when segment-on-silence
+/// is off.
+pub fn begin(app: &AppHandle, settings: &AppSettings, binding_id: &str) -> Option<SegmentSink> {
+    if settings.segment_on_silence_ms == 0 {
+        return None;
+    }
+    cancel();
+
+    let (tx, rx) = mpsc::channel::<Vec<f32>>();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let worker = {
+        let app = app.clone();
+        let cancelled = Arc::clone(&cancelled);
+        let scope = JargonScope {
+            binding_id: Some(binding_id.to_string()),
+            prompt_id: None,
+        };
+        let settings = settings.clone();
+        let preset = resolve_style_preset(&settings, binding_id);
+        std::thread::spawn(move || transcribe_segments(app, rx, cancelled, scope, settings, preset))
+    };
+    *ACTIVE.lock().unwrap() = Some(LiveSegments { cancelled, worker });
+
+    Some(SegmentSink {
+        silence: Duration::from_millis(clamp_silence_ms(settings.segment_on_silence_ms)),
+        tx,
+    })
+}
+
+/// Wait for the segments of the recording that just stopped to be pasted.
+/// The recorder closes the segment channel on stop, so this only waits for
+/// the segment being transcribed, if any. The worker is joined on a blocking
+/// thread so the async runtime keeps running meanwhile.
+pub async fn finish(app: &AppHandle) -> FinishedSegments {
+    let Some(active) = ACTIVE.lock().unwrap().take() else {
+        return FinishedSegments::default();
+    };
+    let joined = tauri::async_runtime::spawn_blocking(move || active.worker.join()).await;
+    let finished = match joined {
+        Ok(Ok(finished)) => finished,
+        Ok(Err(_)) => {
+            error!("Segment transcription thread panicked");
+            FinishedSegments::default()
+        }
+        Err(e) => {
+            error!("Failed to wait for segment transcription: {}", e);
+            FinishedSegments::default()
+        }
+    };
+    if !finished.texts.is_empty() {
+        // The model may have been unloaded after the last segment.
+        app.state::<Arc<TranscriptionManager>>()
+            .initiate_model_load();
+    }
+    finished
+}
+
+/// Stop pasting segments of the current recording. Segments already pasted
+/// stay where they are.
+pub fn cancel() {
+    if let Some(active) = ACTIVE.lock().unwrap().take() {
+        active.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+fn transcribe_segments(
+    app: AppHandle,
+    rx: mpsc::Receiver<Vec<f32>>,
+    cancelled: Arc<AtomicBool>,
+    scope: JargonScope,
+    settings: AppSettings,
+    preset: Option<StylePreset>,
+) -> FinishedSegments {
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    let mut finished = FinishedSegments::default();
+
+    while let Ok(samples) = rx.recv() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        tm.initiate_model_load();
+        let text = match tm.transcribe(samples.clone(), &scope) {
+            Ok(text) => normalize_segment_text_for_post_process(&text),
+            Err(e) => {
+                warn!("Failed to transcribe segment: {}", e);
+                continue;
+            }
+        };
+        if text.is_empty() || cancelled.load(Ordering::Relaxed) {
+            continue;
+        }
+        let (text, inserted) = segment_text(&text, &settings, preset.as_ref(), |name| {
+            snippets::builtin_variable(&app, name)
+        });
+
+        debug!("Pasting segment {}: '{}'", finished.texts.len() + 1, text);
+        let segment = if text.ends_with(char::is_whitespace) {
+            text
+        } else {
+            format!("{} ", text)
+        };
+        let ah = app.clone();
+        let pasted = segment.clone();
+        if let Err(e) = app.run_on_main_thread(move || {
+            if let Err(e) = utils::paste_raw(pasted, ah) {
+                error!("Failed to paste segment: {}", e);
+            }
+        }) {
+            error!("Failed to run segment paste on main thread: {:?}", e);
+            continue;
+        }
+        finished.texts.push(segment);
+        finished.samples.extend_from_slice(&samples);
+        finished.snippet_expansions.extend(inserted);
+    }
+
+    finished
+}
+
+/// Run the text-only steps of the final paste over one segment: voice
+/// commands without key commands, the style preset, then snippets. Returns
+/// the text and the snippet bodies inserted into it.
+fn segment_text(
+    text: &str,
+    settings: &AppSettings,
+    preset: Option<&StylePreset>,
+    variable: impl Fn(&str) -> Option<String>,
+) -> (String, Vec<String>) {
+    let text = if settings.voice_commands_enabled {
+        let tokens = voice_commands::parse(text, &settings.voice_commands);
+        voice_commands::typed_text(&voice_commands::resolve(&tokens, false))
+    } else {
+        text.to_string()
+    };
+    let text = match preset {
+        Some(preset) => style_presets::apply_rules(&text, preset),
+        None => text,
+    };
+    if settings.snippets_enabled {
+        let expansion = snippets::expand(&text, &settings.snippets, variable);
+        (expansion.text, expansion.inserted)
+    } else {
+        (text, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+    use crate::snippets::Snippet;
+
+    #[test]
+    fn segments_get_text_commands_and_snippets_but_no_keys() {
+        let mut settings = get_default_settings();
+        settings.voice_commands_enabled = true;
+        settings.snippets_enabled = true;
+        settings.snippets = vec![Snippet {
+            id: "sig".to_string(),
+            trigger: "insert my signature".to_string(),
+            body: "Best,\nAda".to_string(),
+            enabled: true,
+        }];
+
+        let text = segment_text(
+            "thanks new line insert my signature",
+            &settings,
+            None,
+            |_| None,
+        );
+        assert_eq!(
+            text,
+            (
+                "thanks\nBest,\nAda".to_string(),
+                vec!["Best,\nAda".to_string()]
+            )
+        );
+        assert_eq!(
+            segment_text("hello undo last", &settings, None, |_| None),
+            ("hello undo last".to_string(), Vec::new())
+        );
+
+        settings.voice_commands_enabled = false;
+        settings.snippets_enabled = false;
+        assert_eq!(
+            segment_text("thanks new line", &settings, None, |_| None),
+            ("thanks new line".to_string(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn segment_snippets_are_masked_for_post_processing() {
+        let mut settings = get_default_settings();
+        settings.snippets_enabled = true;
+        settings.snippets = vec![Snippet {
+            id: "addr".to_string(),
+            trigger: "insert my address".to_string(),
+            body: "12 Main St".to_string(),
+            enabled: true,
+        }];
+
+        let (text, inserted) =
+            segment_text("ship it to insert my address", &settings, None, |_| None);
+        let raw_text = format!("{} thanks", text);
+        let (masked, pairs) = snippets::mask_inserted(&raw_text, &inserted);
+        assert!(!masked.contains("12 Main St"));
+        assert_eq!(pairs.len(), 1);
+    }
+}
@@ -1,7 +1,18 @@
-use crate::settings::PostProcessProvider;
+use crate::settings::{PostProcessProvider, WireProtocol};
+use futures_util::stream::{AbortHandle, Abortable, BoxStream};
+use futures_util::{Stream, StreamExt};
 use log::debug;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
+use once_cell::sync::Lazy;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 #[derive(Debug, Serialize)]
 struct ChatMessage {
@@ -9,10 +20,16 @@ struct ChatMessage {
     content: String,
 }
 
+// ============================================================================
+// OpenAI chat completions wire types
+// ============================================================================
+
 #[derive(Debug, Serialize)]
 struct ChatCompletionRequest {
     model: String,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +47,88 @@ struct ChatMessageResponse {
     content: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChatChunkChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunkChoice {
+    #[serde(default)]
+    delta: ChatDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    content: Option<String>,
+}
+
+// ============================================================================
+// Anthropic messages wire types
+// ============================================================================
+
+/// The Messages API requires an explicit output cap.
+const ANTHROPIC_MAX_TOKENS: u32 = 4096;
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, Serialize)]
+struct AnthropicMessagesRequest {
+    model: String,
+    max_tokens: u32,
+    /// Anthropic takes the system prompt as a top-level field, not a message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessagesResponse {
+    #[serde(default)]
+    content: Vec<AnthropicContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    delta: Option<AnthropicStreamDelta>,
+    #[serde(default)]
+    error: Option<ApiErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// Error object shared by both protocols: OpenAI nests
+/// `{"error": {"message"}}`, Anthropic `{"type": "error", "error": {"type", "message"}}`.
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    error: ApiErrorDetail,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ApiErrorDetail {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
 /// Build headers for API requests based on provider type
 fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<HeaderMap, String> {
     let mut headers = HeaderMap::new();
@@ -46,21 +145,30 @@ fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<Header
     );
     headers.insert("X-Title", HeaderValue::from_static("Spittle"));
 
-    // Provider-specific auth headers
-    if !api_key.is_empty() {
-        if provider.id == "anthropic" {
-            headers.insert(
-                "x-api-key",
-                HeaderValue::from_str(api_key)
-                    .map_err(|e| format!("Invalid API key header value: {}", e))?,
-            );
-            headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
-        } else {
+    // Protocol-specific auth headers
+    match provider.wire_protocol {
+        WireProtocol::AnthropicMessages => {
+            // The version header is required even for keyless proxies.
             headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", api_key))
-                    .map_err(|e| format!("Invalid authorization header value: {}", e))?,
+                "anthropic-version",
+                HeaderValue::from_static(ANTHROPIC_VERSION),
             );
+            if !api_key.is_empty() {
+                headers.insert(
+                    "x-api-key",
+                    HeaderValue::from_str(api_key)
+                        .map_err(|e| format!("Invalid API key header value: {}", e))?,
+                );
+            }
+        }
+        WireProtocol::OpenaiChat => {
+            if !api_key.is_empty() {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {}", api_key))
+                        .map_err(|e| format!("Invalid authorization header value: {}", e))?,
+                );
+            }
         }
     }
 
@@ -76,74 +184,454 @@ fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwes
         .map_err(|e| format!("Failed to build HTTP client: {}", e))
 }
 
-/// Send a chat completion request to an OpenAI-compatible API.
+/// Apply an optional per-request deadline. reqwest's request timeout spans
+/// connecting through reading the last byte of the body, so it also bounds
+/// streams that stall mid-response.
+fn with_deadline(
+    request: reqwest::RequestBuilder,
+    timeout: Option<Duration>,
+) -> reqwest::RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+fn describe_request_error(e: &reqwest::Error, timeout: Option<Duration>) -> String {
+    match timeout {
+        Some(timeout) if e.is_timeout() => {
+            format!("Request timed out after {}ms", timeout.as_millis())
+        }
+        _ => format!("HTTP request failed: {}", e),
+    }
+}
+
+fn build_messages(prompt: String, system_message: Option<String>) -> Vec<ChatMessage> {
+    let mut messages = Vec::new();
+    if let Some(system) = system_message {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: system,
+        });
+    }
+    messages.push(ChatMessage {
+        role: "user".to_string(),
+        content: prompt,
+    });
+    messages
+}
+
+fn completion_url(provider: &PostProcessProvider) -> String {
+    let base_url = provider.base_url.trim_end_matches('/');
+    match provider.wire_protocol {
+        WireProtocol::OpenaiChat => format!("{}/chat/completions", base_url),
+        WireProtocol::AnthropicMessages => format!("{}/messages", base_url),
+    }
+}
+
+/// Attach the protocol-specific completion body to `request`.
+fn with_completion_body(
+    request: reqwest::RequestBuilder,
+    protocol: WireProtocol,
+    model: &str,
+    prompt: String,
+    system_message: Option<String>,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    match protocol {
+        WireProtocol::OpenaiChat => request.json(&ChatCompletionRequest {
+            model: model.to_string(),
+            messages: build_messages(prompt, system_message),
+            stream,
+        }),
+        WireProtocol::AnthropicMessages => request.json(&AnthropicMessagesRequest {
+            model: model.to_string(),
+            max_tokens: ANTHROPIC_MAX_TOKENS,
+            system: system_message,
+            messages: build_messages(prompt, None),
+            stream,
+        }),
+    }
+}
+
+/// Pull the text out of a non-streaming completion body.
+fn parse_completion(protocol: WireProtocol, body: &str) -> Result<Option<String>, String> {
+    match protocol {
+        WireProtocol::OpenaiChat => {
+            let completion: ChatCompletionResponse = serde_json::from_str(body)
+                .map_err(|e| format!("Failed to parse API response: {}", e))?;
+            Ok(completion
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.message.content))
+        }
+        WireProtocol::AnthropicMessages => {
+            let message: AnthropicMessagesResponse = serde_json::from_str(body)
+                .map_err(|e| format!("Failed to parse API response: {}", e))?;
+            let text: String = message
+                .content
+                .into_iter()
+                .filter(|block| block.kind == "text")
+                .filter_map(|block| block.text)
+                .collect();
+            Ok(if text.is_empty() { None } else { Some(text) })
+        }
+    }
+}
+
+/// Extract a readable message from an error body, falling back to the raw text.
+fn parse_error_message(protocol: WireProtocol, body: &str) -> String {
+    let detail = match serde_json::from_str::<ApiErrorBody>(body) {
+        Ok(parsed) => parsed.error,
+        Err(_) => return body.trim().to_string(),
+    };
+    let message = detail.message.unwrap_or_else(|| body.trim().to_string());
+    match (protocol, detail.kind) {
+        (WireProtocol::AnthropicMessages, Some(kind)) => format!("{}: {}", kind, message),
+        _ => message,
+    }
+}
+
+async fn error_from_response(protocol: WireProtocol, response: reqwest::Response) -> String {
+    let status = response.status();
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Failed to read error response".to_string());
+    format!(
+        "API request failed with status {}: {}",
+        status,
+        parse_error_message(protocol, &error_text)
+    )
+}
+
+/// Send a chat completion request using the provider's wire protocol.
 /// Returns Ok(Some(content)) on success, Ok(None) if response has no content,
 /// or Err on actual errors (HTTP, parsing, etc.)
 ///
-/// An optional `system_message` is sent as a separate system role message
-/// before the user prompt, ensuring the LLM treats it as instructions.
+/// An optional `system_message` is sent wherever the protocol expects
+/// instructions: a system role message for OpenAI, the top-level `system`
+/// field for Anthropic.
+/// When `timeout` is set the whole request, body included, must finish in time.
 pub async fn send_chat_completion(
     provider: &PostProcessProvider,
     api_key: String,
     model: &str,
     prompt: String,
     system_message: Option<String>,
+    timeout: Option<Duration>,
 ) -> Result<Option<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/chat/completions", base_url);
+    let url = completion_url(provider);
 
     debug!("Sending chat completion request to: {}", url);
 
     let client = create_client(provider, &api_key)?;
 
-    let mut messages = Vec::new();
-    if let Some(system) = system_message {
-        messages.push(ChatMessage {
-            role: "system".to_string(),
-            content: system,
-        });
+    let response = with_completion_body(
+        with_deadline(client.post(&url), timeout),
+        provider.wire_protocol,
+        model,
+        prompt,
+        system_message,
+        false,
+    )
+    .send()
+    .await
+    .map_err(|e| describe_request_error(&e, timeout))?;
+
+    if !response.status().is_success() {
+        return Err(error_from_response(provider.wire_protocol, response).await);
     }
-    messages.push(ChatMessage {
-        role: "user".to_string(),
-        content: prompt,
-    });
 
-    let request_body = ChatCompletionRequest {
-        model: model.to_string(),
-        messages,
-    };
+    let body = response.text().await.map_err(|e| {
+        if e.is_timeout() {
+            describe_request_error(&e, timeout)
+        } else {
+            format!("Failed to read API response: {}", e)
+        }
+    })?;
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+    parse_completion(provider.wire_protocol, &body)
+}
 
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read error response".to_string());
-        return Err(format!(
-            "API request failed with status {}: {}",
-            status, error_text
-        ));
+// ============================================================================
+// Streaming (SSE) chat completions
+// ============================================================================
+
+/// Abort handles for every stream that is currently open, keyed by stream id.
+static ACTIVE_STREAMS: Lazy<Mutex<HashMap<u64, AbortHandle>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_STREAM_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Abort every open chat completion stream. The underlying HTTP response is
+/// dropped on the next poll, which closes the connection.
+pub fn abort_active_streams() -> usize {
+    let mut streams = ACTIVE_STREAMS.lock().unwrap();
+    let count = streams.len();
+    for (_, handle) in streams.drain() {
+        handle.abort();
+    }
+    if count > 0 {
+        debug!("Aborted {} active chat completion stream(s)", count);
     }
+    count
+}
 
-    let completion: ChatCompletionResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
+/// Stream of content deltas from a streaming chat completion.
+///
+/// Ends when the provider sends `[DONE]`, the body closes, or the stream is
+/// aborted through [`abort_active_streams`].
+pub struct ChatDeltaStream {
+    id: u64,
+    inner: Abortable<BoxStream<'static, Result<String, String>>>,
+}
+
+impl ChatDeltaStream {
+    pub fn was_aborted(&self) -> bool {
+        self.inner.is_aborted()
+    }
+}
+
+impl Stream for ChatDeltaStream {
+    type Item = Result<String, String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+impl Drop for ChatDeltaStream {
+    fn drop(&mut self) {
+        if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+            streams.remove(&self.id);
+        }
+    }
+}
 
-    Ok(completion
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.clone()))
+/// Incremental parser for `text/event-stream` response bodies. Handles events
+/// split across network chunks, including splits inside multi-byte characters.
+#[derive(Default)]
+struct SseParser {
+    protocol: WireProtocol,
+    buffer: Vec<u8>,
+    done: bool,
 }
 
-/// Fetch available models from an OpenAI-compatible API
+impl SseParser {
+    fn new(protocol: WireProtocol) -> Self {
+        Self {
+            protocol,
+            ..Self::default()
+        }
+    }
+
+    /// Feed raw bytes and return any content deltas from complete lines.
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<String>, String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut deltas = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            if let Some(delta) = self.parse_line(&String::from_utf8_lossy(&line))? {
+                deltas.push(delta);
+            }
+            if self.done {
+                break;
+            }
+        }
+        Ok(deltas)
+    }
+
+    /// Parse whatever is left once the body has closed.
+    fn finish(&mut self) -> Result<Vec<String>, String> {
+        let rest = std::mem::take(&mut self.buffer);
+        let line = String::from_utf8_lossy(&rest);
+        Ok(self.parse_line(&line)?.into_iter().collect())
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<Option<String>, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let payload = match line.strip_prefix("data:") {
+            Some(payload) => payload.trim_start(),
+            // Blank separators, comments and `event:`/`id:` fields carry no content.
+            // Anthropic repeats the event name in the payload's `type`.
+            None => return Ok(None),
+        };
+
+        match self.protocol {
+            WireProtocol::OpenaiChat => self.parse_openai_payload(payload),
+            WireProtocol::AnthropicMessages => self.parse_anthropic_payload(payload),
+        }
+    }
+
+    fn parse_openai_payload(&mut self, payload: &str) -> Result<Option<String>, String> {
+        if payload == "[DONE]" {
+            self.done = true;
+            return Ok(None);
+        }
+
+        // Checked first: chunks tolerate unknown fields, so an error object
+        // would otherwise parse as an empty chunk and be silently dropped.
+        if serde_json::from_str::<ApiErrorBody>(payload).is_ok() {
+            return Err(format!(
+                "API stream returned an error: {}",
+                parse_error_message(self.protocol, payload)
+            ));
+        }
+
+        serde_json::from_str::<ChatCompletionChunk>(payload)
+            .map(|chunk| {
+                chunk
+                    .choices
+                    .into_iter()
+                    .next()
+                    .and_then(|choice| choice.delta.content)
+                    .filter(|content| !content.is_empty())
+            })
+            .map_err(|e| format!("Failed to parse stream chunk: {}", e))
+    }
+
+    fn parse_anthropic_payload(&mut self, payload: &str) -> Result<Option<String>, String> {
+        let event = serde_json::from_str::<AnthropicStreamEvent>(payload)
+            .map_err(|e| format!("Failed to parse stream event: {}", e))?;
+        match event.kind.as_str() {
+            "content_block_delta" => Ok(event
+                .delta
+                .and_then(|delta| delta.text)
+                .filter(|text| !text.is_empty())),
+            "message_stop" => {
+                self.done = true;
+                Ok(None)
+            }
+            "error" => {
+                let detail = event.error.unwrap_or_default();
+                Err(format!(
+                    "API stream returned an error: {}: {}",
+                    detail.kind.as_deref().unwrap_or("error"),
+                    detail.message.as_deref().unwrap_or("unknown error")
+                ))
+            }
+            // message_start, content_block_start/stop, message_delta, ping
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Send a streaming chat completion request using the provider's wire protocol.
+/// Returns a stream of content deltas once the response headers arrive.
+/// `timeout` bounds the full exchange, so a stream that stalls part-way
+/// through ends with a timeout error.
+pub async fn stream_chat_completion(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    prompt: String,
+    system_message: Option<String>,
+    timeout: Option<Duration>,
+) -> Result<ChatDeltaStream, String> {
+    let url = completion_url(provider);
+    let protocol = provider.wire_protocol;
+
+    debug!("Sending streaming chat completion request to: {}", url);
+
+    let client = create_client(provider, &api_key)?;
+
+    // Register before sending so a cancel that lands while connecting still aborts.
+    let id = NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed);
+    let (connect_handle, connect_registration) = AbortHandle::new_pair();
+    ACTIVE_STREAMS
+        .lock()
+        .unwrap()
+        .insert(id, connect_handle.clone());
+
+    let sent = Abortable::new(
+        with_completion_body(
+            with_deadline(client.post(&url), timeout).header(ACCEPT, "text/event-stream"),
+            protocol,
+            model,
+            prompt,
+            system_message,
+            true,
+        )
+        .send(),
+        connect_registration,
+    )
+    .await;
+    let response = match sent {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => {
+            ACTIVE_STREAMS.lock().unwrap().remove(&id);
+            return Err(describe_request_error(&e, timeout));
+        }
+        Err(_) => {
+            // Cancelled before the response arrived: hand back an already
+            // aborted, empty stream so callers see a cancellation, not an error.
+            let (handle, registration) = AbortHandle::new_pair();
+            handle.abort();
+            return Ok(ChatDeltaStream {
+                id,
+                inner: Abortable::new(futures_util::stream::empty().boxed(), registration),
+            });
+        }
+    };
+
+    if !response.status().is_success() {
+        ACTIVE_STREAMS.lock().unwrap().remove(&id);
+        return Err(error_from_response(protocol, response).await);
+    }
+
+    let deltas = futures_util::stream::unfold(
+        (
+            response.bytes_stream(),
+            SseParser::new(protocol),
+            VecDeque::new(),
+        ),
+        move |(mut body, mut parser, mut pending)| async move {
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((item, (body, parser, pending)));
+                }
+                if parser.done {
+                    return None;
+                }
+                let parsed = match body.next().await {
+                    Some(Ok(chunk)) => parser.push(&chunk),
+                    Some(Err(e)) if e.is_timeout() => Err(describe_request_error(&e, timeout)),
+                    Some(Err(e)) => Err(format!("Failed to read response stream: {}", e)),
+                    None => {
+                        let rest = parser.finish();
+                        parser.done = true;
+                        rest
+                    }
+                };
+                match parsed {
+                    Ok(deltas) => pending.extend(deltas.into_iter().map(Ok)),
+                    Err(e) => {
+                        parser.done = true;
+                        pending.push_back(Err(e));
+                    }
+                }
+            }
+        },
+    )
+    .boxed();
+
+    let (body_handle, body_registration) = AbortHandle::new_pair();
+    ACTIVE_STREAMS
+        .lock()
+        .unwrap()
+        .insert(id, body_handle.clone());
+    if connect_handle.is_aborted() {
+        body_handle.abort();
+    }
+
+    Ok(ChatDeltaStream {
+        id,
+        inner: Abortable::new(deltas, body_registration),
+    })
+}
+
+/// Fetch available models from the provider's models endpoint.
 /// Returns a list of model IDs
 pub async fn fetch_models(
     provider: &PostProcessProvider,
@@ -156,8 +644,13 @@ pub async fn fetch_models(
 
     let client = create_client(provider, &api_key)?;
 
-    let response = client
-        .get(&url)
+    let mut request = client.get(&url);
+    if provider.wire_protocol == WireProtocol::AnthropicMessages {
+        // Anthropic pages the model list (default 20 per page).
+        request = request.query(&[("limit", "1000")]);
+    }
+
+    let response = request
         .send()
         .await
         .map_err(|e| format!("Failed to fetch models: {}", e))?;
@@ -170,7 +663,8 @@ pub async fn fetch_models(
             .unwrap_or_else(|_| "Unknown error".to_string());
         return Err(format!(
             "Model list request failed ({}): {}",
-            status, error_text
+            status,
+            parse_error_message(provider.wire_protocol, &error_text)
         ));
     }
 
@@ -181,7 +675,7 @@ pub async fn fetch_models(
 
     let mut models = Vec::new();
 
-    // Handle OpenAI format: { data: [ { id: "..." }, ... ] }
+    // Handle OpenAI and Anthropic format: { data: [ { id: "..." }, ... ] }
     if let Some(data) = parsed.get("data").and_then(|d| d.as_array()) {
         for entry in data {
             if let Some(id) = entry.get("id").and_then(|i| i.as_str()) {
@@ -202,3 +696,129 @@ pub async fn fetch_models(
 
     Ok(models)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_parser_collects_deltas_until_done() {
+        let mut parser = SseParser::default();
+        let body = b"data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n\
+data: {\"choices\":[{\"delta\":{\"content\":\"Hello\"}}]}\n\n\
+data: {\"choices\":[{\"delta\":{\"content\":\" world\"}}]}\n\n\
+data: [DONE]\n\n";
+        let deltas = parser.push(body).unwrap();
+        assert_eq!(deltas, vec!["Hello", " world"]);
+        assert!(parser.done);
+    }
+
+    #[test]
+    fn sse_parser_handles_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"caf\u{e9}\"}}]}\n\n".as_bytes();
+        // Split inside the multi-byte 'é' as well as mid-line.
+        let split = body.iter().position(|b| *b == 0xC3).unwrap() + 1;
+        assert!(parser.push(&body[..split]).unwrap().is_empty());
+        assert_eq!(parser.push(&body[split..]).unwrap(), vec!["caf\u{e9}"]);
+        assert!(!parser.done);
+    }
+
+    #[test]
+    fn sse_parser_ignores_comments_and_flushes_trailing_line() {
+        let mut parser = SseParser::default();
+        assert!(parser
+            .push(b": keep-alive\nevent: message\n")
+            .unwrap()
+            .is_empty());
+        assert!(parser
+            .push(b"data: {\"choices\":[{\"delta\":{\"content\":\"tail\"}}]}")
+            .unwrap()
+            .is_empty());
+        assert_eq!(parser.finish().unwrap(), vec!["tail"]);
+    }
+
+    #[test]
+    fn sse_parser_surfaces_provider_errors() {
+        let mut parser = SseParser::default();
+        let err = parser
+            .push(b"data: {\"error\":{\"message\":\"rate limited\"}}\n")
+            .unwrap_err();
+        assert!(err.contains("rate limited"));
+    }
+
+    #[test]
+    fn non_streaming_request_omits_stream_flag() {
+        let request = ChatCompletionRequest {
+            model: "m".to_string(),
+            messages: build_messages("hi".to_string(), None),
+            stream: false,
+        };
+        let value = serde_json::to_value(&request).unwrap();
+        assert!(value.get("stream").is_none());
+    }
+
+    #[test]
+    fn anthropic_request_puts_system_prompt_at_top_level() {
+        let request = AnthropicMessagesRequest {
+            model: "claude".to_string(),
+            max_tokens: ANTHROPIC_MAX_TOKENS,
+            system: Some("rules".to_string()),
+            messages: build_messages("hi".to_string(), None),
+            stream: false,
+        };
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["system"], "rules");
+        assert_eq!(value["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(value["messages"][0]["role"], "user");
+        assert!(value.get("stream").is_none());
+    }
+
+    #[test]
+    fn anthropic_completion_joins_text_blocks() {
+        let body = r#"{"content":[{"type":"text","text":"Hello"},{"type":"tool_use","id":"x"},{"type":"text","text":" there"}]}"#;
+        assert_eq!(
+            parse_completion(WireProtocol::AnthropicMessages, body).unwrap(),
+            Some("Hello there".to_string())
+        );
+        assert_eq!(
+            parse_completion(WireProtocol::AnthropicMessages, r#"{"content":[]}"#).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn sse_parser_reads_anthropic_events() {
+        let mut parser = SseParser::new(WireProtocol::AnthropicMessages);
+        let body = b"event: message_start\ndata: {\"type\":\"message_start\",\"message\":{}}\n\n\
+event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n\
+event: ping\ndata: {\"type\":\"ping\"}\n\n\
+event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n";
+        assert_eq!(parser.push(body).unwrap(), vec!["Hi"]);
+        assert!(parser.done);
+
+        let mut parser = SseParser::new(WireProtocol::AnthropicMessages);
+        let err = parser
+            .push(b"data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n")
+            .unwrap_err();
+        assert!(err.contains("overloaded_error: Overloaded"));
+    }
+
+    #[test]
+    fn error_messages_are_extracted_per_protocol() {
+        let anthropic = r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#;
+        assert_eq!(
+            parse_error_message(WireProtocol::AnthropicMessages, anthropic),
+            "authentication_error: invalid x-api-key"
+        );
+        let openai = r#"{"error":{"message":"Incorrect API key","type":"invalid_request_error"}}"#;
+        assert_eq!(
+            parse_error_message(WireProtocol::OpenaiChat, openai),
+            "Incorrect API key"
+        );
+        assert_eq!(
+            parse_error_message(WireProtocol::OpenaiChat, "Bad Gateway"),
+            "Bad Gateway"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::{
+    list_input_devices, vad::SmoothedVad, AudioRecorder, SegmentSink, SileroVad,
+};
 use crate::helpers::clamshell;
 use crate::managers::recording_pipeline::{
     can_attempt_start, cancel as cancel_state, commit_start, is_recording as is_recording_state,
@@ -315,7 +317,7 @@ impl AudioRecordingManager {
         let selected_device = self.get_effective_microphone_device(&settings);
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device, None, None)
+            rec.open(selected_device)
                 .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
         }
 
@@ -378,7 +380,9 @@ impl AudioRecordingManager {
 
     /* ---------- recording --------------------------------------------------- */
 
-    pub fn try_start_recording(&self, binding_id: &str) -> bool {
+    /// Start recording for `binding_id`. With `segments`, speech cut off at
+    /// pauses is sent there while recording continues.
+    pub fn try_start_recording(&self, binding_id: &str, segments: Option<SegmentSink>) -> bool {
         // Keep state lock duration minimal; do not hold it across mic open/start calls.
         if !can_attempt_start(&self.state.lock().unwrap()) {
             false
@@ -394,7 +398,7 @@ impl AudioRecordingManager {
             let started = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                 self.last_level_update_ms
                     .store(now_millis(), Ordering::Relaxed);
-                rec.start().is_ok()
+                rec.start_with_segments(segments).is_ok()
             } else {
                 error!("Recorder not available");
                 false
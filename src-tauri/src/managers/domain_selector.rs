@@ -1,6 +1,8 @@
 use crate::jargon::{JargonCorrection, JargonProfile};
 use crate::settings::{AppSettings, LLMPrompt};
 use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::collections::{HashMap, HashSet};
 use std::sync::mpsc;
 use std::sync::Mutex;
@@ -9,6 +11,9 @@ use std::time::Duration;
 #[derive(Debug, Clone)]
 pub struct DomainContext {
     pub text: String,
+    /// Frontmost app, when a caller looked it up; prompts limited to certain
+    /// apps are skipped without it.
+    pub app: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -51,12 +56,14 @@ impl DomainSelectorManager {
         let min_score = settings.domain_selector_min_score.clamp(0.0, 1.0);
         let hysteresis = settings.domain_selector_hysteresis.clamp(0.0, 1.0);
 
-        let profiles = build_profile_map(settings);
+        let profiles = crate::jargon::profiles_with_packs(&settings.jargon_packs);
+        let known_profiles: HashSet<String> = profiles.keys().cloned().collect();
+        let weights = learned_weights(settings);
         let context_text = context.text.clone();
         let (tx, rx) = mpsc::channel();
 
         std::thread::spawn(move || {
-            let ranked = score_profiles(&profiles, &context_text);
+            let ranked = score_profiles(&profiles, &context_text, &weights);
             let _ = tx.send(ranked);
         });
 
@@ -80,26 +87,14 @@ impl DomainSelectorManager {
             return None;
         }
 
-        let maybe_last = self
+        let last = self
             .last_selection
             .lock()
             .ok()
-            .and_then(|last| (*last).clone());
-        if let Some(last) = maybe_last {
-            if let Some(current_top) = selected.first() {
-                let switched = current_top.profile_id != last.profile_id;
-                let beat_by_margin = current_top.score >= (last.score + hysteresis);
-                if switched && !beat_by_margin {
-                    selected.insert(
-                        0,
-                        RankedProfile {
-                            profile_id: last.profile_id.clone(),
-                            score: last.score,
-                        },
-                    );
-                    selected.truncate(top_k);
-                }
-            }
+            .and_then(|last| (*last).clone())
+            .filter(|last| known_profiles.contains(&last.profile_id));
+        if keep_previous(&mut selected, last, hysteresis) {
+            selected.truncate(top_k);
         }
 
         if let Some(top) = selected.first() {
@@ -139,19 +134,18 @@ impl DomainSelectorManager {
         // fail open and let the normal selected prompt flow continue.
         let timeout_ms = settings.domain_selector_timeout_ms.clamp(10, 80);
         let timeout = Duration::from_millis(timeout_ms);
-        let min_score = settings.domain_selector_min_score.clamp(0.0, 1.0);
-        let hysteresis = settings.domain_selector_hysteresis.clamp(0.0, 1.0);
-        let context_text: String = context.text.chars().take(2000).collect();
+        let context_text = prompt_context_text(&context.text);
+        let app = context.app.clone();
         let prompts_vec = prompts.to_vec();
         let (tx, rx) = mpsc::channel();
 
         std::thread::spawn(move || {
-            let ranked = score_prompts(&prompts_vec, &context_text);
-            let _ = tx.send(ranked);
+            let ranking = rank_prompts(&prompts_vec, &context_text, app.as_deref());
+            let _ = tx.send(ranking);
         });
 
-        let mut ranked = match rx.recv_timeout(timeout) {
-            Ok(ranked) => ranked,
+        let mut ranking = match rx.recv_timeout(timeout) {
+            Ok(ranking) => ranking,
             Err(_) => {
                 warn!(
                     "Prompt selector sidecar timed out after {}ms",
@@ -161,204 +155,571 @@ impl DomainSelectorManager {
             }
         };
 
-        if ranked.is_empty() || ranked[0].score < min_score {
+        if self.order_prompts(settings, &mut ranking) == PromptRouteOutcome::BelowMinScore {
             return None;
         }
 
-        if let Some(last) = self
-            .last_prompt_selection
-            .lock()
-            .ok()
-            .and_then(|last| (*last).clone())
-        {
-            let current_top = &ranked[0];
-            let switched = current_top.profile_id != last.profile_id;
-            let beat_by_margin = current_top.score >= (last.score + hysteresis);
-            if switched && !beat_by_margin {
-                ranked.insert(
-                    0,
-                    RankedProfile {
-                        profile_id: last.profile_id,
-                        score: last.score,
-                    },
-                );
-            }
-        }
-
-        let selected = ranked[0].profile_id.clone();
+        let top = &ranking.ranked[0];
         if let Ok(mut last) = self.last_prompt_selection.lock() {
             *last = Some(LastSelection {
-                profile_id: selected.clone(),
-                score: ranked[0].score,
+                profile_id: top.profile_id.clone(),
+                score: top.score,
             });
         }
         debug!(
             "Post-process prompt selector picked '{}': {:.3}",
-            selected, ranked[0].score
+            top.profile_id, top.score
         );
-        Some(selected)
+        Some(top.profile_id.clone())
     }
-}
 
-fn build_profile_map(settings: &AppSettings) -> HashMap<String, JargonProfile> {
-    let mut profiles = crate::jargon::builtin_profiles();
-    for pack in &settings.jargon_packs {
-        profiles.insert(
-            pack.id.clone(),
-            JargonProfile {
-                label: pack.label.clone(),
-                terms: pack.terms.clone(),
-                corrections: pack.corrections.clone(),
-            },
-        );
+    /// Route `context` like [`Self::select_post_process_prompt_with_timeout`]
+    /// would, without a timeout and without remembering the choice, and
+    /// report how every prompt fared.
+    pub fn explain_prompt_route(
+        &self,
+        settings: &AppSettings,
+        context: &DomainContext,
+        prompts: &[LLMPrompt],
+    ) -> PromptRouteExplanation {
+        let context_text = prompt_context_text(&context.text);
+        let app = context.app.as_deref();
+        let mut ranking = rank_prompts(prompts, &context_text, app);
+        // Scores as matched, before hysteresis may put the previous pick first.
+        let scores: HashMap<String, f32> = ranking
+            .ranked
+            .iter()
+            .map(|item| (item.profile_id.clone(), item.score))
+            .collect();
+        let outcome = if settings.post_process_auto_prompt_selection {
+            self.order_prompts(settings, &mut ranking)
+        } else {
+            PromptRouteOutcome::Disabled
+        };
+        let prompt_id = match outcome {
+            PromptRouteOutcome::BestMatch | PromptRouteOutcome::KeptPrevious => {
+                ranking.ranked.first().map(|top| top.profile_id.clone())
+            }
+            PromptRouteOutcome::Disabled | PromptRouteOutcome::BelowMinScore => None,
+        };
+
+        let text_tokens = tokens(&context_text);
+        let text_token_set: HashSet<String> = text_tokens.iter().cloned().collect();
+        let candidates = prompts
+            .iter()
+            .map(|prompt| {
+                let document: HashSet<String> = prompt_document(prompt).into_iter().collect();
+                let mut matched_words: Vec<String> =
+                    text_token_set.intersection(&document).cloned().collect();
+                matched_words.sort();
+                PromptRouteCandidate {
+                    prompt_id: prompt.id.clone(),
+                    name: prompt.name.clone(),
+                    score: scores.get(&prompt.id).copied().unwrap_or(0.0),
+                    matched_words,
+                    matched_triggers: prompt
+                        .triggers
+                        .iter()
+                        .filter(|trigger| contains_phrase(&text_tokens, trigger))
+                        .cloned()
+                        .collect(),
+                    blocked_by: negative_match(prompt, &text_tokens).cloned(),
+                    wrong_app: !applies_to_app(prompt, app),
+                    priority: prompt.priority,
+                }
+            })
+            .collect();
+
+        PromptRouteExplanation {
+            prompt_id,
+            outcome,
+            min_score: settings.domain_selector_min_score.clamp(0.0, 1.0),
+            candidates,
+        }
+    }
+
+    /// Put the prompt to use first in `ranking`, keeping the previous pick
+    /// unless the new leader beats it by the hysteresis margin.
+    fn order_prompts(
+        &self,
+        settings: &AppSettings,
+        ranking: &mut PromptRanking,
+    ) -> PromptRouteOutcome {
+        let min_score = settings.domain_selector_min_score.clamp(0.0, 1.0);
+        let hysteresis = settings.domain_selector_hysteresis.clamp(0.0, 1.0);
+        if ranking
+            .ranked
+            .first()
+            .map_or(true, |top| top.score < min_score)
+        {
+            return PromptRouteOutcome::BelowMinScore;
+        }
+
+        // A prompt ruled out for this text or app cannot be kept.
+        let last = self
+            .last_prompt_selection
+            .lock()
+            .ok()
+            .and_then(|last| (*last).clone())
+            .filter(|last| ranking.eligible.contains(&last.profile_id));
+        if keep_previous(&mut ranking.ranked, last, hysteresis) {
+            PromptRouteOutcome::KeptPrevious
+        } else {
+            PromptRouteOutcome::BestMatch
+        }
     }
-    profiles
 }
 
-fn score_profiles(profiles: &HashMap<String, JargonProfile>, text: &str) -> Vec<RankedProfile> {
-    let context_tokens = tokenize(text);
-    if context_tokens.is_empty() {
-        return Vec::new();
+/// Put `last` back on top of `ranked` unless the new leader beats it by
+/// `margin`, so near-ties do not flip the selection between dictations.
+fn keep_previous(
+    ranked: &mut Vec<RankedProfile>,
+    last: Option<LastSelection>,
+    margin: f32,
+) -> bool {
+    let (Some(last), Some(top)) = (last, ranked.first()) else {
+        return false;
+    };
+    if top.profile_id == last.profile_id || top.score >= last.score + margin {
+        return false;
     }
+    ranked.retain(|item| item.profile_id != last.profile_id);
+    ranked.insert(
+        0,
+        RankedProfile {
+            profile_id: last.profile_id,
+            score: last.score,
+        },
+    );
+    true
+}
 
-    let mut ranked = Vec::new();
-    for (profile_id, profile) in profiles {
-        let mut score = 0.0_f32;
+/// How prompt routing treated a text, as reported by `explain_prompt_route`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptRouteOutcome {
+    /// Automatic prompt selection is off; the selected prompt is used.
+    Disabled,
+    /// No prompt reached the minimum score; the selected prompt is used.
+    BelowMinScore,
+    /// The best-scoring prompt is used.
+    BestMatch,
+    /// The previous prompt is used: the best one did not beat it by the
+    /// hysteresis margin.
+    KeptPrevious,
+}
 
-        for term in &profile.terms {
-            let term_tokens = tokenize(term);
-            if term_tokens.is_empty() {
-                continue;
-            }
-            let overlap = token_overlap_ratio(&context_tokens, &term_tokens);
-            score += overlap * 1.0;
-        }
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct PromptRouteExplanation {
+    /// Prompt the text routes to, or `None` to fall back to the selected one.
+    pub prompt_id: Option<String>,
+    pub outcome: PromptRouteOutcome,
+    pub min_score: f32,
+    /// Every prompt, in settings order.
+    pub candidates: Vec<PromptRouteCandidate>,
+}
 
-        for JargonCorrection { from, to } in &profile.corrections {
-            let from_tokens = tokenize(from);
-            if !from_tokens.is_empty() {
-                score += token_overlap_ratio(&context_tokens, &from_tokens) * 1.2;
-            }
-            let to_tokens = tokenize(to);
-            if !to_tokens.is_empty() {
-                score += token_overlap_ratio(&context_tokens, &to_tokens) * 1.0;
-            }
-        }
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct PromptRouteCandidate {
+    pub prompt_id: String,
+    pub name: String,
+    /// Routing score after priority; 0 when nothing matched.
+    pub score: f32,
+    /// Words of the text found in the prompt's id, name or triggers.
+    pub matched_words: Vec<String>,
+    pub matched_triggers: Vec<String>,
+    /// Negative keyword that ruled the prompt out.
+    pub blocked_by: Option<String>,
+    /// The prompt is limited to other apps.
+    pub wrong_app: bool,
+    pub priority: i32,
+}
 
-        let normalization =
-            (profile.terms.len() as f32 + profile.corrections.len() as f32 * 1.5).max(1.0);
-        let normalized = (score / normalization).clamp(0.0, 1.0);
-        if normalized > 0.0 {
-            ranked.push(RankedProfile {
-                profile_id: profile_id.clone(),
-                score: normalized,
-            });
-        }
+/// BM25 term-frequency saturation.
+const BM25_K1: f32 = 1.2;
+/// BM25 document-length normalisation.
+const BM25_B: f32 = 0.75;
+/// Raw BM25 score that maps to 0.5. Scores are squashed into 0..1 so the
+/// min-score and hysteresis settings keep their meaning.
+const BM25_HALF_SCORE: f32 = 2.0;
+
+/// Words too common to say anything about a domain.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "been", "by", "for", "from", "in", "into", "is",
+    "it", "me", "my", "of", "on", "or", "our", "the", "this", "that", "to", "was", "we", "were",
+    "with", "you", "your",
+];
+
+/// Pseudo-count added to both sides of a profile's feedback, so a few
+/// signals only nudge its weight.
+const FEEDBACK_PRIOR: f32 = 8.0;
+const MIN_PROFILE_WEIGHT: f32 = 0.5;
+const MAX_PROFILE_WEIGHT: f32 = 1.5;
+/// Counts are halved past this total so recent behaviour dominates.
+const MAX_FEEDBACK_COUNT: u32 = 200;
+
+/// How often the user has kept or corrected against a jargon profile. See
+/// [`record_profile_toggles`] and [`record_edit_feedback`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+pub struct ProfileFeedback {
+    /// Times the profile was enabled, or a dictation was edited towards its terms.
+    pub kept: u32,
+    /// Times the profile was disabled, or its terms were edited out.
+    pub corrected: u32,
+}
+
+impl ProfileFeedback {
+    /// Multiplier for the profile's score: 1.0 without feedback.
+    pub fn weight(&self) -> f32 {
+        ((self.kept as f32 + FEEDBACK_PRIOR) / (self.corrected as f32 + FEEDBACK_PRIOR))
+            .clamp(MIN_PROFILE_WEIGHT, MAX_PROFILE_WEIGHT)
     }
 
-    ranked.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| a.profile_id.cmp(&b.profile_id))
-    });
-    ranked
+    fn add(&mut self, kept: bool) {
+        if kept {
+            self.kept += 1;
+        } else {
+            self.corrected += 1;
+        }
+        if self.kept + self.corrected > MAX_FEEDBACK_COUNT {
+            self.kept /= 2;
+            self.corrected /= 2;
+        }
+    }
 }
 
-fn tokenize(text: &str) -> HashSet<String> {
-    text.split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
-        .map(|token| token.trim().to_lowercase())
-        .filter(|token| token.len() > 1)
+/// Learned profile weights, or none when learning is off.
+fn learned_weights(settings: &AppSettings) -> HashMap<String, f32> {
+    if !settings.domain_selector_learned_weights {
+        return HashMap::new();
+    }
+    settings
+        .domain_selector_profile_feedback
+        .iter()
+        .map(|(id, feedback)| (id.clone(), feedback.weight()))
         .collect()
 }
 
-fn token_overlap_ratio(
-    context_tokens: &HashSet<String>,
-    candidate_tokens: &HashSet<String>,
-) -> f32 {
-    if candidate_tokens.is_empty() {
-        return 0.0;
+/// Count profiles the user switched on as kept and those switched off as
+/// corrected against.
+pub fn record_profile_toggles(settings: &mut AppSettings, previous: &[String]) {
+    if !settings.domain_selector_learned_weights {
+        return;
     }
-    let overlap = candidate_tokens
-        .iter()
-        .filter(|token| context_tokens.contains(*token))
-        .count();
-    overlap as f32 / candidate_tokens.len() as f32
+    let current = settings.jargon_enabled_profiles.clone();
+    let feedback = &mut settings.domain_selector_profile_feedback;
+    for id in current.iter().filter(|id| !previous.contains(id)) {
+        feedback.entry(id.clone()).or_default().add(true);
+    }
+    for id in previous.iter().filter(|id| !current.contains(id)) {
+        feedback.entry(id.clone()).or_default().add(false);
+    }
+}
+
+/// Learn from a history edit: a profile whose words the user typed in was
+/// kept, one whose words they took out was corrected against.
+pub fn record_edit_feedback(settings: &mut AppSettings, original: &str, edited: &str) {
+    if !settings.domain_selector_learned_weights {
+        return;
+    }
+    let before = tokenize(original);
+    let after = tokenize(edited);
+    let added: HashSet<&String> = after.difference(&before).collect();
+    let removed: HashSet<&String> = before.difference(&after).collect();
+    if added.is_empty() && removed.is_empty() {
+        return;
+    }
+
+    let profiles = crate::jargon::profiles_with_packs(&settings.jargon_packs);
+    for (id, profile) in profiles {
+        let vocabulary: HashSet<String> = profile
+            .terms
+            .iter()
+            .chain(profile.corrections.iter().map(|c| &c.to))
+            .flat_map(|text| tokens(text))
+            .collect();
+        let feedback = settings
+            .domain_selector_profile_feedback
+            .entry(id)
+            .or_default();
+        if added.iter().any(|token| vocabulary.contains(*token)) {
+            feedback.add(true);
+        }
+        if removed.iter().any(|token| vocabulary.contains(*token)) {
+            feedback.add(false);
+        }
+    }
+    settings
+        .domain_selector_profile_feedback
+        .retain(|_, feedback| *feedback != ProfileFeedback::default());
 }
 
-fn score_prompts(prompts: &[LLMPrompt], text: &str) -> Vec<RankedProfile> {
+/// Rank `profiles` by BM25 over their terms and the corrections that fit
+/// the context, then apply learned `weights`.
+fn score_profiles(
+    profiles: &HashMap<String, JargonProfile>,
+    text: &str,
+    weights: &HashMap<String, f32>,
+) -> Vec<RankedProfile> {
     let context_tokens = tokenize(text);
     if context_tokens.is_empty() {
         return Vec::new();
     }
-    let joined_text = text.to_lowercase();
-    let mut ranked = Vec::new();
 
-    for prompt in prompts {
-        let mut score = 0.0_f32;
-        let signature = prompt_signature(prompt);
-        let signature_tokens = tokenize(&signature);
-        score += token_overlap_ratio(&context_tokens, &signature_tokens) * 1.8;
+    let documents = profiles
+        .iter()
+        .map(|(profile_id, profile)| {
+            let mut document: Vec<String> = profile.terms.iter().flat_map(|t| tokens(t)).collect();
+            for correction in &profile.corrections {
+                if !correction_fits_context(correction, &context_tokens) {
+                    continue;
+                }
+                document.extend(tokens(&correction.from));
+                document.extend(tokens(&correction.to));
+                // A matching neighbour word is extra evidence for the domain.
+                document.extend(correction.near.iter().flat_map(|word| tokens(word)));
+            }
+            (profile_id.clone(), document)
+        })
+        .collect();
+
+    let mut ranked = Bm25::new(documents).rank(&context_tokens);
+    if !weights.is_empty() {
+        for item in &mut ranked {
+            let weight = weights.get(&item.profile_id).copied().unwrap_or(1.0);
+            item.score = (item.score * weight).clamp(0.0, 1.0);
+        }
+        sort_ranked(&mut ranked);
+    }
+    ranked
+}
+
+/// A correction counts towards its profile only where it could apply: one
+/// of its `near` words is present and none of its `not_near` words are.
+fn correction_fits_context(
+    correction: &JargonCorrection,
+    context_tokens: &HashSet<String>,
+) -> bool {
+    let present = |words: &[String]| {
+        words
+            .iter()
+            .flat_map(|word| tokenize(word))
+            .any(|token| context_tokens.contains(&token))
+    };
+    (correction.near.is_empty() || present(&correction.near)) && !present(&correction.not_near)
+}
+
+/// Lowercased, stemmed tokens of `text` without stopwords, in order.
+fn tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+        .map(|token| token.trim().to_lowercase())
+        .filter(|token| token.len() > 1 && !STOPWORDS.contains(&token.as_str()))
+        .map(|token| stem(&token))
+        .collect()
+}
 
-        for keyword in prompt_keywords(&prompt.id) {
-            if joined_text.contains(keyword) {
-                score += 0.2;
+fn tokenize(text: &str) -> HashSet<String> {
+    tokens(text).into_iter().collect()
+}
+
+/// Strip the commonest English suffixes so "reviewed" matches "review".
+fn stem(token: &str) -> String {
+    if token.len() > 4 {
+        if let Some(base) = token.strip_suffix("ies") {
+            return format!("{}y", base);
+        }
+        if let Some(base) = token.strip_suffix("ing") {
+            return base.to_string();
+        }
+    }
+    if token.len() > 3 {
+        if let Some(base) = token.strip_suffix("ed") {
+            return base.to_string();
+        }
+        if !token.ends_with("ss") {
+            if let Some(base) = token.strip_suffix('s') {
+                return base.to_string();
             }
         }
+    }
+    token.to_string()
+}
 
-        let normalized = score.clamp(0.0, 1.0);
-        if normalized > 0.0 {
-            ranked.push(RankedProfile {
-                profile_id: prompt.id.clone(),
-                score: normalized,
-            });
+/// Okapi BM25 over a handful of documents, with IDF taken across all of them.
+struct Bm25 {
+    documents: Vec<(String, HashMap<String, f32>, f32)>,
+    idf: HashMap<String, f32>,
+    average_len: f32,
+}
+
+impl Bm25 {
+    fn new(documents: Vec<(String, Vec<String>)>) -> Self {
+        let count = documents.len() as f32;
+        let mut document_frequency: HashMap<String, f32> = HashMap::new();
+        let mut total_len = 0.0;
+        let documents: Vec<(String, HashMap<String, f32>, f32)> = documents
+            .into_iter()
+            .map(|(id, tokens)| {
+                let mut frequencies: HashMap<String, f32> = HashMap::new();
+                for token in &tokens {
+                    *frequencies.entry(token.clone()).or_default() += 1.0;
+                }
+                for token in frequencies.keys() {
+                    *document_frequency.entry(token.clone()).or_default() += 1.0;
+                }
+                total_len += tokens.len() as f32;
+                (id, frequencies, tokens.len() as f32)
+            })
+            .collect();
+        let idf = document_frequency
+            .into_iter()
+            .map(|(token, df)| (token, (1.0 + (count - df + 0.5) / (df + 0.5)).ln()))
+            .collect();
+        Self {
+            documents,
+            idf,
+            average_len: (total_len / count.max(1.0)).max(1.0),
         }
     }
 
+    /// Documents matching `query`, best first, with scores in 0..1.
+    fn rank(&self, query: &HashSet<String>) -> Vec<RankedProfile> {
+        let mut ranked: Vec<RankedProfile> = self
+            .documents
+            .iter()
+            .filter_map(|(id, frequencies, len)| {
+                let length_norm = 1.0 - BM25_B + BM25_B * len / self.average_len;
+                let score: f32 = query
+                    .iter()
+                    .filter_map(|token| {
+                        let tf = *frequencies.get(token)?;
+                        let idf = self.idf.get(token).copied().unwrap_or(0.0);
+                        Some(idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * length_norm))
+                    })
+                    .sum();
+                (score > 0.0).then(|| RankedProfile {
+                    profile_id: id.clone(),
+                    score: score / (score + BM25_HALF_SCORE),
+                })
+            })
+            .collect();
+        sort_ranked(&mut ranked);
+        ranked
+    }
+}
+
+fn sort_ranked(ranked: &mut [RankedProfile]) {
     ranked.sort_by(|a, b| {
         b.score
             .partial_cmp(&a.score)
             .unwrap_or(std::cmp::Ordering::Equal)
             .then_with(|| a.profile_id.cmp(&b.profile_id))
     });
-    ranked
 }
 
-fn prompt_signature(prompt: &LLMPrompt) -> String {
+/// Routing score added per priority level.
+const PRIORITY_STEP: f32 = 0.05;
+/// Priorities beyond this would outweigh what the text says.
+pub const MAX_PROMPT_PRIORITY: i32 = 5;
+
+/// Prompts routing may pick for a text, best first.
+struct PromptRanking {
+    ranked: Vec<RankedProfile>,
+    /// Prompts not ruled out by a negative keyword or by `apps`.
+    eligible: HashSet<String>,
+}
+
+/// Rank `prompts` by BM25 over their id, name and triggers, after dropping
+/// those ruled out for this text or app, then apply priorities.
+fn rank_prompts(prompts: &[LLMPrompt], text: &str, app: Option<&str>) -> PromptRanking {
+    let text_tokens = tokens(text);
+    let eligible: Vec<&LLMPrompt> = prompts
+        .iter()
+        .filter(|prompt| {
+            negative_match(prompt, &text_tokens).is_none() && applies_to_app(prompt, app)
+        })
+        .collect();
+    let eligible_ids = eligible.iter().map(|prompt| prompt.id.clone()).collect();
+
+    let context_tokens: HashSet<String> = text_tokens.into_iter().collect();
+    if context_tokens.is_empty() || eligible.is_empty() {
+        return PromptRanking {
+            ranked: Vec::new(),
+            eligible: eligible_ids,
+        };
+    }
+
+    let documents = eligible
+        .iter()
+        .map(|prompt| (prompt.id.clone(), prompt_document(prompt)))
+        .collect();
+    let mut ranked = Bm25::new(documents).rank(&context_tokens);
+    for item in &mut ranked {
+        let priority = eligible
+            .iter()
+            .find(|prompt| prompt.id == item.profile_id)
+            .map_or(0, |prompt| {
+                prompt
+                    .priority
+                    .clamp(-MAX_PROMPT_PRIORITY, MAX_PROMPT_PRIORITY)
+            });
+        item.score = (item.score + priority as f32 * PRIORITY_STEP).clamp(0.0, 1.0);
+    }
+    ranked.retain(|item| item.score > 0.0);
+    sort_ranked(&mut ranked);
+    PromptRanking {
+        ranked,
+        eligible: eligible_ids,
+    }
+}
+
+/// The first 2000 characters; routing only needs the gist.
+fn prompt_context_text(text: &str) -> String {
+    text.chars().take(2000).collect()
+}
+
+fn prompt_document(prompt: &LLMPrompt) -> Vec<String> {
     // Avoid scoring against full prompt instructions (can be large and noisy).
     // We rely on short, stable metadata for low-latency routing.
-    format!("{} {}", prompt.id, prompt.name)
+    let mut document = tokens(&format!("{} {}", prompt.id, prompt.name));
+    for trigger in &prompt.triggers {
+        document.extend(tokens(trigger));
+    }
+    document
+}
+
+fn negative_match<'a>(prompt: &'a LLMPrompt, text_tokens: &[String]) -> Option<&'a String> {
+    prompt
+        .negative_keywords
+        .iter()
+        .find(|keyword| contains_phrase(text_tokens, keyword))
 }
 
-fn prompt_keywords(prompt_id: &str) -> &'static [&'static str] {
-    match prompt_id {
-        "default_action_items" => &[
-            "action item",
-            "todo",
-            "next steps",
-            "owner",
-            "deadline",
-            "task",
-        ],
-        "default_document_writer" => &[
-            "document",
-            "proposal",
-            "design doc",
-            "write-up",
-            "spec",
-            "draft",
-        ],
-        "default_meeting_notes" => &[
-            "meeting",
-            "agenda",
-            "decisions",
-            "attendees",
-            "recap",
-            "notes",
-        ],
-        "default_slack_message" => &["slack", "channel", "team update", "quick update", "message"],
-        _ => &[],
+/// Whether `prompt` may be picked in `app`. Prompts limited to some apps
+/// are never picked when the frontmost app is unknown.
+fn applies_to_app(prompt: &LLMPrompt, app: Option<&str>) -> bool {
+    let apps: Vec<String> = prompt
+        .apps
+        .iter()
+        .map(|app| app.trim().to_lowercase())
+        .filter(|app| !app.is_empty())
+        .collect();
+    if apps.is_empty() {
+        return true;
     }
+    let Some(app) = app.map(str::to_lowercase) else {
+        return false;
+    };
+    apps.iter().any(|name| app.contains(name.as_str()))
+}
+
+/// Whether the words of `phrase` appear consecutively in `text_tokens`.
+fn contains_phrase(text_tokens: &[String], phrase: &str) -> bool {
+    let phrase = tokens(phrase);
+    !phrase.is_empty()
+        && text_tokens
+            .windows(phrase.len())
+            .any(|window| window == phrase.as_slice())
 }
 
 #[cfg(test)]
@@ -384,10 +745,7 @@ mod tests {
                 "Cargo".to_string(),
                 "Clippy".to_string(),
             ],
-            corrections: vec![JargonCorrection {
-                from: "rust lang".to_string(),
-                to: "Rust".to_string(),
-            }],
+            corrections: vec![JargonCorrection::new("rust lang", "Rust")],
         }];
         settings
     }
@@ -398,6 +756,7 @@ mod tests {
         let settings = make_settings();
         let context = DomainContext {
             text: "cargo clippy rust lang".to_string(),
+            app: None,
         };
         let result = manager.select_profiles_with_timeout(&settings, &context);
         assert!(result.is_some());
@@ -405,6 +764,27 @@ mod tests {
         assert!(ids.iter().any(|id| id == "custom_rust"));
     }
 
+    #[test]
+    fn conditional_corrections_only_score_in_context() {
+        let mut profiles = HashMap::new();
+        let mut rust = JargonCorrection::new("rust", "Rust");
+        rust.near = vec!["compile".to_string(), "crate".to_string()];
+        rust.not_near = vec!["car".to_string()];
+        profiles.insert(
+            "rust".to_string(),
+            JargonProfile {
+                label: "Rust".to_string(),
+                terms: Vec::new(),
+                corrections: vec![rust],
+            },
+        );
+
+        let weights = HashMap::new();
+        assert!(!score_profiles(&profiles, "compile the crate in rust", &weights).is_empty());
+        assert!(score_profiles(&profiles, "rust on the car", &weights).is_empty());
+        assert!(score_profiles(&profiles, "rust in the car crate", &weights).is_empty());
+    }
+
     #[test]
     fn selector_is_disabled_by_setting() {
         let manager = DomainSelectorManager::new();
@@ -412,6 +792,7 @@ mod tests {
         settings.domain_selector_enabled = false;
         let context = DomainContext {
             text: "terraform kubernetes".to_string(),
+            app: None,
         };
         assert!(manager
             .select_profiles_with_timeout(&settings, &context)
@@ -424,6 +805,7 @@ mod tests {
         let settings = make_settings();
         let context = DomainContext {
             text: "   ".to_string(),
+            app: None,
         };
         assert!(manager
             .select_profiles_with_timeout(&settings, &context)
@@ -440,23 +822,181 @@ mod tests {
                 id: "default_action_items".to_string(),
                 name: "Action Items".to_string(),
                 prompt: "Extract tasks".to_string(),
+                triggers: vec!["next steps".to_string(), "deadline".to_string()],
+                ..Default::default()
             },
             LLMPrompt {
                 id: "default_document_writer".to_string(),
                 name: "Document Writer".to_string(),
                 prompt: "Write a document".to_string(),
+                triggers: vec!["proposal".to_string()],
+                ..Default::default()
             },
         ];
         let selected = manager.select_post_process_prompt_with_timeout(
             &settings,
             &DomainContext {
                 text: "next steps owner and deadline for each task".to_string(),
+                app: None,
             },
             &prompts,
         );
         assert_eq!(selected, Some("default_action_items".to_string()));
     }
 
+    #[test]
+    fn prompt_routing_respects_negative_keywords_apps_and_priority() {
+        let manager = DomainSelectorManager::new();
+        let mut settings = make_settings();
+        settings.post_process_auto_prompt_selection = true;
+        settings.domain_selector_hysteresis = 0.0;
+        let mut prompts = vec![
+            LLMPrompt {
+                id: "release".to_string(),
+                name: "Release".to_string(),
+                triggers: vec!["changelog".to_string()],
+                negative_keywords: vec!["internal only".to_string()],
+                ..Default::default()
+            },
+            LLMPrompt {
+                id: "chat".to_string(),
+                name: "Chat".to_string(),
+                triggers: vec!["changelog".to_string()],
+                apps: vec!["Slack".to_string()],
+                ..Default::default()
+            },
+        ];
+        let route = |prompts: &[LLMPrompt], text: &str, app: Option<&str>| {
+            manager.select_post_process_prompt_with_timeout(
+                &settings,
+                &DomainContext {
+                    text: text.to_string(),
+                    app: app.map(str::to_string),
+                },
+                prompts,
+            )
+        };
+
+        assert_eq!(
+            route(&prompts, "draft the changelog", None),
+            Some("release".to_string())
+        );
+        assert_eq!(
+            route(
+                &prompts,
+                "draft the changelog, internal only",
+                Some("com.tinyspeck.slackmacgap")
+            ),
+            Some("chat".to_string())
+        );
+        assert_eq!(
+            route(&prompts, "draft the changelog, internal only", None),
+            None
+        );
+
+        prompts[1].priority = 2;
+        assert_eq!(
+            route(&prompts, "draft the changelog", Some("Slack")),
+            Some("chat".to_string())
+        );
+
+        let explanation = manager.explain_prompt_route(
+            &settings,
+            &DomainContext {
+                text: "changelog, internal only".to_string(),
+                app: None,
+            },
+            &prompts,
+        );
+        assert_eq!(explanation.outcome, PromptRouteOutcome::BelowMinScore);
+        assert_eq!(explanation.prompt_id, None);
+        assert_eq!(
+            explanation.candidates[0].blocked_by.as_deref(),
+            Some("internal only")
+        );
+        assert!(explanation.candidates[1].wrong_app);
+        assert_eq!(
+            explanation.candidates[1].matched_triggers,
+            vec!["changelog"]
+        );
+    }
+
+    #[test]
+    fn prompt_hysteresis_keeps_previous_pick_on_near_ties() {
+        let manager = DomainSelectorManager::new();
+        let mut settings = make_settings();
+        settings.post_process_auto_prompt_selection = true;
+        settings.domain_selector_hysteresis = 0.5;
+        let prompts = vec![
+            LLMPrompt {
+                id: "notes".to_string(),
+                name: "Notes".to_string(),
+                triggers: vec!["meeting".to_string()],
+                ..Default::default()
+            },
+            LLMPrompt {
+                id: "email".to_string(),
+                name: "Email".to_string(),
+                triggers: vec!["email".to_string()],
+                negative_keywords: vec!["draft".to_string()],
+                ..Default::default()
+            },
+        ];
+        let context = |text: &str| DomainContext {
+            text: text.to_string(),
+            app: None,
+        };
+
+        let first = manager.select_post_process_prompt_with_timeout(
+            &settings,
+            &context("email about the email"),
+            &prompts,
+        );
+        assert_eq!(first, Some("email".to_string()));
+
+        let explanation =
+            manager.explain_prompt_route(&settings, &context("meeting notes"), &prompts);
+        assert_eq!(explanation.outcome, PromptRouteOutcome::KeptPrevious);
+        assert_eq!(explanation.prompt_id.as_deref(), Some("email"));
+
+        // A prompt ruled out by a negative keyword is never kept.
+        let selected = manager.select_post_process_prompt_with_timeout(
+            &settings,
+            &context("draft meeting notes"),
+            &prompts,
+        );
+        assert_eq!(selected, Some("notes".to_string()));
+    }
+
+    #[test]
+    fn learned_weights_follow_toggles_and_edits() {
+        let mut settings = make_settings();
+        settings.domain_selector_learned_weights = true;
+
+        settings.jargon_enabled_profiles = vec!["coding".to_string()];
+        record_profile_toggles(&mut settings, &["business".to_string()]);
+        record_edit_feedback(
+            &mut settings,
+            "we opened a full request after see I passed",
+            "we opened a pull request after CI passed",
+        );
+        let feedback = &settings.domain_selector_profile_feedback;
+        assert_eq!(feedback["coding"].kept, 2);
+        assert_eq!(feedback["business"].corrected, 1);
+
+        let weights = learned_weights(&settings);
+        assert!(weights["coding"] > 1.0 && weights["business"] < 1.0);
+        let ranked = score_profiles(
+            &crate::jargon::profiles_with_packs(&settings.jargon_packs),
+            "pipeline and API",
+            &weights,
+        );
+        assert_eq!(ranked[0].profile_id, "coding");
+
+        settings.domain_selector_learned_weights = false;
+        assert!(learned_weights(&settings).is_empty());
+    }
+
     #[derive(Debug, Deserialize)]
     struct ProfileEvalSuite {
         #[serde(default)]
@@ -528,6 +1068,31 @@ mod tests {
         notes: Option<String>,
     }
 
+    /// Counts behind the precision and recall reported by the profile eval.
+    #[derive(Default)]
+    struct Retrieval {
+        selected: usize,
+        selected_relevant: usize,
+        relevant: usize,
+        relevant_found: usize,
+    }
+
+    impl Retrieval {
+        fn precision(&self) -> f32 {
+            if self.selected == 0 {
+                return 1.0;
+            }
+            self.selected_relevant as f32 / self.selected as f32
+        }
+
+        fn recall(&self) -> f32 {
+            if self.relevant == 0 {
+                return 1.0;
+            }
+            self.relevant_found as f32 / self.relevant as f32
+        }
+    }
+
     fn default_min_pass_rate() -> f32 {
         0.8
     }
@@ -583,6 +1148,7 @@ mod tests {
         let mut passed = 0usize;
         let mut failures = Vec::new();
         let mut tracked_misses = Vec::new();
+        let mut retrieval = Retrieval::default();
 
         for case in &suite.cases {
             let manager = DomainSelectorManager::new();
@@ -591,6 +1157,7 @@ mod tests {
                     &settings,
                     &DomainContext {
                         text: case.input.clone(),
+                        app: None,
                     },
                 )
                 .unwrap_or_default();
@@ -626,6 +1193,16 @@ mod tests {
                 continue;
             }
 
+            retrieval.selected += selected.len();
+            retrieval.selected_relevant += selected
+                .iter()
+                .filter(|id| case.expect_any_of.contains(id))
+                .count();
+            if !case.expect_any_of.is_empty() {
+                retrieval.relevant += 1;
+                retrieval.relevant_found += usize::from(has_expected);
+            }
+
             if pass {
                 passed += 1;
             } else {
@@ -643,11 +1220,13 @@ mod tests {
 
         let pass_rate = passed as f32 / scored_total as f32;
         eprintln!(
-            "[profile eval] suite='{}' pass_rate={:.2} scored={}/{} tracked_misses={}",
+            "[profile eval] suite='{}' pass_rate={:.2} scored={}/{} precision={:.2} recall={:.2} tracked_misses={}",
             suite.description,
             pass_rate,
             passed,
             scored_total,
+            retrieval.precision(),
+            retrieval.recall(),
             tracked_misses.len()
         );
         if !tracked_misses.is_empty() {
@@ -666,34 +1245,20 @@ mod tests {
         );
     }
 
+    /// The built-in prompts the eval suite expects, with their default triggers.
     fn eval_prompts() -> Vec<LLMPrompt> {
-        vec![
-            LLMPrompt {
-                id: "default_action_items".to_string(),
-                name: "Action Items".to_string(),
-                prompt: "Extract actionable tasks".to_string(),
-            },
-            LLMPrompt {
-                id: "default_document_writer".to_string(),
-                name: "Document Writer".to_string(),
-                prompt: "Structured document draft".to_string(),
-            },
-            LLMPrompt {
-                id: "default_meeting_notes".to_string(),
-                name: "Meeting Notes".to_string(),
-                prompt: "Meeting summary decisions notes".to_string(),
-            },
-            LLMPrompt {
-                id: "default_slack_message".to_string(),
-                name: "Slack Message".to_string(),
-                prompt: "Team update in slack format".to_string(),
-            },
-            LLMPrompt {
-                id: "default_standup_update".to_string(),
-                name: "Standup Update".to_string(),
-                prompt: "Yesterday Today Blockers update".to_string(),
-            },
-        ]
+        let ids = [
+            "default_action_items",
+            "default_document_writer",
+            "default_meeting_notes",
+            "default_slack_message",
+            "default_standup_update",
+        ];
+        crate::settings::get_default_settings()
+            .post_process_prompts
+            .into_iter()
+            .filter(|prompt| ids.contains(&prompt.id.as_str()))
+            .collect()
     }
 
     #[test]
@@ -729,6 +1294,7 @@ mod tests {
                 &settings,
                 &DomainContext {
                     text: case.input.clone(),
+                    app: None,
                 },
                 &prompts,
             );
@@ -31,8 +31,24 @@ static MIGRATIONS: &[M] = &[
     ),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN post_process_attempts TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN edited_text TEXT;"),
 ];
 
+const ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, post_process_attempts, edited_text";
+
+/// One provider/model tried while post-processing a transcription.
+/// Stored as a JSON array alongside the history entry.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct PostProcessAttempt {
+    pub provider_id: String,
+    pub model: String,
+    pub succeeded: bool,
+    pub elapsed_ms: u64,
+    /// Why the attempt failed; `None` for the attempt that succeeded.
+    pub error: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct HistoryEntry {
     pub id: i64,
@@ -43,6 +59,31 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// Every post-processing attempt in the order it was made. Empty when
+    /// post-processing did not run.
+    pub post_process_attempts: Vec<PostProcessAttempt>,
+    /// `transcription_text` as the user corrected it, if they edited it.
+    pub edited_text: Option<String>,
+}
+
+impl HistoryEntry {
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        let attempts: Option<String> = row.get("post_process_attempts")?;
+        Ok(HistoryEntry {
+            id: row.get("id")?,
+            file_name: row.get("file_name")?,
+            timestamp: row.get("timestamp")?,
+            saved: row.get("saved")?,
+            title: row.get("title")?,
+            transcription_text: row.get("transcription_text")?,
+            post_processed_text: row.get("post_processed_text")?,
+            post_process_prompt: row.get("post_process_prompt")?,
+            post_process_attempts: attempts
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            edited_text: row.get("edited_text")?,
+        })
+    }
 }
 
 pub struct HistoryManager {
@@ -183,6 +224,7 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        post_process_attempts: Vec<PostProcessAttempt>,
     ) -> Result<()> {
         let timestamp = Utc::now().timestamp();
         let file_name = format!("spittle-{}.wav", timestamp);
@@ -200,6 +242,7 @@ impl HistoryManager {
             transcription_text,
             post_processed_text,
             post_process_prompt,
+            &post_process_attempts,
         )?;
 
         // Clean up old entries
@@ -221,11 +264,18 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        post_process_attempts: &[PostProcessAttempt],
     ) -> Result<()> {
+        let attempts_json = if post_process_attempts.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(post_process_attempts)?)
+        };
+
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, post_process_attempts) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt, attempts_json],
         )?;
 
         debug!("Saved transcription to database");
@@ -354,22 +404,12 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC",
+            ENTRY_COLUMNS
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-            })
-        })?;
+        let rows = stmt.query_map([], HistoryEntry::from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -385,27 +425,15 @@ impl HistoryManager {
     }
 
     fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
              FROM transcription_history
              ORDER BY timestamp DESC
              LIMIT 1",
-        )?;
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([], HistoryEntry::from_row).optional()?;
 
         Ok(entry)
     }
@@ -437,31 +465,43 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Store the user's correction of an entry's transcription and return
+    /// the updated entry. Editing back to the original clears the edit.
+    pub async fn update_entry_text(&self, id: i64, text: &str) -> Result<HistoryEntry> {
+        let mut entry = self
+            .get_entry_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("History entry {} not found", id))?;
+        entry.edited_text = Some(text.to_string()).filter(|text| *text != entry.transcription_text);
+
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE transcription_history SET edited_text = ?1 WHERE id = ?2",
+            params![entry.edited_text, id],
+        )?;
+
+        debug!("Updated text of history entry {}", id);
+
+        // Emit history updated event
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(entry)
+    }
+
     pub fn get_audio_file_path(&self, file_name: &str) -> PathBuf {
         self.recordings_dir.join(file_name)
     }
 
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([id], HistoryEntry::from_row).optional()?;
 
         Ok(entry)
     }
@@ -524,7 +564,9 @@ mod tests {
                 title TEXT NOT NULL,
                 transcription_text TEXT NOT NULL,
                 post_processed_text TEXT,
-                post_process_prompt TEXT
+                post_process_prompt TEXT,
+                post_process_attempts TEXT,
+                edited_text TEXT
             );",
         )
         .expect("create transcription_history table");
@@ -568,5 +610,40 @@ mod tests {
         assert_eq!(entry.timestamp, 200);
         assert_eq!(entry.transcription_text, "second");
         assert_eq!(entry.post_processed_text.as_deref(), Some("processed"));
+        assert!(entry.post_process_attempts.is_empty());
+    }
+
+    #[test]
+    fn post_process_attempts_round_trip_as_json() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "raw", Some("processed"));
+        let attempts = vec![
+            PostProcessAttempt {
+                provider_id: "groq".to_string(),
+                model: "llama".to_string(),
+                succeeded: false,
+                elapsed_ms: 15000,
+                error: Some("Request timed out after 15000ms".to_string()),
+            },
+            PostProcessAttempt {
+                provider_id: "openrouter".to_string(),
+                model: "gpt".to_string(),
+                succeeded: true,
+                elapsed_ms: 800,
+                error: None,
+            },
+        ];
+        conn.execute(
+            "UPDATE transcription_history SET post_process_attempts = ?1",
+            params![serde_json::to_string(&attempts).unwrap()],
+        )
+        .expect("store attempts");
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch latest entry")
+            .expect("entry exists");
+        assert_eq!(entry.post_process_attempts.len(), 2);
+        assert!(!entry.post_process_attempts[0].succeeded);
+        assert_eq!(entry.post_process_attempts[1].provider_id, "openrouter");
     }
 }
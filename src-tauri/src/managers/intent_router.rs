@@ -0,0 +1,585 @@
+//! Intent router sidecar: decides whether an utterance is plain dictation,
+//! a shell command, a template or an editing command, so it can take the
+//! matching path without a spoken trigger word.
+//!
+//! With a sentence-embedding model installed (an ONNX export of
+//! `all-MiniLM-L6-v2`, `bge-small-en-v1.5` or similar, plus its
+//! `tokenizer.json`), the utterance is compared with prototype examples for
+//! each intent by cosine similarity. Without one, a lexical scorer looks for
+//! the same cues by words. Either way the router only ever adds a route:
+//! when it is off, slow or unsure, the pipeline keeps its usual behavior.
+
+use crate::managers::domain_selector::DomainContext;
+use crate::settings::AppSettings;
+use crate::text_model::{self, TextModel};
+use crate::voice_commands::normalize_phrase;
+use anyhow::Result;
+use log::{debug, warn};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Sentence embedding models are trained on short inputs.
+const MAX_TOKENS: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intent {
+    Dictation,
+    Cli,
+    Template,
+    Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouterSource {
+    Embedding,
+    Lexical,
+}
+
+#[derive(Debug, Clone)]
+pub struct IntentDecision {
+    pub intent: Intent,
+    pub confidence: f32,
+    pub source: RouterSource,
+}
+
+const DICTATION_EXAMPLES: &[&str] = &[
+    "I'll be a few minutes late to the meeting",
+    "thanks for the update, that sounds good to me",
+    "the quarterly numbers look better than we expected",
+    "can you review my pull request when you get a chance",
+    "let's grab lunch tomorrow and talk about the roadmap",
+    "the login page keeps redirecting back to itself after sign in",
+];
+
+const CLI_EXAMPLES: &[&str] = &[
+    "list all files in the current directory",
+    "git status",
+    "change directory to source",
+    "make a new folder called build",
+    "search for todo in all rust files",
+    "show running docker containers",
+    "install the dependencies with npm",
+    "remove the temp directory",
+];
+
+/// Programs whose name at the start of an utterance can mark a shell
+/// command. Their names also come up in prose ("git is great", "make sure
+/// …"), so they only count when a known subcommand or arguments follow.
+const CLI_PROGRAMS: &[&str] = &[
+    "git", "ls", "npm", "npx", "pnpm", "yarn", "cargo", "docker", "kubectl", "grep", "mkdir", "rm",
+    "cp", "mv", "chmod", "curl", "python", "ssh", "cd", "find", "cat", "make", "touch", "brew",
+];
+
+/// Subcommands, space-separated, that mark a shell command right after
+/// their program.
+const CLI_SUBCOMMANDS: &[(&str, &str)] = &[
+    (
+        "git",
+        "status add commit push pull fetch checkout switch branch merge rebase log diff stash \
+         clone reset restore init",
+    ),
+    ("npm", "install i ci run test start build uninstall update"),
+    ("pnpm", "install i add run test dev build remove"),
+    ("yarn", "install add run test dev build remove"),
+    (
+        "cargo",
+        "build run test check clippy fmt add install update clean",
+    ),
+    (
+        "docker",
+        "ps run build pull push images exec compose logs stop",
+    ),
+    ("kubectl", "get apply describe logs delete exec rollout"),
+    ("brew", "install upgrade update uninstall list"),
+];
+
+/// Spoken forms of the characters that make a word look like a path or flag.
+const SPOKEN_ARGUMENT_WORDS: &[&str] = &["dash", "slash", "dot", "tilde", "star", "equals"];
+
+/// Whether the words after a program name read like shell arguments: a
+/// short tail with a flag, path or glob, typed or spoken.
+fn looks_like_arguments(text: &str, words: &[&str]) -> bool {
+    if words.len() < 2 || words.len() > 8 {
+        return false;
+    }
+    let typed = text.split_whitespace().skip(1).any(|token| {
+        let token = token.trim_end_matches(['.', ',', '!', '?']);
+        token.starts_with('-') || token.contains(['/', '~', '.', '*', '='])
+    });
+    typed
+        || words[1..]
+            .iter()
+            .any(|word| SPOKEN_ARGUMENT_WORDS.contains(word))
+}
+
+/// Whether the second word is a known subcommand of the first, as in
+/// "git push origin main".
+fn starts_with_subcommand(words: &[&str]) -> bool {
+    match words {
+        [program, subcommand, ..] => CLI_SUBCOMMANDS.iter().any(|(name, subcommands)| {
+            name == program && subcommands.split_whitespace().any(|s| s == *subcommand)
+        }),
+        _ => false,
+    }
+}
+
+/// Example utterances for every intent that can be taken with the current
+/// settings. Templates and voice commands only have prototypes for what is
+/// configured, so nothing routes to a template or command that cannot run.
+fn prototypes(settings: &AppSettings) -> Vec<(Intent, String)> {
+    fn examples<'a>(
+        intent: Intent,
+        texts: impl IntoIterator<Item = &'a str>,
+    ) -> impl Iterator<Item = (Intent, String)> {
+        texts
+            .into_iter()
+            .map(move |example| (intent, example.to_string()))
+    }
+
+    let mut prototypes: Vec<(Intent, String)> =
+        examples(Intent::Dictation, DICTATION_EXAMPLES.iter().copied()).collect();
+    if settings.cli_mode_enabled {
+        prototypes.extend(examples(Intent::Cli, CLI_EXAMPLES.iter().copied()));
+    }
+    let templates: Vec<&str> = settings
+        .templates
+        .iter()
+        .filter(|template| template.enabled)
+        .map(|template| template.name.as_str())
+        .collect();
+    if settings.templates_enabled {
+        prototypes.extend(examples(Intent::Template, templates));
+    }
+    if settings.voice_commands_enabled {
+        prototypes.extend(examples(
+            Intent::Command,
+            settings
+                .voice_commands
+                .iter()
+                .filter(|command| command.enabled)
+                .flat_map(|command| command.phrases.iter().map(String::as_str)),
+        ));
+    }
+    prototypes
+}
+
+struct RouterModel {
+    model: TextModel,
+    /// Prototype embeddings, computed once per model.
+    cache: HashMap<String, Vec<f32>>,
+}
+
+impl RouterModel {
+    fn load(dir: &Path) -> Result<Self> {
+        // Some exports add a pooled output; otherwise pool the token states.
+        let model = TextModel::load(dir, "intent router", Some("sentence_embedding"), MAX_TOKENS)?;
+        Ok(Self {
+            model,
+            cache: HashMap::new(),
+        })
+    }
+
+    /// Normalized sentence embedding of `text`.
+    fn embed(&mut self, text: &str) -> Result<Vec<f32>> {
+        let output = self.model.run(text)?;
+        let hidden = *output.shape.last().unwrap_or(&0) as usize;
+        if hidden == 0 {
+            anyhow::bail!("Intent router model returned an empty embedding");
+        }
+
+        let embedding = if output.shape.len() == 2 {
+            output.data[..hidden].to_vec()
+        } else {
+            mean_pool(&output.data, &output.attention_mask, hidden)
+        };
+        Ok(normalize(embedding))
+    }
+
+    fn embed_cached(&mut self, text: &str) -> Result<Vec<f32>> {
+        if let Some(embedding) = self.cache.get(text) {
+            return Ok(embedding.clone());
+        }
+        let embedding = self.embed(text)?;
+        self.cache.insert(text.to_string(), embedding.clone());
+        Ok(embedding)
+    }
+}
+
+/// Average the token states the attention mask keeps.
+fn mean_pool(data: &[f32], attention_mask: &[i64], hidden: usize) -> Vec<f32> {
+    let mut pooled = vec![0.0_f32; hidden];
+    let mut kept = 0.0_f32;
+    for (token, &mask) in attention_mask.iter().enumerate() {
+        if mask == 0 {
+            continue;
+        }
+        let state = &data[token * hidden..(token + 1) * hidden];
+        for (sum, value) in pooled.iter_mut().zip(state) {
+            *sum += value;
+        }
+        kept += 1.0;
+    }
+    pooled.iter_mut().for_each(|value| *value /= kept.max(1.0));
+    pooled
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    // Both sides are normalized.
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Best-scoring intent, with each intent scored by its closest prototype.
+fn best_intent(scores: impl Iterator<Item = (Intent, f32)>) -> Option<(Intent, f32)> {
+    let mut best: HashMap<Intent, f32> = HashMap::new();
+    for (intent, score) in scores {
+        let entry = best.entry(intent).or_insert(f32::MIN);
+        *entry = entry.max(score);
+    }
+    best.into_iter().max_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            // Ties go to dictation, which changes nothing.
+            .then_with(|| (a.0 == Intent::Dictation).cmp(&(b.0 == Intent::Dictation)))
+    })
+}
+
+fn score_with_model(
+    model: &mut RouterModel,
+    prototypes: &[(Intent, String)],
+    text: &str,
+) -> Result<Option<(Intent, f32)>> {
+    let utterance = model.embed(text)?;
+    let mut scores = Vec::with_capacity(prototypes.len());
+    for (intent, example) in prototypes {
+        let example = model.embed_cached(example)?;
+        scores.push((*intent, cosine(&utterance, &example)));
+    }
+    Ok(best_intent(scores.into_iter()))
+}
+
+/// Word-level stand-in for the model. Exact cues (a voice command phrase as
+/// the whole utterance, a template name or program name up front) score
+/// high; otherwise the closest prototype by word overlap.
+fn score_lexically(prototypes: &[(Intent, String)], text: &str) -> Option<(Intent, f32)> {
+    let normalized = normalize_phrase(text);
+    if normalized.is_empty() {
+        return None;
+    }
+    let words: Vec<&str> = normalized.split(' ').collect();
+    let tokens: HashSet<&str> = words.iter().copied().collect();
+    let has_intent = |intent: Intent| prototypes.iter().any(|(i, _)| *i == intent);
+
+    let mut scores: Vec<(Intent, f32)> = Vec::new();
+    for (intent, example) in prototypes {
+        let example = normalize_phrase(example);
+        let example_words: Vec<&str> = example.split(' ').collect();
+        let exact = match intent {
+            Intent::Command => normalized == example,
+            Intent::Template => {
+                words.len() > example_words.len()
+                    && words[..example_words.len()] == example_words[..]
+            }
+            _ => false,
+        };
+        if exact {
+            scores.push((*intent, 1.0));
+            continue;
+        }
+        let example_tokens: HashSet<&str> = example_words.into_iter().collect();
+        let shared = tokens.intersection(&example_tokens).count() as f32;
+        let dice = 2.0 * shared / (tokens.len() + example_tokens.len()) as f32;
+        scores.push((*intent, dice));
+    }
+
+    let program_up_front = CLI_PROGRAMS.contains(&words[0])
+        && (starts_with_subcommand(&words) || looks_like_arguments(text, &words));
+    if has_intent(Intent::Cli) && program_up_front {
+        scores.push((Intent::Cli, 0.8));
+    }
+    // Long utterances are prose unless something above says otherwise.
+    if words.len() > 12 {
+        scores.push((Intent::Dictation, 0.7));
+    }
+    best_intent(scores.into_iter())
+}
+
+#[derive(Default)]
+pub struct IntentRouterManager {
+    model: Arc<Mutex<Option<RouterModel>>>,
+}
+
+impl IntentRouterManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the configured model in the background. Called when recording
+    /// starts so the first routing decision does not pay for the load.
+    pub fn warm_up(&self, settings: &AppSettings) {
+        let Some(dir) = text_model::model_dir(settings.intent_router_model_dir.as_deref()) else {
+            return;
+        };
+        let model = Arc::clone(&self.model);
+        std::thread::spawn(move || {
+            if let Ok(mut model) = model.try_lock() {
+                ensure_model(&mut model, &dir);
+            }
+        });
+    }
+
+    /// Classify `context.text`. Returns `None` when routing is off, times
+    /// out or is below the confidence threshold, so the caller keeps its
+    /// usual mode behavior.
+    pub fn route_with_timeout(
+        &self,
+        settings: &AppSettings,
+        context: &DomainContext,
+    ) -> Option<IntentDecision> {
+        if !settings.intent_router_enabled {
+            return None;
+        }
+        if context.text.trim().is_empty() {
+            return None;
+        }
+        let prototypes = prototypes(settings);
+        if prototypes
+            .iter()
+            .all(|(intent, _)| *intent == Intent::Dictation)
+        {
+            return None;
+        }
+
+        let timeout = Duration::from_millis(settings.intent_router_timeout_ms.max(25));
+        let min_confidence = settings.intent_router_min_confidence.clamp(0.0, 1.0);
+        let dir = text_model::model_dir(settings.intent_router_model_dir.as_deref());
+        let model = Arc::clone(&self.model);
+        let text: String = context.text.chars().take(1000).collect();
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let decision = route(&model, dir.as_deref(), &prototypes, &text);
+            let _ = tx.send(decision);
+        });
+
+        let decision = match rx.recv_timeout(timeout) {
+            Ok(decision) => decision?,
+            Err(_) => {
+                warn!(
+                    "Intent router sidecar timed out after {}ms",
+                    timeout.as_millis()
+                );
+                return None;
+            }
+        };
+
+        debug!(
+            "Intent router picked {:?}: {:.3} ({:?})",
+            decision.intent, decision.confidence, decision.source
+        );
+        (decision.confidence >= min_confidence).then_some(decision)
+    }
+}
+
+/// Make sure `slot` holds the model from `dir`, loading it if needed.
+fn ensure_model(slot: &mut Option<RouterModel>, dir: &Path) -> bool {
+    if slot.as_ref().is_some_and(|router| router.model.dir == dir) {
+        return true;
+    }
+    match RouterModel::load(dir) {
+        Ok(model) => {
+            *slot = Some(model);
+            true
+        }
+        Err(err) => {
+            warn!(
+                "Intent router model unavailable, using lexical routing: {}",
+                err
+            );
+            *slot = None;
+            false
+        }
+    }
+}
+
+fn route(
+    model: &Mutex<Option<RouterModel>>,
+    dir: Option<&Path>,
+    prototypes: &[(Intent, String)],
+    text: &str,
+) -> Option<IntentDecision> {
+    if let Some(dir) = dir {
+        // A model still loading from `warm_up` is not waited for.
+        if let Ok(mut slot) = model.try_lock() {
+            if ensure_model(&mut slot, dir) {
+                let model = slot.as_mut().expect("model was just loaded");
+                match score_with_model(model, prototypes, text) {
+                    Ok(best) => {
+                        return best.map(|(intent, confidence)| IntentDecision {
+                            intent,
+                            confidence,
+                            source: RouterSource::Embedding,
+                        })
+                    }
+                    Err(err) => warn!("Intent router model failed, using lexical routing: {}", err),
+                }
+            }
+        }
+    }
+
+    score_lexically(prototypes, text).map(|(intent, confidence)| IntentDecision {
+        intent,
+        confidence,
+        source: RouterSource::Lexical,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_settings() -> AppSettings {
+        let mut settings = crate::settings::get_default_settings();
+        settings.intent_router_enabled = true;
+        settings.intent_router_model_dir = None;
+        settings.intent_router_min_confidence = 0.6;
+        settings.cli_mode_enabled = true;
+        settings.templates_enabled = true;
+        settings.voice_commands_enabled = true;
+        settings
+    }
+
+    fn route_text(settings: &AppSettings, text: &str) -> Option<Intent> {
+        IntentRouterManager::new()
+            .route_with_timeout(
+                settings,
+                &DomainContext {
+                    text: text.to_string(),
+                    app: None,
+                },
+            )
+            .map(|decision| decision.intent)
+    }
+
+    #[test]
+    fn lexical_routing_finds_each_intent() {
+        let settings = make_settings();
+        assert_eq!(
+            route_text(&settings, "git push origin main"),
+            Some(Intent::Cli)
+        );
+        assert_eq!(
+            route_text(&settings, "New paragraph."),
+            Some(Intent::Command)
+        );
+        assert_eq!(
+            route_text(&settings, "pull request summary adds caching"),
+            Some(Intent::Template)
+        );
+        assert_eq!(
+            route_text(
+                &settings,
+                "I looked at the numbers again and I think we should move the launch to next week"
+            ),
+            Some(Intent::Dictation)
+        );
+        // Nothing stands out: fall back to the usual behavior.
+        assert_eq!(route_text(&settings, "sounds fine"), None);
+    }
+
+    #[test]
+    fn only_configured_commands_and_templates_are_routed_to() {
+        let settings = make_settings();
+        for text in ["press enter", "select all", "delete that"] {
+            assert_ne!(
+                route_text(&settings, text),
+                Some(Intent::Command),
+                "{}",
+                text
+            );
+        }
+        assert_ne!(
+            route_text(&settings, "bug report the login page loops forever"),
+            Some(Intent::Template)
+        );
+        assert_eq!(route_text(&settings, "undo that"), Some(Intent::Command));
+    }
+
+    #[test]
+    fn prose_starting_with_a_program_name_is_not_a_command() {
+        let settings = make_settings();
+        for text in [
+            "Make sure you call Sam back.",
+            "find the attached report",
+            "cat videos are the best part of the internet",
+            "touch base with the design team tomorrow",
+            "Git is great for version control",
+            "Python is slow today",
+            "Docker containers are down again",
+        ] {
+            assert_ne!(route_text(&settings, text), Some(Intent::Cli), "{}", text);
+        }
+        assert_eq!(
+            route_text(&settings, "find . -name '*.rs'"),
+            Some(Intent::Cli)
+        );
+        assert_eq!(
+            route_text(&settings, "cat readme dot md"),
+            Some(Intent::Cli)
+        );
+        assert_eq!(route_text(&settings, "docker ps"), Some(Intent::Cli));
+    }
+
+    #[test]
+    fn disabled_modes_are_never_routed_to() {
+        let mut settings = make_settings();
+        settings.cli_mode_enabled = false;
+        settings.voice_commands_enabled = false;
+        assert_ne!(
+            route_text(&settings, "git push origin main"),
+            Some(Intent::Cli)
+        );
+        assert_eq!(route_text(&settings, "new paragraph"), None);
+
+        settings.templates_enabled = false;
+        assert_eq!(
+            route_text(&settings, "pull request summary adds caching"),
+            None
+        );
+    }
+
+    #[test]
+    fn missing_model_falls_back_to_lexical() {
+        let mut settings = make_settings();
+        settings.intent_router_model_dir = Some("/nonexistent/router-model".to_string());
+        let decision = IntentRouterManager::new()
+            .route_with_timeout(
+                &settings,
+                &DomainContext {
+                    text: "git status".to_string(),
+                    app: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(decision.intent, Intent::Cli);
+        assert_eq!(decision.source, RouterSource::Lexical);
+    }
+
+    #[test]
+    fn mean_pool_skips_masked_tokens() {
+        let data = [1.0, 2.0, 3.0, 4.0, 100.0, 100.0];
+        assert_eq!(mean_pool(&data, &[1, 1, 0], 2), vec![2.0, 3.0]);
+        let unit = normalize(vec![3.0, 4.0]);
+        assert!((cosine(&unit, &unit) - 1.0).abs() < 1e-6);
+    }
+}
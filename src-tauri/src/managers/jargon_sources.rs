@@ -0,0 +1,238 @@
+//! Keeps external jargon sources loaded and reloads them when their files
+//! change. See `crate::jargon_sources` for the file formats.
+
+use crate::jargon::{self, CompiledDictionary, DictionaryKey, JargonProfile, JargonSettings};
+use crate::jargon_sources::{self, Fingerprint, JargonScope};
+use crate::managers::workspace_jargon::WorkspaceJargonManager;
+use crate::settings::{self, AppSettings};
+use log::{debug, warn};
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often source files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+struct LoadedSource {
+    path: String,
+    fingerprint: Option<Fingerprint>,
+    profile: Option<Arc<JargonProfile>>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct JargonSourceStatus {
+    pub id: String,
+    pub terms: u32,
+    pub corrections: u32,
+    /// Why the source could not be read; its last good contents stay in use.
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct SourceState {
+    loaded: RwLock<HashMap<String, LoadedSource>>,
+    /// Bumped whenever `loaded` changes.
+    revision: AtomicU64,
+}
+
+pub struct JargonSourceManager {
+    app_handle: AppHandle,
+    state: Arc<SourceState>,
+}
+
+impl JargonSourceManager {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            state: Arc::new(SourceState::default()),
+        };
+        manager.refresh();
+
+        // Poll until the manager is dropped. Without sources there is
+        // nothing to check; `refresh` picks up newly added ones.
+        let app_handle = app_handle.clone();
+        let state = Arc::downgrade(&manager.state);
+        thread::spawn(move || loop {
+            thread::sleep(POLL_INTERVAL);
+            let Some(state) = Weak::upgrade(&state) else {
+                break;
+            };
+            if state.loaded.read().unwrap().is_empty() {
+                continue;
+            }
+            refresh_sources(&app_handle, &state);
+        });
+
+        manager
+    }
+
+    /// Reload any source whose files changed. Called after settings edits
+    /// so changes apply without waiting for the next poll.
+    pub fn refresh(&self) {
+        refresh_sources(&self.app_handle, &self.state);
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.state.revision.load(Ordering::Acquire)
+    }
+
+    /// Loaded sources that apply to `scope`, in priority order.
+    pub fn profiles_for(&self, settings: &AppSettings, scope: &JargonScope) -> Vec<JargonProfile> {
+        let loaded = self.state.loaded.read().unwrap();
+        settings
+            .jargon_sources
+            .iter()
+            .filter(|source| source.applies_to(scope))
+            .filter_map(|source| loaded.get(&source.id)?.profile.as_deref().cloned())
+            .collect()
+    }
+
+    pub fn statuses(&self, settings: &AppSettings) -> Vec<JargonSourceStatus> {
+        let loaded = self.state.loaded.read().unwrap();
+        settings
+            .jargon_sources
+            .iter()
+            .map(|source| {
+                let entry = loaded.get(&source.id);
+                let profile = entry.and_then(|entry| entry.profile.as_deref());
+                JargonSourceStatus {
+                    id: source.id.clone(),
+                    terms: profile.map_or(0, |p| p.terms.len() as u32),
+                    corrections: profile.map_or(0, |p| p.corrections.len() as u32),
+                    error: entry.and_then(|entry| entry.error.clone()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether any jargon applies to `scope`, so a dictionary is worth building.
+pub fn jargon_in_use(settings: &AppSettings, scope: &JargonScope) -> bool {
+    !settings.jargon_enabled_profiles.is_empty()
+        || !settings.jargon_custom_terms.is_empty()
+        || !settings.jargon_custom_corrections.is_empty()
+        || !settings.jargon_packs.is_empty()
+        || settings.workspace_jargon_enabled
+        || settings
+            .jargon_sources
+            .iter()
+            .any(|source| source.applies_to(scope))
+}
+
+/// The active dictionary for `profile_ids` and `scope`. It is only rebuilt
+/// when settings, loaded sources or the profile set change.
+pub fn active_dictionary(
+    app: &AppHandle,
+    settings: &AppSettings,
+    mut profile_ids: Vec<String>,
+    scope: &JargonScope,
+) -> Arc<CompiledDictionary> {
+    profile_ids.sort();
+    profile_ids.dedup();
+    let manager = app.try_state::<Arc<JargonSourceManager>>();
+    let workspace = app
+        .try_state::<Arc<WorkspaceJargonManager>>()
+        .filter(|_| settings.workspace_jargon_enabled);
+    let key = DictionaryKey {
+        settings_revision: settings.revision,
+        sources_revision: manager.as_ref().map_or(0, |manager| manager.revision()),
+        workspace_revision: workspace
+            .as_ref()
+            .map_or(0, |workspace| workspace.revision()),
+        profiles: profile_ids.clone(),
+        scope: scope.clone(),
+    };
+    jargon::cached_dictionary(key, || {
+        let mut source_profiles = match &manager {
+            Some(manager) if !settings.jargon_sources.is_empty() => {
+                manager.profiles_for(settings, scope)
+            }
+            _ => Vec::new(),
+        };
+        // The workspace ranks below every configured source. Its identifier
+        // corrections ("get settings" -> `get_settings`) would rewrite prose,
+        // so they only apply while dictating code.
+        if let Some(profile) = workspace.and_then(|workspace| workspace.profile()) {
+            let mut profile = profile.as_ref().clone();
+            if !profile_ids.iter().any(|id| id == jargon::CODING_PROFILE_ID) {
+                profile.corrections.clear();
+            }
+            source_profiles.push(profile);
+        }
+        let jargon_settings = JargonSettings {
+            enabled_profiles: profile_ids,
+            custom_terms: settings.jargon_custom_terms.clone(),
+            custom_corrections: settings.jargon_custom_corrections.clone(),
+            source_profiles,
+        };
+        jargon::compute_active_dictionary(
+            &jargon_settings,
+            &jargon::profiles_with_packs(&settings.jargon_packs),
+        )
+    })
+}
+
+fn refresh_sources(app: &AppHandle, state: &SourceState) {
+    let settings = settings::get_settings(app);
+    let current = state.loaded.read().unwrap().clone();
+    let mut next = HashMap::new();
+    let mut changed = current.len() != settings.jargon_sources.len();
+
+    for source in &settings.jargon_sources {
+        let previous = current.get(&source.id);
+        let fingerprint = jargon_sources::fingerprint(Path::new(&source.path));
+        if let Some(previous) = previous.filter(|previous| {
+            previous.path == source.path
+                && previous.fingerprint.as_ref() == fingerprint.as_ref().ok()
+        }) {
+            next.insert(source.id.clone(), previous.clone());
+            continue;
+        }
+
+        changed = true;
+        let entry = match jargon_sources::load(source) {
+            Ok(profile) => {
+                debug!(
+                    "Loaded jargon source '{}': {} terms, {} corrections",
+                    source.id,
+                    profile.terms.len(),
+                    profile.corrections.len()
+                );
+                LoadedSource {
+                    path: source.path.clone(),
+                    fingerprint: fingerprint.ok(),
+                    profile: Some(Arc::new(profile)),
+                    error: None,
+                }
+            }
+            Err(err) => {
+                warn!("Failed to load jargon source '{}': {}", source.id, err);
+                // Keep the last good contents of the same path.
+                let profile = previous
+                    .filter(|previous| previous.path == source.path)
+                    .and_then(|previous| previous.profile.clone());
+                LoadedSource {
+                    path: source.path.clone(),
+                    fingerprint: fingerprint.ok(),
+                    profile,
+                    error: Some(err),
+                }
+            }
+        };
+        next.insert(source.id.clone(), entry);
+    }
+
+    if changed {
+        *state.loaded.write().unwrap() = next;
+        state.revision.fetch_add(1, Ordering::AcqRel);
+        let _ = app.emit("jargon-sources-changed", ());
+    }
+}
@@ -1,9 +1,13 @@
 pub mod audio;
 pub mod domain_selector;
 pub mod history;
+pub mod intent_router;
+pub mod jargon_sources;
 pub mod model;
 pub mod model_catalog;
 pub mod recording_pipeline;
+pub mod reranker;
+pub mod workspace_jargon;
 
 #[cfg(not(feature = "mock_transcription"))]
 pub mod transcription;
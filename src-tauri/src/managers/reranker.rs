@@ -0,0 +1,434 @@
+//! Re-ranker sidecar: picks the best of several post-processing candidates
+//! (the raw transcription, a rule-based cleanup, the LLM output and the LLM
+//! output under a second prompt) for the active context.
+//!
+//! Candidates that drop protected spans or drift from the transcription are
+//! ruled out first, using the same check as the divergence guard. The rest
+//! are scored on jargon and workspace terms spelled exactly, fidelity to the
+//! transcription and surface polish. Fidelity comes from a cross-encoder
+//! ONNX model (`ms-marco-MiniLM-L-6-v2` or similar) when one is installed,
+//! and from word similarity otherwise.
+//!
+//! The model is loaded by `warm_up` when recording starts, never while
+//! scoring. Scoring runs under a strict deadline. On timeout, or when no
+//! candidate clearly beats a safer one, the safest candidate wins.
+
+use crate::settings::AppSettings;
+use crate::text_model::{self, TextModel};
+use anyhow::Result;
+use log::{debug, warn};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const MAX_TOKENS: usize = 512;
+/// Most context terms checked against each candidate.
+const MAX_CONTEXT_TERMS: usize = 300;
+/// A candidate must beat every safer one by this much to be picked.
+const MIN_MARGIN: f32 = 0.02;
+/// Word similarity above this counts as fully faithful; light cleanup
+/// should not lose to the raw text on fidelity alone.
+const FIDELITY_SATURATION: f32 = 0.9;
+
+const TERMS_WEIGHT: f32 = 0.4;
+const FIDELITY_WEIGHT: f32 = 0.3;
+const POLISH_WEIGHT: f32 = 0.3;
+
+const FILLER_WORDS: &[&str] = &["um", "uh", "erm", "uhm", "hmm", "mhm"];
+
+/// Where a candidate came from, safest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CandidateKind {
+    Raw,
+    RuleCleaned,
+    Llm,
+    LlmSecondPrompt,
+}
+
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub kind: CandidateKind,
+    pub text: String,
+}
+
+impl Candidate {
+    pub fn new(kind: CandidateKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// What the candidates are judged against.
+#[derive(Debug, Clone, Default)]
+pub struct RerankContext {
+    /// The transcription every candidate was produced from.
+    pub raw: String,
+    /// Active jargon and workspace terms, in their exact spelling.
+    pub terms: Vec<String>,
+}
+
+/// Relevance of `passage` to `query` under the cross-encoder, squashed to
+/// 0..1.
+fn score_pair(model: &mut TextModel, query: &str, passage: &str) -> Result<f32> {
+    let output = model.run((query, passage))?;
+    let logit = *output
+        .data
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Re-ranker model returned no score"))?;
+    Ok(1.0 / (1.0 + (-logit).exp()))
+}
+
+#[derive(Default)]
+pub struct RerankerManager {
+    model: Arc<Mutex<Option<TextModel>>>,
+}
+
+impl RerankerManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the configured model in the background. Called when recording
+    /// starts so the first re-rank is not cut short by the load.
+    pub fn warm_up(&self, settings: &AppSettings) {
+        let Some(dir) = text_model::model_dir(settings.reranker_model_dir.as_deref()) else {
+            return;
+        };
+        let model = Arc::clone(&self.model);
+        std::thread::spawn(move || {
+            if let Ok(mut model) = model.try_lock() {
+                ensure_model(&mut model, &dir);
+            }
+        });
+    }
+
+    /// Pick one of `candidates`, which must include the raw transcription.
+    /// Never fails: when scoring is off, slow or unsure, the safest
+    /// candidate is returned. Blocks for up to the timeout, so async
+    /// callers run it on a blocking thread.
+    pub fn rerank_with_timeout(
+        &self,
+        settings: &AppSettings,
+        context: &RerankContext,
+        candidates: Vec<Candidate>,
+    ) -> Candidate {
+        let mut candidates = safe_candidates(&context.raw, candidates);
+        let safest = candidates[0].clone();
+        if !settings.reranker_enabled || candidates.len() == 1 {
+            return safest;
+        }
+
+        let timeout = Duration::from_millis(settings.reranker_timeout_ms.max(25));
+        let dir = text_model::model_dir(settings.reranker_model_dir.as_deref());
+        let model = Arc::clone(&self.model);
+        let context = context.clone();
+        let scored = candidates.clone();
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let scores = score_candidates(&model, dir.as_deref(), &context, &scored);
+            let _ = tx.send(scores);
+        });
+
+        let scores = match rx.recv_timeout(timeout) {
+            Ok(scores) => scores,
+            Err(_) => {
+                warn!(
+                    "Re-ranker sidecar timed out after {}ms; keeping the safest candidate",
+                    timeout.as_millis()
+                );
+                return safest;
+            }
+        };
+
+        debug!(
+            "Re-ranker scores: {:?}",
+            candidates
+                .iter()
+                .zip(&scores)
+                .map(|(candidate, score)| format!("{:?}:{:.3}", candidate.kind, score))
+                .collect::<Vec<_>>()
+        );
+        let index = pick(&scores);
+        candidates.swap_remove(index)
+    }
+}
+
+/// Drop candidates that would fail the divergence guard, and sort the rest
+/// safest first. The raw transcription always stays.
+fn safe_candidates(raw: &str, candidates: Vec<Candidate>) -> Vec<Candidate> {
+    let mut safe: Vec<Candidate> = candidates
+        .into_iter()
+        .filter(|candidate| match candidate.kind {
+            CandidateKind::Raw => true,
+            _ => match crate::cleanup::check_output_divergence(raw, &candidate.text) {
+                Ok(()) => true,
+                Err(reason) => {
+                    debug!(
+                        "Re-ranker dropped {:?} candidate: {}",
+                        candidate.kind, reason
+                    );
+                    false
+                }
+            },
+        })
+        .collect();
+    if !safe
+        .iter()
+        .any(|candidate| candidate.kind == CandidateKind::Raw)
+    {
+        safe.push(Candidate::new(CandidateKind::Raw, raw));
+    }
+    safe.sort_by_key(|candidate| candidate.kind);
+    safe
+}
+
+/// Index of the best score, where a candidate only beats a safer one by at
+/// least `MIN_MARGIN`. `scores` are in safety order.
+fn pick(scores: &[f32]) -> usize {
+    let best = scores.iter().copied().fold(f32::MIN, f32::max);
+    scores
+        .iter()
+        .position(|&score| score + MIN_MARGIN >= best)
+        .unwrap_or(0)
+}
+
+fn score_candidates(
+    model: &Mutex<Option<TextModel>>,
+    dir: Option<&Path>,
+    context: &RerankContext,
+    candidates: &[Candidate],
+) -> Vec<f32> {
+    let fidelity = dir
+        .and_then(|dir| model_fidelity(model, dir, context, candidates))
+        .unwrap_or_else(|| {
+            candidates
+                .iter()
+                .map(|candidate| lexical_fidelity(&context.raw, &candidate.text))
+                .collect()
+        });
+
+    let terms: Vec<&str> = context
+        .terms
+        .iter()
+        .take(MAX_CONTEXT_TERMS)
+        .map(String::as_str)
+        .collect();
+    let hits: Vec<f32> = candidates
+        .iter()
+        .map(|candidate| term_hits(&candidate.text, &terms))
+        .collect();
+    let most_hits = hits.iter().copied().fold(0.0, f32::max);
+
+    candidates
+        .iter()
+        .zip(hits)
+        .zip(fidelity)
+        .map(|((candidate, hits), fidelity)| {
+            let terms = if most_hits > 0.0 {
+                (hits / most_hits).max(0.0)
+            } else {
+                0.0
+            };
+            TERMS_WEIGHT * terms
+                + FIDELITY_WEIGHT * fidelity
+                + POLISH_WEIGHT * polish(&candidate.text)
+        })
+        .collect()
+}
+
+/// Load the model in `dir` into `slot` unless it is already there.
+fn ensure_model(slot: &mut Option<TextModel>, dir: &Path) {
+    if slot.as_ref().is_some_and(|model| model.dir == dir) {
+        return;
+    }
+    match TextModel::load(dir, "re-ranker", None, MAX_TOKENS) {
+        Ok(model) => *slot = Some(model),
+        Err(err) => {
+            warn!(
+                "Re-ranker model unavailable, using lexical scoring: {}",
+                err
+            );
+            *slot = None;
+        }
+    }
+}
+
+/// Cross-encoder fidelity per candidate, or `None` to use the lexical
+/// scorer. A model still loading, or not yet warmed up, is not waited for.
+fn model_fidelity(
+    model: &Mutex<Option<TextModel>>,
+    dir: &Path,
+    context: &RerankContext,
+    candidates: &[Candidate],
+) -> Option<Vec<f32>> {
+    let mut slot = model.try_lock().ok()?;
+    let model = slot.as_mut().filter(|model| model.dir == dir)?;
+    candidates
+        .iter()
+        .map(|candidate| score_pair(model, &context.raw, &candidate.text))
+        .collect::<Result<Vec<f32>>>()
+        .map_err(|err| warn!("Re-ranker model failed, using lexical scoring: {}", err))
+        .ok()
+}
+
+fn lexical_fidelity(raw: &str, text: &str) -> f32 {
+    (crate::cleanup::word_similarity(raw, text) / FIDELITY_SATURATION).min(1.0)
+}
+
+/// Occurrences of `terms` spelled exactly, less half of those spelled
+/// differently ("Github" for "GitHub").
+fn term_hits(text: &str, terms: &[&str]) -> f32 {
+    let lower = text.to_ascii_lowercase();
+    let mut hits = 0.0;
+    for term in terms.iter().filter(|term| !term.is_empty()) {
+        let needle = term.to_ascii_lowercase();
+        for (start, _) in lower.match_indices(&needle) {
+            let end = start + needle.len();
+            let bounded =
+                !lower[..start].ends_with(is_word_char) && !lower[end..].starts_with(is_word_char);
+            if !bounded {
+                continue;
+            }
+            hits += if &text[start..end] == *term {
+                1.0
+            } else {
+                -0.5
+            };
+        }
+    }
+    hits
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// 0..1: starts with a capital, ends a sentence, and has no filler words
+/// or stutters ("the the").
+fn polish(text: &str) -> f32 {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+
+    let fillers = words
+        .iter()
+        .filter(|word| FILLER_WORDS.contains(&word.as_str()))
+        .count();
+    let stutters = words.windows(2).filter(|pair| pair[0] == pair[1]).count();
+    let defects = ((fillers + stutters) as f32 * 5.0 / words.len() as f32).min(1.0);
+
+    let trimmed = text.trim();
+    let mut polish = 0.5 * (1.0 - defects);
+    if trimmed
+        .chars()
+        .find(|c| c.is_alphabetic())
+        .is_some_and(char::is_uppercase)
+    {
+        polish += 0.25;
+    }
+    if trimmed.ends_with(['.', '!', '?', ')', '`']) {
+        polish += 0.25;
+    }
+    polish
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_settings() -> AppSettings {
+        let mut settings = crate::settings::get_default_settings();
+        settings.reranker_enabled = true;
+        settings.reranker_model_dir = None;
+        settings
+    }
+
+    fn rerank(context: &RerankContext, candidates: Vec<Candidate>) -> CandidateKind {
+        RerankerManager::new()
+            .rerank_with_timeout(&make_settings(), context, candidates)
+            .kind
+    }
+
+    #[test]
+    fn exact_jargon_spelling_wins() {
+        let context = RerankContext {
+            raw: "um so we deploy it with cube control and then check the the github actions logs"
+                .to_string(),
+            terms: vec!["kubectl".to_string(), "GitHub Actions".to_string()],
+        };
+        let candidates = vec![
+            Candidate::new(CandidateKind::Raw, context.raw.clone()),
+            Candidate::new(
+                CandidateKind::RuleCleaned,
+                "So we deploy it with cube control and then check the github actions logs.",
+            ),
+            Candidate::new(
+                CandidateKind::Llm,
+                "So we deploy it with kubectl and then check the GitHub Actions logs.",
+            ),
+        ];
+        assert_eq!(rerank(&context, candidates), CandidateKind::Llm);
+    }
+
+    #[test]
+    fn unsafe_candidates_and_ties_fall_back_to_safer_ones() {
+        let context = RerankContext {
+            raw: "Open @src/main.rs and look at the parser.".to_string(),
+            terms: Vec::new(),
+        };
+        // Dropping the @file reference rules the LLM output out.
+        let dropped = vec![
+            Candidate::new(CandidateKind::Raw, context.raw.clone()),
+            Candidate::new(
+                CandidateKind::Llm,
+                "Open the main file and look at the parser.",
+            ),
+        ];
+        assert_eq!(rerank(&context, dropped), CandidateKind::Raw);
+
+        // Nothing to improve: the raw text is kept.
+        let same = vec![
+            Candidate::new(CandidateKind::Llm, context.raw.clone()),
+            Candidate::new(CandidateKind::Raw, context.raw.clone()),
+        ];
+        assert_eq!(rerank(&context, same), CandidateKind::Raw);
+    }
+
+    #[test]
+    fn disabled_reranker_keeps_the_safest_candidate() {
+        let mut settings = make_settings();
+        settings.reranker_enabled = false;
+        let context = RerankContext {
+            raw: "uh hello there".to_string(),
+            terms: Vec::new(),
+        };
+        let chosen = RerankerManager::new().rerank_with_timeout(
+            &settings,
+            &context,
+            vec![Candidate::new(CandidateKind::Llm, "Hello there.")],
+        );
+        assert_eq!(chosen.kind, CandidateKind::Raw);
+        assert_eq!(chosen.text, "uh hello there");
+    }
+
+    #[test]
+    fn term_hits_penalize_wrong_spelling() {
+        let terms = ["GitHub"];
+        assert_eq!(term_hits("push to GitHub", &terms), 1.0);
+        assert_eq!(term_hits("push to github", &terms), -0.5);
+        assert_eq!(term_hits("push to githubs", &terms), 0.0);
+        assert!(polish("Hello there.") > polish("um hello hello there"));
+    }
+}
@@ -1,11 +1,13 @@
 use crate::audio_toolkit::{apply_custom_words, filter_transcription_output};
 use crate::domain::events::{ModelStateEvent, ModelStateKind};
+use crate::jargon_sources::JargonScope;
 use crate::managers::domain_selector::{DomainContext, DomainSelectorManager};
+use crate::managers::jargon_sources::{active_dictionary, jargon_in_use};
 use crate::managers::model::{EngineType, ModelManager};
+use crate::phonetic::apply_phonetic_matches;
 use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
 use anyhow::Result;
 use log::{debug, error, info, warn};
-use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
@@ -47,21 +49,6 @@ pub struct TranscriptionManager {
 }
 
 impl TranscriptionManager {
-    fn build_profiles_map(settings: &AppSettings) -> HashMap<String, crate::jargon::JargonProfile> {
-        let mut profiles = crate::jargon::builtin_profiles();
-        for pack in &settings.jargon_packs {
-            profiles.insert(
-                pack.id.clone(),
-                crate::jargon::JargonProfile {
-                    label: pack.label.clone(),
-                    terms: pack.terms.clone(),
-                    corrections: pack.corrections.clone(),
-                },
-            );
-        }
-        profiles
-    }
-
     fn effective_profile_ids(&self, settings: &AppSettings, context_text: &str) -> Vec<String> {
         let mut profile_ids = settings.jargon_enabled_profiles.clone();
         if let Some(selector) = self.app_handle.try_state::<Arc<DomainSelectorManager>>() {
@@ -69,6 +56,7 @@ impl TranscriptionManager {
                 settings,
                 &DomainContext {
                     text: context_text.to_string(),
+                    app: None,
                 },
             );
             if let Some(auto_profiles) = auto_profiles {
@@ -395,7 +383,7 @@ impl TranscriptionManager {
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
+    pub fn transcribe(&self, audio: Vec<f32>, scope: &JargonScope) -> Result<String> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -459,26 +447,21 @@ impl TranscriptionManager {
                     };
 
                     // Build jargon initial_prompt if jargon has active terms
-                    let initial_prompt = if !settings.jargon_enabled_profiles.is_empty()
-                        || !settings.jargon_custom_terms.is_empty()
-                        || !settings.jargon_packs.is_empty()
-                    {
-                        let profiles = Self::build_profiles_map(&settings);
+                    let initial_prompt = if jargon_in_use(&settings, scope) {
                         let effective_profiles = self.effective_profile_ids(&settings, "");
-                        let jargon_settings = crate::jargon::JargonSettings {
-                            enabled_profiles: effective_profiles,
-                            custom_terms: settings.jargon_custom_terms.clone(),
-                            custom_corrections: settings.jargon_custom_corrections.clone(),
-                        };
-                        let dict =
-                            crate::jargon::compute_active_dictionary(&jargon_settings, &profiles);
-                        if !dict.terms.is_empty() {
-                            let prompt = crate::jargon::build_initial_prompt(&dict);
+                        let compiled = active_dictionary(
+                            &self.app_handle,
+                            &settings,
+                            effective_profiles,
+                            scope,
+                        );
+                        if !compiled.dictionary.terms.is_empty() {
+                            let prompt = crate::jargon::build_initial_prompt(&compiled.dictionary);
                             if !prompt.is_empty() {
                                 debug!(
                                     "Jargon initial_prompt ({} chars): {}",
                                     prompt.len(),
-                                    &prompt[..prompt.len().min(100)]
+                                    prompt.chars().take(100).collect::<String>()
                                 );
                                 Some(prompt)
                             } else {
@@ -548,22 +531,22 @@ impl TranscriptionManager {
         // Filter out filler words and hallucinations
         let filtered_result = filter_transcription_output(&corrected_result);
 
-        // Apply jargon corrections
-        let filtered_result = if !settings.jargon_enabled_profiles.is_empty()
-            || !settings.jargon_custom_corrections.is_empty()
-            || !settings.jargon_packs.is_empty()
-        {
-            let profiles = Self::build_profiles_map(&settings);
+        let compiled = if jargon_in_use(&settings, scope) {
             let effective_profiles = self.effective_profile_ids(&settings, &filtered_result);
-            let jargon_settings = crate::jargon::JargonSettings {
-                enabled_profiles: effective_profiles,
-                custom_terms: settings.jargon_custom_terms.clone(),
-                custom_corrections: settings.jargon_custom_corrections.clone(),
-            };
-            let dict = crate::jargon::compute_active_dictionary(&jargon_settings, &profiles);
-            if !dict.corrections.is_empty() {
-                let corrected =
-                    crate::jargon::apply_corrections(&filtered_result, &dict.corrections);
+            Some(active_dictionary(
+                &self.app_handle,
+                &settings,
+                effective_profiles,
+                scope,
+            ))
+        } else {
+            None
+        };
+
+        // Apply jargon corrections
+        let filtered_result = match &compiled {
+            Some(compiled) if !compiled.corrections.is_empty() => {
+                let corrected = compiled.corrections.apply(&filtered_result);
                 if corrected != filtered_result {
                     debug!(
                         "Jargon corrections applied: {} -> {} chars",
@@ -572,9 +555,18 @@ impl TranscriptionManager {
                     );
                 }
                 corrected
-            } else {
-                filtered_result
             }
+            _ => filtered_result,
+        };
+
+        // Catch what the corrections did not list by sound
+        let filtered_result = if settings.phonetic_matching_enabled {
+            // Without jargon, the index holds only the custom words.
+            let compiled = compiled.unwrap_or_else(|| {
+                active_dictionary(&self.app_handle, &settings, Vec::new(), scope)
+            });
+            let index = compiled.phonetic_index(&settings.custom_words);
+            apply_phonetic_matches(&filtered_result, index, settings.phonetic_match_threshold)
         } else {
             filtered_result
         };
@@ -0,0 +1,104 @@
+//! Keeps the jargon profile for the current workspace root. See
+//! `crate::workspace_jargon` for what goes into it.
+
+use crate::at_file_expansion::is_git_repository;
+use crate::context_providers::get_workspace_root;
+use crate::jargon::JargonProfile;
+use crate::settings::AppSettings;
+use crate::workspace_jargon;
+use log::debug;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Instant;
+
+struct LoadedWorkspace {
+    root: PathBuf,
+    profile: Arc<JargonProfile>,
+}
+
+#[derive(Default)]
+struct WorkspaceState {
+    loaded: RwLock<Option<LoadedWorkspace>>,
+    /// Bumped whenever `loaded` changes.
+    revision: AtomicU64,
+    refreshing: AtomicBool,
+}
+
+#[derive(Default)]
+pub struct WorkspaceJargonManager {
+    state: Arc<WorkspaceState>,
+}
+
+impl WorkspaceJargonManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up the workspace root in the background and rebuild the profile
+    /// if it moved. Called when recording starts, so the profile is usually
+    /// ready by the time the transcription needs it.
+    pub fn refresh(&self, settings: &AppSettings) {
+        if !settings.workspace_jargon_enabled {
+            self.clear();
+            return;
+        }
+        if self.state.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let state = Arc::clone(&self.state);
+        let settings = settings.clone();
+        thread::spawn(move || {
+            let root = get_workspace_root(&settings).filter(|root| is_git_repository(root));
+            let current = state
+                .loaded
+                .read()
+                .unwrap()
+                .as_ref()
+                .map(|loaded| loaded.root.clone());
+            if root != current {
+                let loaded = root.map(|root| {
+                    let started = Instant::now();
+                    let profile = workspace_jargon::build_profile(&root);
+                    debug!(
+                        "Built workspace jargon for {}: {} terms, {} corrections in {:?}",
+                        root.display(),
+                        profile.terms.len(),
+                        profile.corrections.len(),
+                        started.elapsed()
+                    );
+                    LoadedWorkspace {
+                        root,
+                        profile: Arc::new(profile),
+                    }
+                });
+                *state.loaded.write().unwrap() = loaded;
+                state.revision.fetch_add(1, Ordering::AcqRel);
+            }
+            state.refreshing.store(false, Ordering::Release);
+        });
+    }
+
+    fn clear(&self) {
+        let mut loaded = self.state.loaded.write().unwrap();
+        if loaded.take().is_some() {
+            self.state.revision.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.state.revision.load(Ordering::Acquire)
+    }
+
+    /// The profile for the last workspace root seen, if any.
+    pub fn profile(&self) -> Option<Arc<JargonProfile>> {
+        self.state
+            .loaded
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|loaded| Arc::clone(&loaded.profile))
+    }
+}
@@ -354,6 +354,13 @@ pub fn hide_recording_overlay(app_handle: &AppHandle) {
     }
 }
 
+/// Sends partial post-processing output to the overlay while an LLM response streams in.
+pub fn emit_post_process_partial(app_handle: &AppHandle, text: &str) {
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("post-process-partial", text);
+    }
+}
+
 pub fn emit_levels(app_handle: &AppHandle, levels: &Vec<f32>) {
     // emit levels to main app
     let _ = app_handle.emit("mic-level", levels);
@@ -0,0 +1,551 @@
+//! Sound-based matching for jargon terms and custom words.
+//!
+//! Exact corrections only catch phrases someone wrote down in advance. This
+//! layer indexes every term of the `ActiveDictionary` (plus custom words and
+//! the spoken side of each correction) by its Double Metaphone codes, then
+//! compares 1-3 word n-grams of the transcription against it, so "cube
+//! cuttle" or "post gress" land on `kubectl` and `Postgres`.
+//!
+//! A match needs a combined confidence of sound and spelling similarity
+//! above the configured threshold. Protected spans (paths, URLs, flags, code)
+//! are masked first, like `jargon::apply_corrections`.
+
+use crate::audio_toolkit::text::word_spans;
+use crate::jargon::{mask_protected_spans, restore_protected_spans, ActiveDictionary};
+use log::{debug, warn};
+use std::collections::HashSet;
+use strsim::normalized_levenshtein;
+
+/// Longest n-gram, in words, compared against the index.
+const MAX_NGRAM_WORDS: usize = 3;
+/// Codes shorter than this match too many everyday words ("cash" vs "cache").
+const MIN_CODE_LEN: usize = 3;
+/// Candidates shorter than this, letters only, are left alone.
+const MIN_CANDIDATE_LEN: usize = 4;
+/// How much the sound counts against the spelling in the confidence.
+const CODE_WEIGHT: f64 = 0.6;
+
+// ============================================================================
+// Double Metaphone
+// ============================================================================
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+/// Primary and alternate Double Metaphone codes for `word`.
+///
+/// This is a compact variant that keeps the English rules (silent letters,
+/// soft C/G, PH, TH, SH, TIO, ...) and leaves out the rarer Slavic,
+/// Germanic and Romance special cases. Codes are not truncated.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    let w: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut primary = String::new();
+    let mut alternate = String::new();
+    if w.is_empty() {
+        return (primary, alternate);
+    }
+
+    let n = w.len();
+    let at = |i: usize| w.get(i).copied().unwrap_or('\0');
+    let starts = |i: usize, s: &str| {
+        s.chars()
+            .enumerate()
+            .all(|(offset, c)| w.get(i + offset) == Some(&c))
+    };
+    let mut add = |p: &str, a: &str| {
+        primary.push_str(p);
+        alternate.push_str(a);
+    };
+
+    let mut i = 0;
+    if ["GN", "KN", "PN", "WR", "PS"].iter().any(|s| starts(0, s)) {
+        i = 1;
+    }
+    if w[0] == 'X' {
+        add("S", "S");
+        i = 1;
+    }
+
+    while i < n {
+        let c = w[i];
+        if is_vowel(c) {
+            if i == 0 {
+                add("A", "A");
+            }
+            i += 1;
+            continue;
+        }
+        let next = at(i + 1);
+        match c {
+            'B' => {
+                add("P", "P");
+                i += if next == 'B' { 2 } else { 1 };
+            }
+            'C' => {
+                if starts(i, "CH") {
+                    let hard = i == 0
+                        && ["CHARAC", "CHARIS", "CHORUS", "CHEMIS"]
+                            .iter()
+                            .any(|s| starts(0, s));
+                    if hard {
+                        add("K", "X");
+                    } else {
+                        add("X", "K");
+                    }
+                    i += 2;
+                } else if matches!(next, 'I' | 'E' | 'Y') {
+                    add("S", "S");
+                    i += 2;
+                } else if matches!(next, 'C' | 'K' | 'G' | 'Q') {
+                    if next == 'C' && matches!(at(i + 2), 'I' | 'E' | 'Y') {
+                        add("KS", "KS");
+                        i += 3;
+                    } else {
+                        add("K", "K");
+                        i += 2;
+                    }
+                } else {
+                    add("K", "K");
+                    i += 1;
+                }
+            }
+            'D' => {
+                if next == 'G' && matches!(at(i + 2), 'I' | 'E' | 'Y') {
+                    add("J", "J");
+                    i += 3;
+                } else {
+                    add("T", "T");
+                    i += if matches!(next, 'T' | 'D') { 2 } else { 1 };
+                }
+            }
+            'G' => {
+                if next == 'H' {
+                    // "gh" after a vowel is silent, as in "night".
+                    if i == 0 || !is_vowel(at(i - 1)) {
+                        add("K", "K");
+                    }
+                    i += 2;
+                } else if next == 'N' {
+                    if i == 0 || i + 2 == n {
+                        add("N", "N");
+                    } else {
+                        add("KN", "N");
+                    }
+                    i += 2;
+                } else if matches!(next, 'E' | 'I' | 'Y') {
+                    add("J", "K");
+                    i += 2;
+                } else {
+                    add("K", "K");
+                    i += if next == 'G' { 2 } else { 1 };
+                }
+            }
+            'H' => {
+                if (i == 0 || is_vowel(at(i - 1))) && is_vowel(next) {
+                    add("H", "H");
+                }
+                i += 1;
+            }
+            'J' => {
+                add("J", "H");
+                i += if next == 'J' { 2 } else { 1 };
+            }
+            'M' => {
+                add("M", "M");
+                // Final "mb" as in "thumb".
+                i += if next == 'M' || (next == 'B' && i + 2 == n) {
+                    2
+                } else {
+                    1
+                };
+            }
+            'P' => {
+                if next == 'H' {
+                    add("F", "F");
+                    i += 2;
+                } else {
+                    add("P", "P");
+                    i += if matches!(next, 'P' | 'B') { 2 } else { 1 };
+                }
+            }
+            'S' => {
+                if next == 'H' {
+                    add("X", "X");
+                    i += 2;
+                } else if starts(i, "SIO") || starts(i, "SIA") {
+                    add("S", "X");
+                    i += 3;
+                } else if starts(i, "SCH") {
+                    add("SK", "SK");
+                    i += 3;
+                } else if next == 'C' && matches!(at(i + 2), 'E' | 'I' | 'Y') {
+                    add("S", "S");
+                    i += 3;
+                } else {
+                    add("S", "S");
+                    i += if matches!(next, 'S' | 'Z') { 2 } else { 1 };
+                }
+            }
+            'T' => {
+                if starts(i, "TIO") || starts(i, "TIA") {
+                    add("X", "X");
+                    i += 3;
+                } else if starts(i, "TCH") {
+                    add("X", "X");
+                    i += 3;
+                } else if next == 'H' {
+                    add("0", "T");
+                    i += 2;
+                } else {
+                    add("T", "T");
+                    i += if matches!(next, 'T' | 'D') { 2 } else { 1 };
+                }
+            }
+            'W' => {
+                if i == 0 && is_vowel(next) {
+                    add("A", "F");
+                }
+                i += 1;
+            }
+            'X' => {
+                add("KS", "KS");
+                i += if matches!(next, 'C' | 'X') { 2 } else { 1 };
+            }
+            _ => {
+                let code = match c {
+                    'F' | 'V' => "F",
+                    'K' | 'Q' => "K",
+                    'Z' => "S",
+                    'L' => "L",
+                    'N' => "N",
+                    'R' => "R",
+                    _ => "",
+                };
+                add(code, code);
+                let double = next == c || (c == 'V' && next == 'F') || (c == 'F' && next == 'V');
+                i += if double { 2 } else { 1 };
+            }
+        }
+    }
+
+    (primary, alternate)
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+struct Entry {
+    replacement: String,
+    /// Lowercase letters and digits only.
+    compact: String,
+    codes: (String, String),
+}
+
+/// Dictionary entries keyed by sound.
+pub struct PhoneticIndex {
+    entries: Vec<Entry>,
+}
+
+fn compact(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn code_distance(a: &(String, String), b: &(String, String)) -> f64 {
+    [(&a.0, &b.0), (&a.0, &b.1), (&a.1, &b.0), (&a.1, &b.1)]
+        .iter()
+        .map(|(x, y)| 1.0 - normalized_levenshtein(x, y))
+        .fold(f64::MAX, f64::min)
+}
+
+/// How alike `a` and `b` sound, from 0.0 to 1.0 (same codes).
+pub fn sound_similarity(a: &str, b: &str) -> f64 {
+    let a = double_metaphone(&compact(a));
+    let b = double_metaphone(&compact(b));
+    if a.0.is_empty() || b.0.is_empty() {
+        return 0.0;
+    }
+    1.0 - code_distance(&a, &b)
+}
+
+impl PhoneticIndex {
+    /// Index dictionary terms, custom words, and the spoken side of each
+    /// unconditional correction (which maps to its written side).
+    pub fn new(dictionary: &ActiveDictionary, custom_words: &[String]) -> Self {
+        let targets = dictionary
+            .terms
+            .iter()
+            .chain(custom_words)
+            .map(|term| (term.as_str(), term.as_str()))
+            .chain(
+                dictionary
+                    .corrections
+                    .iter()
+                    .filter(|c| !c.is_conditional())
+                    .map(|c| (c.from.as_str(), c.to.as_str())),
+            );
+
+        let mut seen = HashSet::new();
+        let mut entries: Vec<Entry> = Vec::new();
+        for (spoken, replacement) in targets {
+            let compact = compact(spoken);
+            let codes = double_metaphone(&compact);
+            if codes.0.len() < MIN_CODE_LEN || replacement.trim().is_empty() {
+                continue;
+            }
+            if !seen.insert(compact.clone()) {
+                continue;
+            }
+            entries.push(Entry {
+                replacement: replacement.to_string(),
+                compact,
+                codes,
+            });
+        }
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Best entry for `candidate` and its confidence (1.0 = same sound and
+    /// spelling), if any reaches `threshold`.
+    pub fn best_match(&self, candidate: &str, threshold: f64) -> Option<(&str, f64)> {
+        let candidate = compact(candidate);
+        if candidate.chars().count() < MIN_CANDIDATE_LEN || candidate.len() > 50 {
+            return None;
+        }
+        let codes = double_metaphone(&candidate);
+        if codes.0.len() < MIN_CODE_LEN {
+            return None;
+        }
+
+        let mut best: Option<(&Entry, f64)> = None;
+        for entry in &self.entries {
+            if entry.codes.0.len().abs_diff(codes.0.len()) > 2 {
+                continue;
+            }
+            let sound = code_distance(&codes, &entry.codes);
+            let spelling = 1.0 - normalized_levenshtein(&candidate, &entry.compact);
+            let confidence = 1.0 - (CODE_WEIGHT * sound + (1.0 - CODE_WEIGHT) * spelling);
+            if confidence >= threshold && best.map_or(true, |(_, score)| confidence > score) {
+                best = Some((entry, confidence));
+            }
+        }
+        best.map(|(entry, score)| (entry.replacement.as_str(), score))
+    }
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// Replace n-grams that sound like an indexed term. At each word the most
+/// confident n-gram wins; it is skipped if the n-gram starting one word later
+/// is more confident, so a match does not swallow the word before it. A
+/// single word that only differs from its match in case is left as spoken.
+pub fn apply_phonetic_matches(text: &str, index: &PhoneticIndex, threshold: f64) -> String {
+    if index.is_empty() || text.is_empty() {
+        return text.to_string();
+    }
+
+    let (masked, spans) = mask_protected_spans(text);
+    let bounds = word_spans(&masked);
+    let words: Vec<&str> = bounds
+        .iter()
+        .map(|&(start, end)| &masked[start..end])
+        .collect();
+    let mut result = String::with_capacity(masked.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < words.len() {
+        let found = best_ngram_at(&words, i, index, threshold).filter(|found| {
+            if found.words == 1 {
+                return compact(found.replacement) != compact(&found.candidate);
+            }
+            best_ngram_at(&words, i + 1, index, threshold)
+                .map_or(true, |next| next.confidence <= found.confidence)
+        });
+        let Some(found) = found else {
+            i += 1;
+            continue;
+        };
+
+        debug!(
+            "Phonetic match '{}' -> '{}' ({:.2})",
+            found.candidate, found.replacement, found.confidence
+        );
+        let prefix = leading_punctuation(words[i]);
+        let suffix = trailing_punctuation(words[i + found.words - 1]);
+        result.push_str(&masked[copied..bounds[i].0]);
+        result.push_str(prefix);
+        result.push_str(found.replacement);
+        result.push_str(suffix);
+        copied = bounds[i + found.words - 1].1;
+        i += found.words;
+    }
+    result.push_str(&masked[copied..]);
+
+    let restored = restore_protected_spans(&result, &spans);
+    if let Some(span) = spans.iter().find(|s| restored.contains(&s.placeholder)) {
+        warn!(
+            "Placeholder {} was not properly restored, returning original text",
+            span.placeholder
+        );
+        return text.to_string();
+    }
+    restored
+}
+
+struct NgramMatch<'a> {
+    words: usize,
+    candidate: String,
+    replacement: &'a str,
+    confidence: f64,
+}
+
+fn best_ngram_at<'a>(
+    words: &[&str],
+    start: usize,
+    index: &'a PhoneticIndex,
+    threshold: f64,
+) -> Option<NgramMatch<'a>> {
+    let mut best: Option<NgramMatch<'a>> = None;
+    for n in (1..=MAX_NGRAM_WORDS).rev() {
+        let Some(ngram) = words.get(start..start + n) else {
+            continue;
+        };
+        if !is_matchable(ngram) {
+            continue;
+        }
+        let candidate = ngram
+            .iter()
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let Some((replacement, confidence)) = index.best_match(&candidate, threshold) else {
+            continue;
+        };
+        if best.as_ref().map_or(true, |b| confidence > b.confidence) {
+            best = Some(NgramMatch {
+                words: n,
+                candidate,
+                replacement,
+                confidence,
+            });
+        }
+    }
+    best
+}
+
+/// N-grams never span a placeholder or a sentence/clause break.
+fn is_matchable(ngram: &[&str]) -> bool {
+    let has_placeholder = ngram.iter().any(|w| w.contains('\u{27E6}'));
+    let broken = ngram[..ngram.len() - 1]
+        .iter()
+        .any(|w| w.ends_with(|c: char| matches!(c, ',' | '.' | ';' | ':' | '!' | '?')));
+    !has_placeholder && !broken
+}
+
+fn leading_punctuation(word: &str) -> &str {
+    let end = word
+        .find(|c: char| c.is_alphanumeric())
+        .unwrap_or(word.len());
+    &word[..end]
+}
+
+fn trailing_punctuation(word: &str) -> &str {
+    let start = word.rfind(|c: char| c.is_alphanumeric()).map_or(0, |i| {
+        i + word[i..].chars().next().map_or(1, char::len_utf8)
+    });
+    &word[start..]
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jargon::JargonCorrection;
+
+    fn index(terms: &[&str]) -> PhoneticIndex {
+        let dictionary = ActiveDictionary {
+            terms: terms.iter().map(|t| t.to_string()).collect(),
+            corrections: vec![JargonCorrection::new("engine x", "nginx")],
+        };
+        PhoneticIndex::new(&dictionary, &["Terraform".to_string()])
+    }
+
+    #[test]
+    fn codes_follow_english_spelling_rules() {
+        assert_eq!(double_metaphone("kubectl").0, "KPKTL");
+        assert_eq!(double_metaphone("cubecuttle").0, "KPKTL");
+        assert_eq!(double_metaphone("postgres"), double_metaphone("postgress"));
+        assert_eq!(double_metaphone("phone").0, "FN");
+        assert_eq!(double_metaphone("knight").0, "NT");
+        assert_eq!(double_metaphone("json"), double_metaphone("jason"));
+    }
+
+    #[test]
+    fn sound_alike_ngrams_are_replaced() {
+        let index = index(&["kubectl", "Postgres", "GitHub"]);
+        assert_eq!(
+            apply_phonetic_matches("run cube cuttle get pods", &index, 0.8),
+            "run kubectl get pods"
+        );
+        assert_eq!(
+            apply_phonetic_matches("Back up post gress, then push to get hub.", &index, 0.8),
+            "Back up Postgres, then push to GitHub."
+        );
+        assert_eq!(
+            apply_phonetic_matches("apply the tear a form plan", &index, 0.8),
+            "apply the Terraform plan"
+        );
+        assert_eq!(
+            apply_phonetic_matches("First:\n\nrun  cube cuttle\tnow", &index, 0.8),
+            "First:\n\nrun  kubectl\tnow"
+        );
+    }
+
+    #[test]
+    fn low_confidence_and_protected_text_is_kept() {
+        let index = index(&["kubectl", "Helm", "cache"]);
+        let text = "go home and clock out, cash it";
+        assert_eq!(apply_phonetic_matches(text, &index, 0.8), text);
+
+        let text = "open /tmp/cube/cuttle.txt and `cube cuttle`";
+        assert_eq!(apply_phonetic_matches(text, &index, 0.8), text);
+    }
+
+    #[test]
+    fn term_alone_reaches_close_spellings_only() {
+        // Only the term, no correction: "cube cuttle" shares its codes, but
+        // "cube control" (KPKNTRL vs KPKTL, 0.65) stays below the default
+        // threshold and is left to the devops profile's correction.
+        let index = PhoneticIndex::new(
+            &ActiveDictionary {
+                terms: vec!["kubectl".to_string()],
+                corrections: Vec::new(),
+            },
+            &[],
+        );
+        assert_eq!(
+            apply_phonetic_matches("run cube cuttle, not `cube cuttle`", &index, 0.8),
+            "run kubectl, not `cube cuttle`"
+        );
+        let text = "run cube control get pods";
+        assert_eq!(apply_phonetic_matches(text, &index, 0.8), text);
+    }
+}
@@ -6,30 +6,67 @@
 
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
+use crate::cleanup::{CleanupManager, TextCleanupProvider};
+use crate::cli_mode::{self, CliSafety};
+use crate::clipboard::TextEdit;
+use crate::jargon_sources::JargonScope;
+use crate::last_dictation;
 use crate::managers::domain_selector::{DomainContext, DomainSelectorManager};
-use crate::managers::history::HistoryManager;
+use crate::managers::history::{HistoryManager, PostProcessAttempt};
+use crate::managers::intent_router::{Intent, IntentRouterManager};
+use crate::managers::jargon_sources::{active_dictionary, jargon_in_use};
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
+use crate::pipeline_stages::{self, StageContext, StageRecord};
+use crate::settings::{
+    AppSettings, PasteMethod, PostProcessProvider, APPLE_INTELLIGENCE_PROVIDER_ID,
+    LOCAL_CLEANUP_PROVIDER_ID,
+};
+use crate::snippets;
+use crate::style_presets::{self, StylePreset};
+use crate::templates::{self, TemplateInvocation};
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils;
+use crate::voice_commands::{self, DictationAction, KeyAction};
 use crate::ManagedToggleState;
 use ferrous_opencc::{config::BuiltinConfig, OpenCC};
-use log::{debug, error, info};
+use futures_util::StreamExt;
+use log::{debug, error, info, warn};
 use once_cell::sync::Lazy;
 use regex::Regex;
-// similar crate is available but we use a simpler prefix/suffix diff
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 
 static SPACE_BEFORE_PUNCT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+([,.;:!?])").unwrap());
 
-fn normalize_segment_text_for_post_process(text: &str) -> String {
+pub(crate) fn normalize_segment_text_for_post_process(text: &str) -> String {
     let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
     let trimmed = collapsed.trim();
     SPACE_BEFORE_PUNCT_RE.replace_all(trimmed, "$1").to_string()
 }
 
+/// The style preset for a dictation with `binding_id` into the frontmost app.
+pub(crate) fn resolve_style_preset(
+    settings: &AppSettings,
+    binding_id: &str,
+) -> Option<StylePreset> {
+    // Looking up the frontmost app can spawn a process; skip it when no
+    // rule could use the answer.
+    let app_name = if settings.style_preset_app_rules.is_empty() {
+        None
+    } else {
+        crate::context_providers::frontmost_app_name()
+    };
+    let preset = style_presets::resolve(settings, binding_id, app_name.as_deref());
+    if let Some(preset) = &preset {
+        debug!(
+            "Using style preset '{}' (binding '{}', app {:?})",
+            preset.id, binding_id, app_name
+        );
+    }
+    preset
+}
+
 fn should_insert_boundary_space(left: &str, right: &str) -> bool {
     if left.is_empty() || right.is_empty() {
         return false;
@@ -47,23 +84,6 @@ fn should_insert_boundary_space(left: &str, right: &str) -> bool {
         )
 }
 
-fn build_profiles_map(
-    settings: &AppSettings,
-) -> std::collections::HashMap<String, crate::jargon::JargonProfile> {
-    let mut profiles = crate::jargon::builtin_profiles();
-    for pack in &settings.jargon_packs {
-        profiles.insert(
-            pack.id.clone(),
-            crate::jargon::JargonProfile {
-                label: pack.label.clone(),
-                terms: pack.terms.clone(),
-                corrections: pack.corrections.clone(),
-            },
-        );
-    }
-    profiles
-}
-
 fn effective_profiles_for_text(app: &AppHandle, settings: &AppSettings, text: &str) -> Vec<String> {
     let mut profile_ids = settings.jargon_enabled_profiles.clone();
     if let Some(selector) = app.try_state::<Arc<DomainSelectorManager>>() {
@@ -71,6 +91,7 @@ fn effective_profiles_for_text(app: &AppHandle, settings: &AppSettings, text: &s
             settings,
             &DomainContext {
                 text: text.to_string(),
+                app: None,
             },
         ) {
             if settings.domain_selector_blend_manual_profiles {
@@ -87,6 +108,27 @@ fn effective_profiles_for_text(app: &AppHandle, settings: &AppSettings, text: &s
     profile_ids
 }
 
+/// Active jargon terms for `text`, in their exact spelling.
+pub(crate) fn active_jargon_terms(
+    app: &AppHandle,
+    settings: &AppSettings,
+    text: &str,
+    binding_id: &str,
+) -> Vec<String> {
+    let scope = JargonScope {
+        binding_id: Some(binding_id.to_string()),
+        prompt_id: settings.post_process_selected_prompt_id.clone(),
+    };
+    if !jargon_in_use(settings, &scope) {
+        return Vec::new();
+    }
+    let profiles = effective_profiles_for_text(app, settings, text);
+    active_dictionary(app, settings, profiles, &scope)
+        .dictionary
+        .terms
+        .clone()
+}
+
 // ============================================================================
 // Pipeline state enum
 // ============================================================================
@@ -106,6 +148,12 @@ enum PipelineState {
         raw_text_pasted: bool,
     },
 
+    /// The utterance is a shell command; nothing has been pasted yet.
+    CliRequested { spoken: String },
+
+    /// The utterance asked for a template; nothing has been pasted yet.
+    TemplateRequested { invocation: TemplateInvocation },
+
     /// Post-processing complete. We have the original and processed text.
     PostProcessed {
         raw_text: String,
@@ -126,9 +174,20 @@ pub struct TranscriptionPipeline {
     app: AppHandle,
     settings: AppSettings,
     post_process: bool,
+    /// Started from the CLI binding: the whole dictation is a shell command.
+    cli_mode: bool,
     binding_id: String,
     /// Audio samples kept around for history saving.
     samples_for_history: Vec<f32>,
+    /// Timing and outcome of each stage run during post-processing.
+    stage_records: Vec<StageRecord>,
+    /// Resolved when the run starts, from the binding and frontmost app.
+    style_preset: Option<StylePreset>,
+    /// Key commands split off a dictation before post-processing, sent once
+    /// the final text is written.
+    pending_keys: Vec<KeyAction>,
+    /// Snippet bodies already pasted with the `pasted_segments`.
+    segment_snippets: Vec<String>,
 }
 
 impl TranscriptionPipeline {
@@ -137,6 +196,7 @@ impl TranscriptionPipeline {
         pasted_segments: Vec<String>,
         settings: AppSettings,
         post_process: bool,
+        cli_mode: bool,
         binding_id: String,
         app: AppHandle,
     ) -> Self {
@@ -149,13 +209,39 @@ impl TranscriptionPipeline {
             app,
             settings,
             post_process,
+            cli_mode,
             binding_id,
             samples_for_history,
+            stage_records: Vec::new(),
+            style_preset: None,
+            pending_keys: Vec::new(),
+            segment_snippets: Vec::new(),
         }
     }
 
+    /// Audio of the `pasted_segments`, which came before `samples`; kept so
+    /// history stores the whole recording.
+    pub fn with_segment_audio(mut self, segment_samples: Vec<f32>) -> Self {
+        if !segment_samples.is_empty() {
+            let mut samples = segment_samples;
+            samples.append(&mut self.samples_for_history);
+            self.samples_for_history = samples;
+        }
+        self
+    }
+
+    /// Snippet bodies expanded into the `pasted_segments`, so post-processing
+    /// does not rewrite them.
+    pub fn with_segment_snippets(mut self, snippet_expansions: Vec<String>) -> Self {
+        self.segment_snippets = snippet_expansions;
+        self
+    }
+
     /// Run the pipeline to completion.
     pub async fn run(mut self) {
+        // A new run invalidates whatever the previous one left behind.
+        last_dictation::clear(&self.app);
+        self.style_preset = self.resolve_style_preset();
         loop {
             self.state = match self.state {
                 PipelineState::Stopped { .. } => match self.transcribe_and_paste() {
@@ -173,11 +259,22 @@ impl TranscriptionPipeline {
                         self.finalize()
                     }
                 }
+                PipelineState::CliRequested { .. } => self.build_and_insert_command().await,
+                PipelineState::TemplateRequested { .. } => self.fill_and_insert_template().await,
                 PipelineState::PostProcessed { .. } => self.apply_diff_and_finalize(),
                 PipelineState::Done => break,
             };
         }
 
+        if !self.stage_records.is_empty() {
+            let total_ms: u64 = self.stage_records.iter().map(|r| r.elapsed_ms).sum();
+            debug!(
+                "Pipeline ran {} stages in {}ms",
+                self.stage_records.len(),
+                total_ms
+            );
+        }
+
         // Clear toggle state now that transcription is complete
         if let Ok(mut states) = self.app.state::<ManagedToggleState>().lock() {
             states.active_toggles.insert(self.binding_id.clone(), false);
@@ -188,7 +285,7 @@ impl TranscriptionPipeline {
     // Transitions
     // ========================================================================
 
-    /// Stopped → RawTextVisible | Done
+    /// Stopped → RawTextVisible | CliRequested | TemplateRequested | Done
     fn transcribe_and_paste(&mut self) -> Result<PipelineState, anyhow::Error> {
         let (samples, pasted_segments) =
             match std::mem::replace(&mut self.state, PipelineState::Done) {
@@ -201,7 +298,81 @@ impl TranscriptionPipeline {
 
         let tm = self.app.state::<Arc<TranscriptionManager>>();
         let transcription_time = Instant::now();
-        let remaining_transcription = tm.transcribe(samples)?;
+        let scope = JargonScope {
+            binding_id: Some(self.binding_id.clone()),
+            prompt_id: None,
+        };
+        let remaining_transcription = tm.transcribe(samples, &scope)?;
+
+        // Spoken triggers always win; the router only picks a mode for
+        // utterances without one.
+        let intent = if pasted_segments.is_empty() && !self.cli_mode {
+            self.route_intent(&remaining_transcription)
+        } else {
+            None
+        };
+
+        // CLI and template mode take over the whole utterance, ahead of
+        // voice commands and post-processing.
+        if pasted_segments.is_empty() {
+            let cli_request = if self.cli_mode {
+                Some(remaining_transcription.clone())
+            } else if self.settings.cli_mode_enabled {
+                cli_mode::strip_trigger(&remaining_transcription).or_else(|| {
+                    (intent == Some(Intent::Cli)).then(|| remaining_transcription.clone())
+                })
+            } else {
+                None
+            };
+            if let Some(spoken) = cli_request {
+                return Ok(PipelineState::CliRequested { spoken });
+            }
+        }
+        if self.settings.templates_enabled && pasted_segments.is_empty() {
+            let invocation =
+                templates::parse_invocation(&remaining_transcription, &self.settings.templates)
+                    .or_else(|| match intent {
+                        Some(Intent::Template) => templates::parse_untriggered(
+                            &remaining_transcription,
+                            &self.settings.templates,
+                        ),
+                        _ => None,
+                    });
+            if let Some(invocation) = invocation {
+                debug!("Template '{}' requested", invocation.template.id);
+                return Ok(PipelineState::TemplateRequested { invocation });
+            }
+        }
+        // An utterance that is only an editing command is not rewritten,
+        // so its key commands run.
+        if intent == Some(Intent::Command) {
+            self.post_process = false;
+        }
+
+        // Spoken editing commands. Post-processing rewrites the text around
+        // key commands, so there trailing ones are split off and sent after
+        // the final text is written. Key commands between typed spans must
+        // run in spoken order, so those utterances are not post-processed.
+        let voice_tokens = if self.settings.voice_commands_enabled {
+            voice_commands::parse(&remaining_transcription, &self.settings.voice_commands)
+        } else {
+            voice_commands::literal(&remaining_transcription)
+        };
+        let dictation = voice_commands::resolve(&voice_tokens, true);
+        let remaining_transcription = if self.post_process {
+            match split_for_post_process(&dictation, !pasted_segments.is_empty()) {
+                Some((text, keys)) => {
+                    self.pending_keys = keys;
+                    text
+                }
+                None => {
+                    self.post_process = false;
+                    voice_commands::typed_text(&dictation)
+                }
+            }
+        } else {
+            voice_commands::typed_text(&dictation)
+        };
 
         // Reconstruct full text from segments + remaining
         let transcription = if pasted_segments.is_empty() {
@@ -219,7 +390,7 @@ impl TranscriptionPipeline {
             transcription
         );
 
-        if transcription.is_empty() {
+        if transcription.is_empty() && dictation.is_empty() {
             return Ok(PipelineState::Done);
         }
 
@@ -254,25 +425,48 @@ impl TranscriptionPipeline {
             };
 
             // Single-write mode: do not paste raw text during post-process.
-            // We paste exactly once after processing completes.
+            // We paste exactly once after processing completes. When streaming,
+            // the raw text goes out now and the final text lands as a diff.
+            // Segments are already in the field, so then the rest of the raw
+            // text follows them and the final text is always a diff.
+            let raw_text_pasted = had_segments
+                || (self.settings.post_process_streaming
+                    && pipeline_stages::enabled_stage_ids(&self.settings.pipeline_stages)
+                        .iter()
+                        .any(|id| id == pipeline_stages::LLM_POST_PROCESS_STAGE_ID));
+            let unpasted = &raw_text[if had_segments { joined.len() } else { 0 }..];
+            if raw_text_pasted && !unpasted.is_empty() {
+                let ah = self.app.clone();
+                let text = unpasted.to_string();
+                self.app
+                    .run_on_main_thread(move || {
+                        if let Err(e) = utils::paste_raw(text, ah) {
+                            error!("Failed to paste raw text before streaming: {}", e);
+                        }
+                    })
+                    .unwrap_or_else(|e| {
+                        error!("Failed to run raw paste on main thread: {:?}", e);
+                    });
+            }
 
             utils::show_processing_overlay(&self.app);
 
             Ok(PipelineState::RawTextVisible {
                 raw_text,
                 had_segments,
-                raw_text_pasted: false,
+                raw_text_pasted,
             })
         } else {
             // No post-processing — paste final text with trailing space / auto-submit
             if pasted_segments.is_empty() {
                 // Simple case: single paste
                 let ah = self.app.clone();
-                let text = self.expand_at_refs_for_output(&transcription);
+                let actions = self.expand_dictation_for_output(dictation);
                 let paste_time = Instant::now();
+                let settings = self.settings.clone();
                 self.app
                     .run_on_main_thread(move || {
-                        match utils::paste(text, ah.clone()) {
+                        match paste_dictation(actions, &ah, &settings) {
                             Ok(()) => {
                                 debug!("Text pasted successfully in {:?}", paste_time.elapsed())
                             }
@@ -289,14 +483,18 @@ impl TranscriptionPipeline {
             } else {
                 // Segments already pasted live with trailing space via paste().
                 // Just paste the remaining if any, then finalize UI.
-                if !remaining_transcription.is_empty() {
+                if !dictation.is_empty() {
                     let ah = self.app.clone();
-                    let text = self.expand_at_refs_for_output(&remaining_transcription);
+                    let actions = self.expand_dictation_for_output(dictation);
+                    let settings = self.settings.clone();
                     self.app
                         .run_on_main_thread(move || {
-                            if let Err(e) = utils::paste(text, ah) {
+                            if let Err(e) = paste_dictation(actions, &ah, &settings) {
                                 error!("Failed to paste remaining transcription: {}", e);
                             }
+                            // Earlier segments were pasted separately, so the
+                            // record would only cover the tail.
+                            last_dictation::clear(&ah);
                         })
                         .unwrap_or_else(|e| {
                             error!("Failed to run paste on main thread: {:?}", e);
@@ -311,6 +509,8 @@ impl TranscriptionPipeline {
     }
 
     /// RawTextVisible → PostProcessed
+    ///
+    /// Runs the stage list from `AppSettings::pipeline_stages` over the raw text.
     async fn post_process_text(&mut self) -> PipelineState {
         let (raw_text, had_segments, raw_text_pasted) =
             match std::mem::replace(&mut self.state, PipelineState::Done) {
@@ -323,54 +523,31 @@ impl TranscriptionPipeline {
                 _ => unreachable!(),
             };
 
-        let mut final_text = raw_text.clone();
-        let mut post_processed_text: Option<String> = None;
-        let mut post_process_prompt: Option<String> = None;
-
-        // Chinese variant conversion
-        if let Some(converted) = maybe_convert_chinese_variant(&self.settings, &raw_text).await {
-            final_text = converted;
-        }
-
-        // LLM post-processing
-        info!(
-            "Starting LLM post-processing on text ({} chars, had_segments={}): '{}'",
-            final_text.len(),
+        let mut ctx = StageContext {
+            app: self.app.clone(),
+            settings: self.settings.clone(),
             had_segments,
-            &final_text[..final_text.len().min(100)]
-        );
-        let processed =
-            post_process_transcription(&self.app, &self.settings, &final_text, had_segments).await;
-        match &processed {
-            Some(text) => info!(
-                "LLM post-processing returned ({} chars): '{}'",
-                text.len(),
-                &text[..text.len().min(100)]
-            ),
-            None => error!(
-                "LLM post-processing returned None — check provider/model/prompt/API key config"
-            ),
-        }
+            post_process: self.post_process,
+            binding_id: self.binding_id.clone(),
+            style_preset: self.style_preset.clone(),
+            post_process_prompt: None,
+            post_process_attempts: Vec::new(),
+            snippet_expansions: self.segment_snippets.clone(),
+            cancelled: false,
+        };
+        let stages = pipeline_stages::stages_from_settings(&self.settings);
+        let run = pipeline_stages::run_stages(&stages, raw_text.clone(), &mut ctx).await;
 
-        if let Some(processed_text) = processed {
-            post_processed_text = Some(processed_text.clone());
-            final_text = processed_text;
-
-            if let Some(prompt_id) = &self.settings.post_process_selected_prompt_id {
-                if let Some(prompt) = self
-                    .settings
-                    .post_process_prompts
-                    .iter()
-                    .find(|p| &p.id == prompt_id)
-                {
-                    post_process_prompt = Some(prompt.prompt.clone());
-                }
-            }
-        } else if final_text != raw_text {
-            post_processed_text = Some(final_text.clone());
-        }
+        let post_process_prompt = ctx.post_process_prompt;
+        let post_process_attempts = ctx.post_process_attempts;
+        let post_processed_text = if run.history_text != raw_text || post_process_prompt.is_some() {
+            Some(run.history_text.clone())
+        } else {
+            None
+        };
+        self.stage_records = run.records;
 
-        // Save to history (pre-expansion text)
+        // Save to history (text from history-recording stages only, e.g. pre-expansion)
         let hm = Arc::clone(&self.app.state::<Arc<HistoryManager>>());
         let transcription_for_history = raw_text.clone();
         let samples_for_history = self.samples_for_history.clone();
@@ -381,6 +558,7 @@ impl TranscriptionPipeline {
                     transcription_for_history,
                     post_processed_text,
                     post_process_prompt,
+                    post_process_attempts,
                 )
                 .await
             {
@@ -388,15 +566,126 @@ impl TranscriptionPipeline {
             }
         });
 
-        final_text = self.expand_at_refs_for_output(&final_text);
+        if ctx.cancelled {
+            // cancel_current_operation already reset the overlay and tray;
+            // leave whatever is on screen untouched.
+            info!("Post-processing cancelled; skipping output");
+            return PipelineState::Done;
+        }
 
         PipelineState::PostProcessed {
             raw_text,
-            final_text,
+            final_text: run.text,
             raw_text_pasted,
         }
     }
 
+    /// CliRequested → Done
+    ///
+    /// Builds one command line and pastes it without a trailing space.
+    /// Auto-submit is only sent when the command passes the deny list.
+    async fn build_and_insert_command(&mut self) -> PipelineState {
+        let spoken = match std::mem::replace(&mut self.state, PipelineState::Done) {
+            PipelineState::CliRequested { spoken } => spoken,
+            _ => unreachable!(),
+        };
+
+        let translation = cli_mode::translate(&spoken);
+        let mut command = translation.command;
+        if self.settings.cli_mode_llm_assist && !translation.matched_rule {
+            utils::show_processing_overlay(&self.app);
+            if let Some(suggested) = complete_with_llm(
+                &self.settings,
+                cli_mode::LLM_SYSTEM_MESSAGE.to_string(),
+                spoken.clone(),
+                cli_mode::command_from_llm,
+            )
+            .await
+            {
+                command = suggested;
+            }
+        }
+        if command.is_empty() {
+            self.cleanup();
+            return PipelineState::Done;
+        }
+
+        let safety = cli_mode::classify(&command, &self.settings.cli_mode_deny_patterns);
+        info!("CLI command '{}' classified as {:?}", command, safety);
+
+        let ah = self.app.clone();
+        self.app
+            .run_on_main_thread(move || {
+                match utils::paste_raw(command, ah.clone()) {
+                    Ok(()) => match safety {
+                        CliSafety::Safe => {
+                            if let Err(e) = utils::send_auto_submit(&ah) {
+                                error!("Failed to auto-submit CLI command: {}", e);
+                            }
+                        }
+                        CliSafety::Denied(reason) => {
+                            warn!("Not auto-submitting CLI command: matched {}", reason);
+                        }
+                    },
+                    Err(e) => error!("Failed to paste CLI command: {}", e),
+                }
+                utils::hide_recording_overlay(&ah);
+                change_tray_icon(&ah, TrayIconState::Idle);
+            })
+            .unwrap_or_else(|e| {
+                error!("Failed to run CLI paste on main thread: {:?}", e);
+                utils::hide_recording_overlay(&self.app);
+                change_tray_icon(&self.app, TrayIconState::Idle);
+            });
+
+        PipelineState::Done
+    }
+
+    /// TemplateRequested → Done
+    ///
+    /// Fills the template from the rest of the utterance and pastes it as-is,
+    /// without trailing space or auto-submit, so unfilled placeholders can
+    /// still be completed with `template_next_placeholder`.
+    async fn fill_and_insert_template(&mut self) -> PipelineState {
+        let invocation = match std::mem::replace(&mut self.state, PipelineState::Done) {
+            PipelineState::TemplateRequested { invocation } => invocation,
+            _ => unreachable!(),
+        };
+
+        let names = templates::placeholders(&invocation.template.body);
+        let mut values = templates::fill_from_speech(&names, &invocation.spoken);
+        if self.settings.template_llm_fill
+            && !names.is_empty()
+            && !invocation.spoken.trim().is_empty()
+        {
+            utils::show_processing_overlay(&self.app);
+            if let Some(filled) = fill_template_with_llm(&self.settings, &invocation).await {
+                values = filled;
+            }
+        }
+        let rendered = templates::render(&invocation.template.body, &values);
+
+        let ah = self.app.clone();
+        let pasted = self.settings.paste_method != PasteMethod::None;
+        self.app
+            .run_on_main_thread(move || {
+                match utils::paste_raw(rendered.clone(), ah.clone()) {
+                    Ok(()) if pasted => templates::record_insertion(&ah, &rendered),
+                    Ok(()) => {}
+                    Err(e) => error!("Failed to paste template: {}", e),
+                }
+                utils::hide_recording_overlay(&ah);
+                change_tray_icon(&ah, TrayIconState::Idle);
+            })
+            .unwrap_or_else(|e| {
+                error!("Failed to run template paste on main thread: {:?}", e);
+                utils::hide_recording_overlay(&self.app);
+                change_tray_icon(&self.app, TrayIconState::Idle);
+            });
+
+        PipelineState::Done
+    }
+
     /// PostProcessed → Done
     fn apply_diff_and_finalize(&mut self) -> PipelineState {
         let (raw_text, final_text, raw_text_pasted) =
@@ -413,19 +702,20 @@ impl TranscriptionPipeline {
         info!(
             "Comparing for diff — original ({} chars): '{}' vs final ({} chars): '{}'",
             raw_text.len(),
-            &raw_text[..raw_text.len().min(80)],
+            raw_text.chars().take(80).collect::<String>(),
             final_text.len(),
-            &final_text[..final_text.len().min(80)],
+            final_text.chars().take(80).collect::<String>(),
         );
 
         if !raw_text_pasted {
             let ah = self.app.clone();
             let text = final_text.clone();
             let settings = self.settings.clone();
+            let keys = std::mem::take(&mut self.pending_keys);
             self.app
                 .run_on_main_thread(move || {
                     if !text.is_empty() {
-                        if let Err(e) = utils::paste_raw(text, ah.clone()) {
+                        if let Err(e) = utils::paste_raw(text.clone(), ah.clone()) {
                             error!("Failed to paste finalized post-processed text: {}", e);
                         } else {
                             debug!(
@@ -434,7 +724,7 @@ impl TranscriptionPipeline {
                             );
                         }
                     }
-                    apply_trailing_space_and_autosubmit(&ah, &settings);
+                    finish_post_processed_paste(&ah, &settings, &keys, &text);
                     utils::hide_recording_overlay(&ah);
                     change_tray_icon(&ah, TrayIconState::Idle);
                 })
@@ -445,28 +735,30 @@ impl TranscriptionPipeline {
                 });
         } else if let Some(diff) = compute_text_diff(&raw_text, &final_text) {
             debug!(
-                "Applying diff: delete {} chars, insert {} chars, suffix {} chars",
-                diff.delete_chars,
-                diff.insert.len(),
-                diff.suffix_chars,
+                "Applying diff: {} edit(s), restore {} chars",
+                diff.edits.len(),
+                diff.restore_chars,
             );
-            let diff_insert = diff.insert.clone();
-            let diff_suffix = diff.suffix_chars;
-            let diff_delete = diff.delete_chars;
             let ah = self.app.clone();
             let settings = self.settings.clone();
+            let keys = std::mem::take(&mut self.pending_keys);
             self.app
                 .run_on_main_thread(move || {
-                    match utils::apply_text_diff(diff_suffix, diff_delete, &diff_insert, ah.clone())
-                    {
-                        Ok(()) => debug!(
-                            "Text diff applied successfully in {:?}",
-                            paste_time.elapsed()
-                        ),
-                        Err(e) => error!("Failed to apply text diff: {}", e),
+                    match utils::apply_text_diff(&diff.edits, diff.restore_chars, ah.clone()) {
+                        Ok(()) => {
+                            debug!(
+                                "Text diff applied successfully in {:?}",
+                                paste_time.elapsed()
+                            );
+                            finish_post_processed_paste(&ah, &settings, &keys, &final_text);
+                        }
+                        Err(e) => {
+                            error!("Failed to apply text diff: {}", e);
+                            // The field now holds an unknown mix of raw and
+                            // final text, so there is nothing exact to undo.
+                            apply_trailing_space_and_autosubmit(&ah, &settings);
+                        }
                     }
-                    // Apply trailing space and auto-submit after diff
-                    apply_trailing_space_and_autosubmit(&ah, &settings);
                     utils::hide_recording_overlay(&ah);
                     change_tray_icon(&ah, TrayIconState::Idle);
                 })
@@ -480,9 +772,10 @@ impl TranscriptionPipeline {
             info!("Text unchanged after processing (diff is None), no replacement needed");
             let ah = self.app.clone();
             let settings = self.settings.clone();
+            let keys = std::mem::take(&mut self.pending_keys);
             self.app
                 .run_on_main_thread(move || {
-                    apply_trailing_space_and_autosubmit(&ah, &settings);
+                    finish_post_processed_paste(&ah, &settings, &keys, &final_text);
                     utils::hide_recording_overlay(&ah);
                     change_tray_icon(&ah, TrayIconState::Idle);
                 })
@@ -503,18 +796,56 @@ impl TranscriptionPipeline {
         PipelineState::Done
     }
 
-    fn expand_at_refs_for_output(&self, text: &str) -> String {
-        if !self.settings.at_file_expansion_enabled {
-            return text.to_string();
-        }
+    /// What the intent router makes of `text`, if it is confident.
+    fn route_intent(&self, text: &str) -> Option<Intent> {
+        let router = self.app.try_state::<Arc<IntentRouterManager>>()?;
+        router
+            .route_with_timeout(
+                &self.settings,
+                &DomainContext {
+                    text: text.to_string(),
+                    app: None,
+                },
+            )
+            .map(|decision| decision.intent)
+    }
 
-        let expanded =
-            crate::at_file_expansion::maybe_expand_at_refs(text, &self.settings, &self.app);
-        if expanded != text {
-            let delta = expanded.len() as isize - text.len() as isize;
-            debug!("@file expansion adjusted output by {} chars", delta);
-        }
-        expanded
+    fn resolve_style_preset(&self) -> Option<StylePreset> {
+        resolve_style_preset(&self.settings, &self.binding_id)
+    }
+
+    /// Apply the style preset, then expand snippets and @file references in
+    /// typed text. The paths without post-processing do not run the stage list.
+    fn expand_dictation_for_output(&self, actions: Vec<DictationAction>) -> Vec<DictationAction> {
+        actions
+            .into_iter()
+            .map(|action| match action {
+                DictationAction::Type(text) => {
+                    let text = match &self.style_preset {
+                        Some(preset) => style_presets::apply_rules(&text, preset),
+                        None => text,
+                    };
+                    let text = if self.settings.snippets_enabled {
+                        let expansion = snippets::expand(&text, &self.settings.snippets, |name| {
+                            snippets::builtin_variable(&self.app, name)
+                        });
+                        if expansion.inserted.is_empty() {
+                            text
+                        } else {
+                            expansion.text
+                        }
+                    } else {
+                        text
+                    };
+                    DictationAction::Type(pipeline_stages::expand_at_refs_for_output(
+                        &self.app,
+                        &self.settings,
+                        &text,
+                    ))
+                }
+                key => key,
+            })
+            .collect()
     }
 
     /// Cleanup on error — hide overlay, reset tray.
@@ -524,6 +855,77 @@ impl TranscriptionPipeline {
     }
 }
 
+/// The text to post-process and the key commands to send after it, or `None`
+/// when the dictation should be written out as spoken instead: when a key
+/// command comes before typed text, or when only key commands were spoken
+/// and there is nothing to rewrite.
+fn split_for_post_process(
+    dictation: &[DictationAction],
+    had_segments: bool,
+) -> Option<(String, Vec<KeyAction>)> {
+    voice_commands::split_trailing_key_actions(dictation)
+        .filter(|(text, _)| !text.is_empty() || had_segments)
+}
+
+/// Write out dictation with voice commands resolved. The last typed text goes
+/// through `paste` for trailing space, auto-submit and clipboard handling;
+/// when the dictation ends in a key command those are skipped so they do not
+/// act on whatever the command just did.
+///
+/// Only plain typed dictation is recorded for `undo_last_dictation`; once a
+/// key command has run, deleting characters would not restore the field.
+fn paste_dictation(
+    actions: Vec<DictationAction>,
+    app: &AppHandle,
+    settings: &AppSettings,
+) -> Result<(), String> {
+    let mut typed = String::new();
+    let mut undoable = true;
+    let last = actions.len().saturating_sub(1);
+    for (index, action) in actions.into_iter().enumerate() {
+        match action {
+            DictationAction::Type(text) => {
+                typed.push_str(&text);
+                if index == last {
+                    utils::paste(text, app.clone())?
+                } else {
+                    utils::paste_raw(text, app.clone())?
+                }
+            }
+            DictationAction::Key(key) => {
+                undoable = false;
+                voice_commands::perform_key_action(app, key)?
+            }
+        }
+    }
+    if undoable {
+        last_dictation::record(app, settings, &typed);
+    }
+    Ok(())
+}
+
+/// Finish writing post-processed text: send the key commands split off
+/// before post-processing, or else add the trailing space and auto-submit
+/// and record the text for `undo_last_dictation`. As in `paste_dictation`,
+/// neither follows a key command.
+fn finish_post_processed_paste(
+    app: &AppHandle,
+    settings: &AppSettings,
+    keys: &[KeyAction],
+    text: &str,
+) {
+    if keys.is_empty() {
+        apply_trailing_space_and_autosubmit(app, settings);
+        last_dictation::record(app, settings, text);
+        return;
+    }
+    for key in keys {
+        if let Err(e) = voice_commands::perform_key_action(app, *key) {
+            error!("Failed to send key command after post-processing: {}", e);
+        }
+    }
+}
+
 // ============================================================================
 // Trailing space & auto-submit helper
 // ============================================================================
@@ -533,7 +935,7 @@ impl TranscriptionPipeline {
 fn apply_trailing_space_and_autosubmit(app: &AppHandle, settings: &AppSettings) {
     use crate::clipboard::paste_raw;
     use crate::input::EnigoState;
-    use crate::settings::{AutoSubmitKey, PasteMethod};
+    use crate::settings::AutoSubmitKey;
     use enigo::{Direction, Key, Keyboard};
 
     if settings.append_trailing_space {
@@ -593,8 +995,16 @@ fn apply_trailing_space_and_autosubmit(app: &AppHandle, settings: &AppSettings)
 // Minimal diff for post-processing replacement
 // ============================================================================
 
-/// Describes the minimal edit needed to transform the original pasted text into
-/// the post-processed text.
+/// Above this many separate edits, keyboard navigation between them costs more
+/// (and risks more) than retyping one region covering all of them.
+const MAX_DIFF_EDITS: usize = 8;
+
+/// Unchanged runs shorter than this are retyped rather than navigated over, so
+/// nearby word changes collapse into a single edit.
+const MIN_DIFF_GAP_CHARS: usize = 4;
+
+/// Describes the edits needed to transform the original pasted text into the
+/// post-processed text.
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct TextDiff {
     /// Number of characters from the common suffix that must be backspaced over.
@@ -603,12 +1013,27 @@ pub(crate) struct TextDiff {
     pub delete_chars: usize,
     /// The replacement text to insert in place of the deleted region.
     pub insert: String,
+    /// Edits to apply right-to-left, starting with the cursor at the end of the
+    /// original text. A single edit covering the region above when the
+    /// word-level diff would need more than `MAX_DIFF_EDITS`.
+    pub edits: Vec<TextEdit>,
+    /// Characters to move right after the last edit to return to the end.
+    pub restore_chars: usize,
 }
 
-/// Compute the minimal diff between the original text (already pasted and visible)
-/// and the new post-processed text. Finds the common prefix and suffix, then treats
-/// everything in between as a single replacement region suitable for keyboard-based
-/// apply (backspace over suffix + changed region, type replacement + suffix).
+/// A changed region in char offsets: `original[start..end]` becomes `insert`.
+struct DiffHunk {
+    start: usize,
+    end: usize,
+    insert: String,
+}
+
+/// Compute the diff between the original text (already pasted and visible)
+/// and the new post-processed text. The texts are compared word by word, and
+/// each changed run becomes its own edit so a fix at the start and one at the
+/// end do not retype everything in between. The common prefix and suffix are
+/// also recorded as a single replacement region, used when there are too many
+/// edits to be worth navigating.
 ///
 /// Returns `None` if the texts are identical.
 pub(crate) fn compute_text_diff(original: &str, processed: &str) -> Option<TextDiff> {
@@ -641,21 +1066,127 @@ pub(crate) fn compute_text_diff(original: &str, processed: &str) -> Option<TextD
         .iter()
         .collect();
 
+    let hunks = word_diff_hunks(original, processed);
+    let (edits, restore_chars) = if hunks.len() <= MAX_DIFF_EDITS {
+        edits_from_hunks(&hunks, orig_chars.len(), proc_chars.len())
+    } else {
+        let edit = TextEdit {
+            cursor_left: suffix_len,
+            delete_chars,
+            insert: insert.clone(),
+        };
+        (vec![edit], suffix_len)
+    };
+
     debug!(
-        "Text diff: prefix={}, delete={}, insert={} chars, suffix={}",
+        "Text diff: prefix={}, delete={}, insert={} chars, suffix={}, edits={}",
         prefix_len,
         delete_chars,
         insert.len(),
         suffix_len,
+        edits.len(),
     );
 
     Some(TextDiff {
         suffix_chars: suffix_len,
         delete_chars,
         insert,
+        edits,
+        restore_chars,
     })
 }
 
+/// Word-level changed regions between the two texts, in forward order.
+fn word_diff_hunks(original: &str, processed: &str) -> Vec<DiffHunk> {
+    let diff = similar::TextDiff::configure()
+        .algorithm(similar::Algorithm::Patience)
+        .diff_words(original, processed);
+    let old_tokens = diff.old_slices();
+    let new_tokens = diff.new_slices();
+
+    // Char offset where each token starts, plus one trailing entry for the end.
+    let offsets = |tokens: &[&str]| -> Vec<usize> {
+        std::iter::once(0)
+            .chain(tokens.iter().scan(0, |pos, token| {
+                *pos += token.chars().count();
+                Some(*pos)
+            }))
+            .collect()
+    };
+    let old_offsets = offsets(old_tokens);
+
+    let mut hunks: Vec<DiffHunk> = Vec::new();
+    for op in diff.ops() {
+        if op.tag() == similar::DiffTag::Equal {
+            continue;
+        }
+        let start = old_offsets[op.old_range().start];
+        let end = old_offsets[op.old_range().end];
+        let insert: String = new_tokens[op.new_range()].concat();
+
+        match hunks.last_mut() {
+            Some(last) if start - last.end < MIN_DIFF_GAP_CHARS => {
+                let gap: String = original
+                    .chars()
+                    .skip(last.end)
+                    .take(start - last.end)
+                    .collect();
+                last.insert.push_str(&gap);
+                last.insert.push_str(&insert);
+                last.end = end;
+            }
+            _ => hunks.push(DiffHunk { start, end, insert }),
+        }
+    }
+
+    // Word tokens differ as a whole; keep only the characters that changed.
+    let orig_chars: Vec<char> = original.chars().collect();
+    for hunk in &mut hunks {
+        let old = &orig_chars[hunk.start..hunk.end];
+        let new: Vec<char> = hunk.insert.chars().collect();
+        let prefix = old
+            .iter()
+            .zip(new.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let max_suffix = old.len().min(new.len()) - prefix;
+        let suffix = old
+            .iter()
+            .rev()
+            .zip(new.iter().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        hunk.start += prefix;
+        hunk.end -= suffix;
+        hunk.insert = new[prefix..new.len() - suffix].iter().collect();
+    }
+    hunks.retain(|hunk| hunk.start < hunk.end || !hunk.insert.is_empty());
+    hunks
+}
+
+/// Turn forward-ordered hunks into right-to-left cursor edits, returning the
+/// edits and the distance back to the end of the text afterwards.
+fn edits_from_hunks(
+    hunks: &[DiffHunk],
+    orig_len: usize,
+    proc_len: usize,
+) -> (Vec<TextEdit>, usize) {
+    // Edits run from the end of the text backwards, so the char index of
+    // everything left of the current edit is unaffected by earlier edits.
+    let mut cursor = orig_len;
+    let mut edits = Vec::with_capacity(hunks.len());
+    for hunk in hunks.iter().rev() {
+        edits.push(TextEdit {
+            cursor_left: cursor - hunk.end,
+            delete_chars: hunk.end - hunk.start,
+            insert: hunk.insert.clone(),
+        });
+        cursor = hunk.start + hunk.insert.chars().count();
+    }
+    (edits, proc_len - cursor)
+}
+
 // ============================================================================
 // Post-processing helpers (moved from actions.rs)
 // ============================================================================
@@ -696,34 +1227,22 @@ fn strip_leaked_prompt_instructions(text: &str) -> String {
     no_segments.trim().to_string()
 }
 
-async fn post_process_transcription(
+/// Prompt and system message shared by every provider attempt.
+struct PostProcessRequest {
+    prompt: String,
+    system_message: Option<String>,
+    /// The prompt reshapes the text, so only protected spans are guarded.
+    restructures: bool,
+}
+
+fn build_post_process_request(
     app: &AppHandle,
     settings: &AppSettings,
     transcription: &str,
     had_segments: bool,
-) -> Option<String> {
-    let provider = match settings.active_post_process_provider().cloned() {
-        Some(provider) => provider,
-        None => {
-            debug!("Post-processing enabled but no provider is selected");
-            return None;
-        }
-    };
-
-    let model = settings
-        .post_process_models
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
-
-    if model.trim().is_empty() {
-        debug!(
-            "Post-processing skipped because provider '{}' has no model configured",
-            provider.id
-        );
-        return None;
-    }
-
+    binding_id: &str,
+    style_instructions: Option<&str>,
+) -> Option<PostProcessRequest> {
     let selected_prompt_id = match select_post_process_prompt_id(app, settings, transcription) {
         Some(id) => id.clone(),
         None => {
@@ -732,12 +1251,12 @@ async fn post_process_transcription(
         }
     };
 
-    let prompt = match settings
+    let (prompt, restructures) = match settings
         .post_process_prompts
         .iter()
         .find(|prompt| prompt.id == selected_prompt_id)
     {
-        Some(prompt) => prompt.prompt.clone(),
+        Some(prompt) => (prompt.prompt.clone(), prompt.restructures),
         None => {
             debug!(
                 "Post-processing skipped because prompt '{}' was not found",
@@ -752,11 +1271,6 @@ async fn post_process_transcription(
         return None;
     }
 
-    debug!(
-        "Starting LLM post-processing with provider '{}' (model: {})",
-        provider.id, model
-    );
-
     let mut processed_prompt = prompt.replace("${output}", transcription);
 
     // Build system message with global dictation safety rules plus optional segment context.
@@ -776,20 +1290,20 @@ async fn post_process_transcription(
     let system_message = Some(system_parts.join("\n\n"));
 
     // Inject jargon context if active
-    if !settings.jargon_enabled_profiles.is_empty()
-        || !settings.jargon_custom_terms.is_empty()
-        || !settings.jargon_packs.is_empty()
-    {
-        let profiles = build_profiles_map(settings);
+    let scope = JargonScope {
+        binding_id: Some(binding_id.to_string()),
+        prompt_id: Some(selected_prompt_id.clone()),
+    };
+    if jargon_in_use(settings, &scope) {
         let effective_profiles = effective_profiles_for_text(app, settings, transcription);
-        let jargon_settings = crate::jargon::JargonSettings {
-            enabled_profiles: effective_profiles,
-            custom_terms: settings.jargon_custom_terms.clone(),
-            custom_corrections: settings.jargon_custom_corrections.clone(),
-        };
-        let dict = crate::jargon::compute_active_dictionary(&jargon_settings, &profiles);
-        if !dict.terms.is_empty() {
-            let terms_str: Vec<&str> = dict.terms.iter().map(|s| s.as_str()).collect();
+        let compiled = active_dictionary(app, settings, effective_profiles, &scope);
+        if !compiled.dictionary.terms.is_empty() {
+            let terms_str: Vec<&str> = compiled
+                .dictionary
+                .terms
+                .iter()
+                .map(|s| s.as_str())
+                .collect();
             processed_prompt = format!(
                 "{}\n\nIMPORTANT: Use these exact spellings for technical terms: {}",
                 processed_prompt,
@@ -798,6 +1312,11 @@ async fn post_process_transcription(
         }
     }
 
+    if let Some(instructions) = style_instructions {
+        processed_prompt.push_str("\n\n");
+        processed_prompt.push_str(instructions);
+    }
+
     if settings.at_file_expansion_enabled {
         processed_prompt.push_str(
             "\n\nIMPORTANT: Preserve any @file-style references exactly (for example @main.rs or @\"my file.ts\"). Do not expand, remove, or rewrite these references.",
@@ -808,23 +1327,266 @@ async fn post_process_transcription(
         "Processed prompt (had_segments={}, {} chars): '{}'",
         had_segments,
         processed_prompt.len(),
-        &processed_prompt[..processed_prompt.len().min(500)]
+        processed_prompt.chars().take(500).collect::<String>()
+    );
+
+    Some(PostProcessRequest {
+        prompt: processed_prompt,
+        system_message,
+        restructures,
+    })
+}
+
+/// Remove zero-width characters and any prompt instructions the model echoed back.
+fn sanitize_llm_output(content: &str) -> String {
+    let content = content
+        .replace('\u{200B}', "")
+        .replace('\u{200C}', "")
+        .replace('\u{200D}', "")
+        .replace('\u{FEFF}', "");
+    strip_leaked_prompt_instructions(&content)
+}
+
+/// Outcome of post-processing across the active provider and its fallbacks.
+#[derive(Default)]
+pub(crate) struct PostProcessResult {
+    /// Output of the first attempt that succeeded.
+    pub text: Option<String>,
+    /// Every attempt made, in order, for the history entry.
+    pub attempts: Vec<PostProcessAttempt>,
+    /// The user cancelled while an attempt was in flight.
+    pub cancelled: bool,
+}
+
+enum AttemptOutcome {
+    Succeeded(String),
+    Failed(String),
+    Cancelled,
+}
+
+/// Run the LLM post-processing prompt against the active provider, then each
+/// configured fallback, until one succeeds. Every attempt is bounded by
+/// `post_process_timeout_ms`, and with `post_process_divergence_guard` set,
+/// output that strays too far from the transcription counts as a failure.
+/// For a prompt that restructures the text, only dropped protected spans do.
+/// When all attempts fail the caller keeps the raw transcription. With
+/// `stream` set, partial output is forwarded to the overlay as it arrives.
+pub(crate) async fn post_process_transcription(
+    app: &AppHandle,
+    settings: &AppSettings,
+    transcription: &str,
+    had_segments: bool,
+    stream: bool,
+    binding_id: &str,
+    style_instructions: Option<String>,
+) -> PostProcessResult {
+    let mut result = PostProcessResult::default();
+
+    let targets = settings.post_process_attempt_targets();
+    if targets.is_empty() {
+        debug!("Post-processing enabled but no provider with a configured model is available");
+        return result;
+    }
+
+    // The local cleanup model carries its own instruction, so it can run
+    // without a selected prompt; every other provider needs one.
+    let request = build_post_process_request(
+        app,
+        settings,
+        transcription,
+        had_segments,
+        binding_id,
+        style_instructions.as_deref(),
     );
+    if request.is_none()
+        && targets
+            .iter()
+            .all(|(provider, _)| provider.id != LOCAL_CLEANUP_PROVIDER_ID)
+    {
+        return result;
+    }
+
+    let timeout = Duration::from_millis(settings.post_process_timeout_ms);
+    for (provider, model) in targets {
+        let is_local_cleanup = provider.id == LOCAL_CLEANUP_PROVIDER_ID;
+        if !is_local_cleanup && request.is_none() {
+            continue;
+        }
+        debug!(
+            "Starting LLM post-processing with provider '{}' (model: {}, timeout: {}ms)",
+            provider.id,
+            model,
+            timeout.as_millis()
+        );
+        if stream && !result.attempts.is_empty() {
+            // Clear partial output left behind by the attempt that failed.
+            utils::emit_post_process_partial(app, "");
+        }
+
+        let started = Instant::now();
+        let outcome = match request.as_ref() {
+            Some(request) if !is_local_cleanup => {
+                if stream && provider.id != APPLE_INTELLIGENCE_PROVIDER_ID {
+                    stream_attempt(app, settings, provider, &model, request, timeout).await
+                } else {
+                    request_attempt(settings, provider, &model, request, timeout).await
+                }
+            }
+            _ => local_cleanup_attempt(app, transcription, timeout).await,
+        };
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+
+        let mut attempt = PostProcessAttempt {
+            provider_id: provider.id.clone(),
+            model,
+            succeeded: false,
+            elapsed_ms,
+            error: None,
+        };
+        let outcome = match outcome {
+            AttemptOutcome::Succeeded(content) if settings.post_process_divergence_guard => {
+                let check: fn(&str, &str) -> Result<(), String> = match request.as_ref() {
+                    Some(request) if request.restructures && !is_local_cleanup => {
+                        crate::cleanup::check_protected_spans
+                    }
+                    _ => crate::cleanup::check_output_divergence,
+                };
+                match check(transcription, &content) {
+                    Ok(()) => AttemptOutcome::Succeeded(content),
+                    Err(reason) => AttemptOutcome::Failed(format!(
+                        "output rejected by divergence guard: {}",
+                        reason
+                    )),
+                }
+            }
+            outcome => outcome,
+        };
+        match outcome {
+            AttemptOutcome::Succeeded(content) => {
+                debug!(
+                    "LLM post-processing succeeded for provider '{}' in {}ms. Output length: {} chars",
+                    provider.id,
+                    elapsed_ms,
+                    content.len()
+                );
+                attempt.succeeded = true;
+                result.attempts.push(attempt);
+                result.text = Some(content);
+                return result;
+            }
+            AttemptOutcome::Failed(err) => {
+                error!(
+                    "LLM post-processing failed for provider '{}' (model: {}) after {}ms: {}",
+                    provider.id, attempt.model, elapsed_ms, err
+                );
+                attempt.error = Some(err);
+                result.attempts.push(attempt);
+            }
+            AttemptOutcome::Cancelled => {
+                info!("LLM post-processing was cancelled");
+                attempt.error = Some("cancelled by user".to_string());
+                result.attempts.push(attempt);
+                result.cancelled = true;
+                return result;
+            }
+        }
+    }
 
+    error!(
+        "All {} post-processing attempt(s) failed. Falling back to original transcription.",
+        result.attempts.len()
+    );
+    result
+}
+
+/// Ask the post-processing providers, in fallback order, to fill the
+/// template's placeholders. On-device providers are skipped because they are
+/// not asked for structured output elsewhere either. Returns `None` when no
+/// provider answered with usable values.
+async fn fill_template_with_llm(
+    settings: &AppSettings,
+    invocation: &TemplateInvocation,
+) -> Option<std::collections::HashMap<String, String>> {
+    let names = templates::placeholders(&invocation.template.body);
+    let (system_message, prompt) =
+        templates::llm_fill_request(&invocation.template, &invocation.spoken);
+    complete_with_llm(settings, system_message, prompt, |content| {
+        let values = templates::parse_llm_values(content, &names);
+        if values.is_none() {
+            warn!("Template fill reply was not a JSON object");
+        }
+        values
+    })
+    .await
+}
+
+/// Send one chat completion through the post-processing targets in order,
+/// skipping providers that cannot take a custom system message. Each
+/// sanitized reply goes through `parse`; a reply it rejects falls through to
+/// the next target like a failed request. Returns the first parsed value.
+async fn complete_with_llm<T>(
+    settings: &AppSettings,
+    system_message: String,
+    prompt: String,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    let timeout = Duration::from_millis(settings.post_process_timeout_ms);
+
+    for (provider, model) in settings.post_process_attempt_targets() {
+        if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID || provider.id == LOCAL_CLEANUP_PROVIDER_ID
+        {
+            continue;
+        }
+        let api_key = settings
+            .post_process_api_keys
+            .get(&provider.id)
+            .cloned()
+            .unwrap_or_default();
+        match crate::llm_client::send_chat_completion(
+            provider,
+            api_key,
+            &model,
+            prompt.clone(),
+            Some(system_message.clone()),
+            Some(timeout),
+        )
+        .await
+        {
+            Ok(Some(content)) => match parse(&sanitize_llm_output(&content)) {
+                Some(value) => return Some(value),
+                None => warn!("LLM reply from provider '{}' was not usable", provider.id),
+            },
+            Ok(None) => warn!("LLM request to provider '{}' had no content", provider.id),
+            Err(e) => warn!("LLM request to provider '{}' failed: {}", provider.id, e),
+        }
+    }
+    None
+}
+
+async fn request_attempt(
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    request: &PostProcessRequest,
+    timeout: Duration,
+) -> AttemptOutcome {
     if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
+        // On-device generation is synchronous and never stalls on the network,
+        // so the per-attempt deadline does not apply here.
         #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
         {
             if !apple_intelligence::check_apple_intelligence_availability() {
-                debug!("Apple Intelligence selected but not currently available on this device");
-                return None;
+                return AttemptOutcome::Failed(
+                    "Apple Intelligence is not currently available on this device".to_string(),
+                );
             }
 
             // Apple Intelligence has no system message support, so prepend
             // segment context directly into the prompt as a preamble.
-            let ai_prompt = if let Some(ref sys) = system_message {
-                format!("[System instruction: {}]\n\n{}", sys, processed_prompt)
+            let ai_prompt = if let Some(ref sys) = request.system_message {
+                format!("[System instruction: {}]\n\n{}", sys, request.prompt)
             } else {
-                processed_prompt.clone()
+                request.prompt.clone()
             };
 
             let token_limit = model.trim().parse::<i32>().unwrap_or(0);
@@ -832,27 +1594,22 @@ async fn post_process_transcription(
                 Ok(result) => {
                     let sanitized = strip_leaked_prompt_instructions(&result);
                     if sanitized.trim().is_empty() {
-                        debug!("Apple Intelligence returned an empty response");
-                        None
+                        AttemptOutcome::Failed(
+                            "Apple Intelligence returned an empty response".to_string(),
+                        )
                     } else {
-                        debug!(
-                            "Apple Intelligence post-processing succeeded. Output length: {} chars",
-                            sanitized.len()
-                        );
-                        Some(sanitized)
+                        AttemptOutcome::Succeeded(sanitized)
                     }
                 }
-                Err(err) => {
-                    error!("Apple Intelligence post-processing failed: {}", err);
-                    None
-                }
+                Err(err) => AttemptOutcome::Failed(format!("Apple Intelligence failed: {}", err)),
             };
         }
 
         #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
         {
-            debug!("Apple Intelligence provider selected on unsupported platform");
-            return None;
+            return AttemptOutcome::Failed(
+                "Apple Intelligence is not supported on this platform".to_string(),
+            );
         }
     }
 
@@ -863,41 +1620,114 @@ async fn post_process_transcription(
         .unwrap_or_default();
 
     match crate::llm_client::send_chat_completion(
-        &provider,
+        provider,
         api_key,
-        &model,
-        processed_prompt,
-        system_message,
+        model,
+        request.prompt.clone(),
+        request.system_message.clone(),
+        Some(timeout),
     )
     .await
     {
         Ok(Some(content)) => {
-            let content = content
-                .replace('\u{200B}', "")
-                .replace('\u{200C}', "")
-                .replace('\u{200D}', "")
-                .replace('\u{FEFF}', "");
-            let content = strip_leaked_prompt_instructions(&content);
-            debug!(
-                "LLM post-processing succeeded for provider '{}'. Output length: {} chars",
-                provider.id,
-                content.len()
-            );
-            Some(content)
+            let content = sanitize_llm_output(&content);
+            if content.trim().is_empty() {
+                AttemptOutcome::Failed("LLM API response was empty".to_string())
+            } else {
+                AttemptOutcome::Succeeded(content)
+            }
         }
-        Ok(None) => {
-            error!("LLM API response has no content");
-            None
+        Ok(None) => AttemptOutcome::Failed("LLM API response has no content".to_string()),
+        Err(e) => AttemptOutcome::Failed(e),
+    }
+}
+
+/// Run the on-device flan-t5 cleanup model over the transcription. Protected
+/// spans are masked and the output validated inside `CleanupManager`, which
+/// returns the input unchanged when its guardrails reject a rewrite. Loading
+/// and decoding a long text can be slow, so the attempt deadline applies; a
+/// model that is not downloaded yet fails the attempt instead of waiting.
+async fn local_cleanup_attempt(
+    app: &AppHandle,
+    transcription: &str,
+    timeout: Duration,
+) -> AttemptOutcome {
+    let manager = match app.try_state::<Arc<CleanupManager>>() {
+        Some(manager) => manager.inner().clone(),
+        None => {
+            return AttemptOutcome::Failed("Local cleanup model is not initialized".to_string())
         }
-        Err(e) => {
-            error!(
-                "LLM post-processing failed for provider '{}': {}. Falling back to original transcription.",
-                provider.id,
-                e
-            );
-            None
+    };
+
+    match tokio::time::timeout(timeout, manager.cleanup(transcription)).await {
+        Ok(Ok(cleaned)) => AttemptOutcome::Succeeded(cleaned),
+        Ok(Err(e)) => AttemptOutcome::Failed(format!("{} failed: {:#}", manager.name(), e)),
+        Err(_) => AttemptOutcome::Failed(format!(
+            "{} timed out after {} ms",
+            manager.name(),
+            timeout.as_millis()
+        )),
+    }
+}
+
+/// Minimum gap between partial-output updates sent to the overlay.
+const PARTIAL_OUTPUT_EMIT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Streaming variant of [`request_attempt`]. Partial output is forwarded to
+/// the overlay as it arrives; the sanitized full text is returned once the
+/// stream closes.
+async fn stream_attempt(
+    app: &AppHandle,
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    request: &PostProcessRequest,
+    timeout: Duration,
+) -> AttemptOutcome {
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut stream = match crate::llm_client::stream_chat_completion(
+        provider,
+        api_key,
+        model,
+        request.prompt.clone(),
+        request.system_message.clone(),
+        Some(timeout),
+    )
+    .await
+    {
+        Ok(stream) => stream,
+        Err(e) => return AttemptOutcome::Failed(e),
+    };
+
+    let mut content = String::new();
+    let mut last_emit: Option<Instant> = None;
+    while let Some(delta) = stream.next().await {
+        match delta {
+            Ok(delta) => {
+                content.push_str(&delta);
+                if last_emit.map_or(true, |at| at.elapsed() >= PARTIAL_OUTPUT_EMIT_INTERVAL) {
+                    utils::emit_post_process_partial(app, &content);
+                    last_emit = Some(Instant::now());
+                }
+            }
+            Err(e) => return AttemptOutcome::Failed(e),
         }
     }
+
+    if stream.was_aborted() {
+        return AttemptOutcome::Cancelled;
+    }
+
+    let content = sanitize_llm_output(&content);
+    if content.trim().is_empty() {
+        return AttemptOutcome::Failed("LLM stream closed without content".to_string());
+    }
+    AttemptOutcome::Succeeded(content)
 }
 
 fn select_post_process_prompt_id(
@@ -915,18 +1745,30 @@ fn select_post_process_prompt_id(
         None => return fallback,
     };
 
+    // Only prompts limited to certain apps need the frontmost app.
+    let app_name = if settings
+        .post_process_prompts
+        .iter()
+        .all(|prompt| prompt.apps.is_empty())
+    {
+        None
+    } else {
+        crate::context_providers::frontmost_app_name()
+    };
+
     selector
         .select_post_process_prompt_with_timeout(
             settings,
             &DomainContext {
                 text: transcription.to_string(),
+                app: app_name,
             },
             &settings.post_process_prompts,
         )
         .or(fallback)
 }
 
-async fn maybe_convert_chinese_variant(
+pub(crate) async fn maybe_convert_chinese_variant(
     settings: &AppSettings,
     transcription: &str,
 ) -> Option<String> {
@@ -1054,6 +1896,78 @@ mod tests {
         assert_eq!(diff.insert, "dog");
     }
 
+    /// Simulate keyboard application of `diff.edits` to `original`, starting
+    /// with the cursor at the end, and check the cursor ends there too.
+    fn apply_edits(original: &str, diff: &TextDiff) -> String {
+        let mut text: Vec<char> = original.chars().collect();
+        let mut cursor = text.len();
+        for edit in &diff.edits {
+            cursor -= edit.cursor_left;
+            text.drain(cursor - edit.delete_chars..cursor);
+            cursor -= edit.delete_chars;
+            let insert: Vec<char> = edit.insert.chars().collect();
+            text.splice(cursor..cursor, insert.iter().copied());
+            cursor += insert.len();
+        }
+        cursor += diff.restore_chars;
+        assert_eq!(cursor, text.len(), "cursor must return to the end");
+        text.into_iter().collect()
+    }
+
+    #[test]
+    fn test_diff_edits_fix_both_ends_without_retyping_middle() {
+        let original = "so the deploy script now waits for the health check before it \
+                        switches traffic over to the new version of the service ok";
+        let processed = "The deploy script now waits for the health check before it \
+                         switches traffic over to the new version of the service.";
+        let diff = compute_text_diff(original, processed).unwrap();
+        assert_eq!(diff.edits.len(), 2);
+        assert_eq!(diff.edits[0].delete_chars, 3);
+        assert_eq!(diff.edits[0].insert, ".");
+        assert_eq!(diff.edits[1].insert, "T");
+        assert!(diff.edits.iter().all(|edit| edit.delete_chars < 10));
+        assert_eq!(apply_edits(original, &diff), processed);
+    }
+
+    #[test]
+    fn test_diff_edits_merge_nearby_changes() {
+        let diff = compute_text_diff("hello world", "Hello world.").unwrap();
+        assert_eq!(diff.edits.len(), 1);
+        assert_eq!(apply_edits("hello world", &diff), "Hello world.");
+    }
+
+    #[test]
+    fn test_diff_edits_handle_insertions_and_deletions() {
+        let original = "please um add the tests for the parser and and then run them all";
+        let processed = "Please add the unit tests for the parser and then run them all.";
+        let diff = compute_text_diff(original, processed).unwrap();
+        assert!(diff.edits.len() > 1);
+        assert_eq!(apply_edits(original, &diff), processed);
+    }
+
+    #[test]
+    fn test_diff_falls_back_to_single_region_when_edits_are_many() {
+        let original = (0..20)
+            .map(|i| format!("word{} stays here", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let processed = (0..20)
+            .map(|i| format!("Word{} stays here", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let diff = compute_text_diff(&original, &processed).unwrap();
+        assert_eq!(
+            diff.edits,
+            vec![TextEdit {
+                cursor_left: diff.suffix_chars,
+                delete_chars: diff.delete_chars,
+                insert: diff.insert.clone(),
+            }]
+        );
+        assert_eq!(diff.restore_chars, diff.suffix_chars);
+        assert_eq!(apply_edits(&original, &diff), processed);
+    }
+
     // ── Real-world segment-on-silence test cases ──────────────────────
 
     /// Regression: multiple scattered changes (filler removal + punctuation)
@@ -1148,6 +2062,35 @@ mod tests {
         assert_eq!(diff.insert, "This is a test sentence.");
     }
 
+    /// Every real-world case above must also round-trip through the edits.
+    #[test]
+    fn test_diff_edits_round_trip_real_world_cases() {
+        let cases = [
+            (
+                "this is the test to determine if it's a little bit of a ni text to speech \
+                 silence segmentation is working the pasting is not working",
+                "this is the test to determine if it's a little bit of a text to speech. \
+                 Silence segmentation is working, the pasting is not working.",
+            ),
+            (
+                "please do add the integration tests, preferably with an i term to\
+                 process actually launched\
+                 such that we can\
+                 properly",
+                "Please do add the integration tests, preferably with an iTerm to \
+                 process actually launched such that we can properly.",
+            ),
+            (
+                "okay let's test i'm not sure there may be bugs ",
+                "Okay, let's test. I'm not sure there may be bugs.",
+            ),
+        ];
+        for (original, processed) in cases {
+            let diff = compute_text_diff(original, processed).unwrap();
+            assert_eq!(apply_edits(original, &diff), processed);
+        }
+    }
+
     /// Multiple segment boundaries cleaned up by LLM.
     #[test]
     fn test_diff_multiple_segment_boundary_cleanup() {
@@ -1170,4 +2113,47 @@ mod tests {
         );
         assert_eq!(applied, processed);
     }
+
+    fn dictate(text: &str) -> Vec<DictationAction> {
+        let commands: Vec<_> = voice_commands::VoiceCommandKind::ALL
+            .into_iter()
+            .map(voice_commands::VoiceCommandConfig::with_default_phrases)
+            .collect();
+        voice_commands::resolve(&voice_commands::parse(text, &commands), true)
+    }
+
+    #[test]
+    fn test_post_process_defers_trailing_key_commands() {
+        assert_eq!(
+            split_for_post_process(&dictate("hello world undo last"), false),
+            Some(("hello world".to_string(), vec![KeyAction::Undo]))
+        );
+    }
+
+    #[test]
+    fn test_post_process_skipped_for_key_command_mid_dictation() {
+        let dictation = dictate("hello world undo last goodbye");
+        assert_eq!(split_for_post_process(&dictation, false), None);
+        // Written out as spoken: the undo runs between the two spans.
+        assert_eq!(
+            dictation,
+            vec![
+                DictationAction::Type("hello world".to_string()),
+                DictationAction::Key(KeyAction::Undo),
+                DictationAction::Type("goodbye".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_post_process_skipped_for_only_key_commands() {
+        assert_eq!(
+            split_for_post_process(&dictate("select last word"), false),
+            None
+        );
+        assert_eq!(
+            split_for_post_process(&dictate("select last word"), true),
+            Some((String::new(), vec![KeyAction::SelectPreviousWord]))
+        );
+    }
 }
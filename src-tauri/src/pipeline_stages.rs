@@ -0,0 +1,569 @@
+//! Composable text stages run by `TranscriptionPipeline` after transcription.
+//!
+//! Each stage takes the current text plus a shared [`StageContext`] and either
+//! produces new text, skips, or fails. Failed and skipped stages pass their
+//! input through unchanged so a single broken step never drops the dictation.
+//! The order and enabled state of stages come from `AppSettings::pipeline_stages`.
+
+use crate::managers::history::PostProcessAttempt;
+use crate::managers::reranker::{Candidate, CandidateKind, RerankContext, RerankerManager};
+use crate::settings::{AppSettings, PipelineStageConfig};
+use crate::style_presets::{CapitalizationPolicy, NumberFormat, PunctuationPolicy, StylePreset};
+use log::{debug, info, warn};
+use serde::Serialize;
+use specta::Type;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+pub const CHINESE_VARIANT_STAGE_ID: &str = "chinese_variant";
+pub const SNIPPET_EXPANSION_STAGE_ID: &str = "snippet_expansion";
+pub const LLM_POST_PROCESS_STAGE_ID: &str = "llm_post_process";
+pub const STYLE_PRESET_STAGE_ID: &str = "style_preset";
+pub const AT_FILE_EXPANSION_STAGE_ID: &str = "at_file_expansion";
+
+/// Built-in stage ids in their default execution order.
+pub const BUILTIN_STAGE_IDS: &[&str] = &[
+    CHINESE_VARIANT_STAGE_ID,
+    SNIPPET_EXPANSION_STAGE_ID,
+    LLM_POST_PROCESS_STAGE_ID,
+    STYLE_PRESET_STAGE_ID,
+    AT_FILE_EXPANSION_STAGE_ID,
+];
+
+// ============================================================================
+// Stage trait & context
+// ============================================================================
+
+/// State shared by every stage of a single pipeline run.
+pub(crate) struct StageContext {
+    pub app: AppHandle,
+    pub settings: AppSettings,
+    /// Whether the raw text was stitched together from live-pasted segments.
+    pub had_segments: bool,
+    /// Whether the binding that triggered this run asked for LLM post-processing.
+    pub post_process: bool,
+    /// Binding that started this run.
+    pub binding_id: String,
+    /// Style preset picked for this run's binding and frontmost app.
+    pub style_preset: Option<StylePreset>,
+    /// Prompt text used by the LLM stage, saved alongside the history entry.
+    pub post_process_prompt: Option<String>,
+    /// Provider attempts made by the LLM stage, saved alongside the history entry.
+    pub post_process_attempts: Vec<PostProcessAttempt>,
+    /// Snippet bodies inserted by the snippet stage; later stages must not
+    /// rewrite them.
+    pub snippet_expansions: Vec<String>,
+    /// Set by a stage when the user cancelled the operation; no further
+    /// stages run and nothing is written out.
+    pub cancelled: bool,
+}
+
+pub(crate) enum StageOutcome {
+    /// The stage produced text (which may equal its input).
+    Continue(String),
+    /// The stage had nothing to do; the reason is recorded.
+    Skip(String),
+    /// The stage failed; the error is recorded and the input passes through.
+    Fail(String),
+}
+
+#[async_trait::async_trait]
+pub(crate) trait PipelineStage: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    /// Whether this stage's output belongs in the saved history entry.
+    /// Output-only rewrites such as @file expansion return `false`.
+    fn records_history(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, text: &str, ctx: &mut StageContext) -> StageOutcome;
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    Applied,
+    Skipped,
+    Failed,
+}
+
+/// Timing and outcome of one stage within a pipeline run.
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct StageRecord {
+    pub stage_id: String,
+    pub status: StageStatus,
+    pub elapsed_ms: u64,
+    pub changed: bool,
+    pub detail: Option<String>,
+}
+
+pub(crate) struct StageRun {
+    /// Text after every stage has run; this is what gets written out.
+    pub text: String,
+    /// Text after the last stage that records history.
+    pub history_text: String,
+    pub records: Vec<StageRecord>,
+}
+
+// ============================================================================
+// Stage registry & runner
+// ============================================================================
+
+fn builtin_stage(id: &str) -> Option<Box<dyn PipelineStage>> {
+    match id {
+        CHINESE_VARIANT_STAGE_ID => Some(Box::new(ChineseVariantStage)),
+        SNIPPET_EXPANSION_STAGE_ID => Some(Box::new(SnippetExpansionStage)),
+        LLM_POST_PROCESS_STAGE_ID => Some(Box::new(LlmPostProcessStage)),
+        STYLE_PRESET_STAGE_ID => Some(Box::new(StylePresetStage)),
+        AT_FILE_EXPANSION_STAGE_ID => Some(Box::new(AtFileExpansionStage)),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_known_stage(id: &str) -> bool {
+    BUILTIN_STAGE_IDS.contains(&id)
+}
+
+/// Resolve the enabled stage ids from settings, preserving order and
+/// dropping duplicates and ids that no stage implements.
+pub(crate) fn enabled_stage_ids(configs: &[PipelineStageConfig]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    configs
+        .iter()
+        .filter(|config| config.enabled && is_known_stage(&config.id))
+        .filter(|config| seen.insert(config.id.clone()))
+        .map(|config| config.id.clone())
+        .collect()
+}
+
+pub(crate) fn stages_from_settings(settings: &AppSettings) -> Vec<Box<dyn PipelineStage>> {
+    enabled_stage_ids(&settings.pipeline_stages)
+        .iter()
+        .filter_map(|id| builtin_stage(id))
+        .collect()
+}
+
+/// Run `stages` in order over `input`, recording each stage's timing and outcome.
+pub(crate) async fn run_stages(
+    stages: &[Box<dyn PipelineStage>],
+    input: String,
+    ctx: &mut StageContext,
+) -> StageRun {
+    let mut text = input;
+    let mut history_text = text.clone();
+    let mut records = Vec::with_capacity(stages.len());
+
+    for stage in stages {
+        let started = Instant::now();
+        let outcome = stage.run(&text, ctx).await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+
+        let record = match outcome {
+            StageOutcome::Continue(next) => {
+                let changed = next != text;
+                text = next;
+                if stage.records_history() {
+                    history_text = text.clone();
+                }
+                StageRecord {
+                    stage_id: stage.id().to_string(),
+                    status: StageStatus::Applied,
+                    elapsed_ms,
+                    changed,
+                    detail: None,
+                }
+            }
+            StageOutcome::Skip(reason) => StageRecord {
+                stage_id: stage.id().to_string(),
+                status: StageStatus::Skipped,
+                elapsed_ms,
+                changed: false,
+                detail: Some(reason),
+            },
+            StageOutcome::Fail(err) => {
+                warn!("Pipeline stage '{}' failed: {}", stage.id(), err);
+                StageRecord {
+                    stage_id: stage.id().to_string(),
+                    status: StageStatus::Failed,
+                    elapsed_ms,
+                    changed: false,
+                    detail: Some(err),
+                }
+            }
+        };
+
+        debug!(
+            "Pipeline stage '{}' {:?} in {}ms (changed={})",
+            record.stage_id, record.status, record.elapsed_ms, record.changed
+        );
+        records.push(record);
+
+        if ctx.cancelled {
+            info!("Pipeline cancelled during stage '{}'", stage.id());
+            break;
+        }
+    }
+
+    info!(
+        "Pipeline stages: {}",
+        records
+            .iter()
+            .map(|r| format!("{}={:?}/{}ms", r.stage_id, r.status, r.elapsed_ms))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    StageRun {
+        text,
+        history_text,
+        records,
+    }
+}
+
+// ============================================================================
+// Built-in stages
+// ============================================================================
+
+struct ChineseVariantStage;
+
+#[async_trait::async_trait]
+impl PipelineStage for ChineseVariantStage {
+    fn id(&self) -> &'static str {
+        CHINESE_VARIANT_STAGE_ID
+    }
+
+    async fn run(&self, text: &str, ctx: &mut StageContext) -> StageOutcome {
+        if ctx.settings.selected_language != "zh-Hans"
+            && ctx.settings.selected_language != "zh-Hant"
+        {
+            return StageOutcome::Skip("language is not a Chinese variant".to_string());
+        }
+        match crate::pipeline::maybe_convert_chinese_variant(&ctx.settings, text).await {
+            Some(converted) => StageOutcome::Continue(converted),
+            None => StageOutcome::Fail("OpenCC conversion unavailable".to_string()),
+        }
+    }
+}
+
+struct SnippetExpansionStage;
+
+#[async_trait::async_trait]
+impl PipelineStage for SnippetExpansionStage {
+    fn id(&self) -> &'static str {
+        SNIPPET_EXPANSION_STAGE_ID
+    }
+
+    async fn run(&self, text: &str, ctx: &mut StageContext) -> StageOutcome {
+        if !ctx.settings.snippets_enabled {
+            return StageOutcome::Skip("snippets are disabled".to_string());
+        }
+        let app = ctx.app.clone();
+        let expansion = crate::snippets::expand(text, &ctx.settings.snippets, |name| {
+            crate::snippets::builtin_variable(&app, name)
+        });
+        if expansion.inserted.is_empty() {
+            return StageOutcome::Skip("no snippet trigger spoken".to_string());
+        }
+        ctx.snippet_expansions.extend(expansion.inserted);
+        StageOutcome::Continue(expansion.text)
+    }
+}
+
+struct LlmPostProcessStage;
+
+#[async_trait::async_trait]
+impl PipelineStage for LlmPostProcessStage {
+    fn id(&self) -> &'static str {
+        LLM_POST_PROCESS_STAGE_ID
+    }
+
+    async fn run(&self, text: &str, ctx: &mut StageContext) -> StageOutcome {
+        if !ctx.post_process {
+            return StageOutcome::Skip("binding does not request post-processing".to_string());
+        }
+
+        info!(
+            "Starting LLM post-processing on text ({} chars, had_segments={}): '{}'",
+            text.len(),
+            ctx.had_segments,
+            text.chars().take(100).collect::<String>()
+        );
+        // Snippet bodies go to the model as placeholders so it cannot rewrite them.
+        let (masked, snippet_pairs) = crate::snippets::mask_inserted(text, &ctx.snippet_expansions);
+        let style_instructions = ctx
+            .style_preset
+            .as_ref()
+            .and_then(crate::style_presets::llm_instructions);
+        let primary = crate::pipeline::post_process_transcription(
+            &ctx.app,
+            &ctx.settings,
+            &masked,
+            ctx.had_segments,
+            ctx.settings.post_process_streaming,
+            &ctx.binding_id,
+            style_instructions.clone(),
+        );
+        // The re-ranker's second prompt runs alongside, without streaming.
+        let (result, second) = match second_prompt_settings(&ctx.settings) {
+            Some(second_settings) => {
+                let second = crate::pipeline::post_process_transcription(
+                    &ctx.app,
+                    &second_settings,
+                    &masked,
+                    ctx.had_segments,
+                    false,
+                    &ctx.binding_id,
+                    style_instructions,
+                );
+                let (result, second) = futures_util::future::join(primary, second).await;
+                (result, Some(second))
+            }
+            None => (primary.await, None),
+        };
+        let attempt_count = result.attempts.len();
+        ctx.post_process_attempts = result.attempts;
+        if result.cancelled || second.as_ref().is_some_and(|second| second.cancelled) {
+            ctx.cancelled = true;
+            return StageOutcome::Fail("cancelled by user".to_string());
+        }
+
+        match result.text {
+            Some(processed_text) => {
+                let processed_text =
+                    match crate::snippets::restore_inserted(&processed_text, &snippet_pairs) {
+                        Ok(restored) => restored,
+                        Err(e) => return StageOutcome::Fail(e),
+                    };
+                info!(
+                    "LLM post-processing returned ({} chars): '{}'",
+                    processed_text.len(),
+                    processed_text.chars().take(100).collect::<String>()
+                );
+                let mut prompt_id = ctx.settings.post_process_selected_prompt_id.clone();
+                let processed_text = if ctx.settings.reranker_enabled {
+                    let second_text = second
+                        .and_then(|second| second.text)
+                        .and_then(|t| crate::snippets::restore_inserted(&t, &snippet_pairs).ok());
+                    let chosen = rerank_output(ctx, text, processed_text, second_text).await;
+                    info!("Re-ranker kept the {:?} candidate", chosen.kind);
+                    prompt_id = match chosen.kind {
+                        CandidateKind::Llm => prompt_id,
+                        CandidateKind::LlmSecondPrompt => {
+                            ctx.settings.reranker_second_prompt_id.clone()
+                        }
+                        CandidateKind::Raw | CandidateKind::RuleCleaned => None,
+                    };
+                    chosen.text
+                } else {
+                    processed_text
+                };
+                if let Some(prompt_id) = &prompt_id {
+                    ctx.post_process_prompt = ctx
+                        .settings
+                        .post_process_prompts
+                        .iter()
+                        .find(|p| &p.id == prompt_id)
+                        .map(|p| p.prompt.clone());
+                }
+                StageOutcome::Continue(processed_text)
+            }
+            None if attempt_count == 0 => StageOutcome::Fail(
+                "LLM post-processing returned None — check provider/model/prompt/API key config"
+                    .to_string(),
+            ),
+            None => StageOutcome::Fail(format!(
+                "all {} post-processing provider attempt(s) failed",
+                attempt_count
+            )),
+        }
+    }
+}
+
+/// Settings for the re-ranker's second-prompt candidate, when one is set
+/// up and differs from the selected prompt.
+fn second_prompt_settings(settings: &AppSettings) -> Option<AppSettings> {
+    if !settings.reranker_enabled {
+        return None;
+    }
+    let prompt_id = settings.reranker_second_prompt_id.as_ref()?;
+    if settings.post_process_selected_prompt_id.as_ref() == Some(prompt_id)
+        || !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+    {
+        return None;
+    }
+    let mut second = settings.clone();
+    second.post_process_selected_prompt_id = Some(prompt_id.clone());
+    second.post_process_auto_prompt_selection = false;
+    Some(second)
+}
+
+/// Pick between the LLM output, the raw text, a rule-based cleanup of it
+/// and the second-prompt output. Scoring blocks, so it runs off the async
+/// runtime.
+async fn rerank_output(
+    ctx: &StageContext,
+    raw: &str,
+    llm: String,
+    second: Option<String>,
+) -> Candidate {
+    let Some(reranker) = ctx.app.try_state::<Arc<RerankerManager>>() else {
+        return Candidate::new(CandidateKind::Llm, llm);
+    };
+    let reranker = Arc::clone(reranker.inner());
+
+    let mut candidates = vec![
+        Candidate::new(CandidateKind::Raw, raw),
+        Candidate::new(CandidateKind::Llm, llm),
+    ];
+    // Without a preset, only the cleanup that cannot change meaning.
+    let preset = ctx.style_preset.clone().unwrap_or_else(|| StylePreset {
+        id: "cleanup".to_string(),
+        name: "Cleanup".to_string(),
+        punctuation: PunctuationPolicy::Keep,
+        capitalization: CapitalizationPolicy::Sentence,
+        remove_fillers: true,
+        numbers: NumberFormat::Keep,
+        max_sentence_words: None,
+        rewrite_instructions: String::new(),
+    });
+    let (masked, snippet_pairs) = crate::snippets::mask_inserted(raw, &ctx.snippet_expansions);
+    let cleaned = crate::style_presets::apply_rules(&masked, &preset);
+    if let Ok(cleaned) = crate::snippets::restore_inserted(&cleaned, &snippet_pairs) {
+        candidates.push(Candidate::new(CandidateKind::RuleCleaned, cleaned));
+    }
+    if let Some(second) = second {
+        candidates.push(Candidate::new(CandidateKind::LlmSecondPrompt, second));
+    }
+
+    let context = RerankContext {
+        raw: raw.to_string(),
+        terms: crate::pipeline::active_jargon_terms(&ctx.app, &ctx.settings, raw, &ctx.binding_id),
+    };
+    let settings = ctx.settings.clone();
+    let fallback = Candidate::new(CandidateKind::Raw, raw);
+    tauri::async_runtime::spawn_blocking(move || {
+        reranker.rerank_with_timeout(&settings, &context, candidates)
+    })
+    .await
+    .unwrap_or_else(|err| {
+        warn!("Re-ranker task failed: {}", err);
+        fallback
+    })
+}
+
+struct StylePresetStage;
+
+#[async_trait::async_trait]
+impl PipelineStage for StylePresetStage {
+    fn id(&self) -> &'static str {
+        STYLE_PRESET_STAGE_ID
+    }
+
+    async fn run(&self, text: &str, ctx: &mut StageContext) -> StageOutcome {
+        let Some(preset) = ctx.style_preset.as_ref() else {
+            return StageOutcome::Skip("no style preset assigned".to_string());
+        };
+        let (masked, snippet_pairs) = crate::snippets::mask_inserted(text, &ctx.snippet_expansions);
+        let styled = crate::style_presets::apply_rules(&masked, preset);
+        match crate::snippets::restore_inserted(&styled, &snippet_pairs) {
+            Ok(restored) => StageOutcome::Continue(restored),
+            Err(e) => StageOutcome::Fail(e),
+        }
+    }
+}
+
+struct AtFileExpansionStage;
+
+#[async_trait::async_trait]
+impl PipelineStage for AtFileExpansionStage {
+    fn id(&self) -> &'static str {
+        AT_FILE_EXPANSION_STAGE_ID
+    }
+
+    fn records_history(&self) -> bool {
+        false
+    }
+
+    async fn run(&self, text: &str, ctx: &mut StageContext) -> StageOutcome {
+        if !ctx.settings.at_file_expansion_enabled {
+            return StageOutcome::Skip("@file expansion is disabled".to_string());
+        }
+        StageOutcome::Continue(expand_at_refs_for_output(&ctx.app, &ctx.settings, text))
+    }
+}
+
+/// Expand @file references for output. Also used directly by the
+/// non-post-process paste path, which does not run the stage list.
+pub(crate) fn expand_at_refs_for_output(
+    app: &AppHandle,
+    settings: &AppSettings,
+    text: &str,
+) -> String {
+    if !settings.at_file_expansion_enabled {
+        return text.to_string();
+    }
+
+    let expanded = crate::at_file_expansion::maybe_expand_at_refs(text, settings, app);
+    if expanded != text {
+        let delta = expanded.len() as isize - text.len() as isize;
+        debug!("@file expansion adjusted output by {} chars", delta);
+    }
+    expanded
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(id: &str, enabled: bool) -> PipelineStageConfig {
+        PipelineStageConfig {
+            id: id.to_string(),
+            enabled,
+        }
+    }
+
+    #[test]
+    fn enabled_stage_ids_preserve_configured_order() {
+        let configs = vec![
+            config(AT_FILE_EXPANSION_STAGE_ID, true),
+            config(LLM_POST_PROCESS_STAGE_ID, true),
+            config(CHINESE_VARIANT_STAGE_ID, true),
+        ];
+        assert_eq!(
+            enabled_stage_ids(&configs),
+            vec![
+                AT_FILE_EXPANSION_STAGE_ID,
+                LLM_POST_PROCESS_STAGE_ID,
+                CHINESE_VARIANT_STAGE_ID
+            ]
+        );
+    }
+
+    #[test]
+    fn enabled_stage_ids_drop_disabled_unknown_and_duplicate_stages() {
+        let configs = vec![
+            config(CHINESE_VARIANT_STAGE_ID, false),
+            config("does_not_exist", true),
+            config(LLM_POST_PROCESS_STAGE_ID, true),
+            config(LLM_POST_PROCESS_STAGE_ID, true),
+        ];
+        assert_eq!(enabled_stage_ids(&configs), vec![LLM_POST_PROCESS_STAGE_ID]);
+    }
+
+    #[test]
+    fn every_builtin_id_resolves_to_a_stage() {
+        for id in BUILTIN_STAGE_IDS {
+            let stage = builtin_stage(id).expect("builtin stage");
+            assert_eq!(stage.id(), *id);
+        }
+    }
+}
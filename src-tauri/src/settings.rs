@@ -1,13 +1,23 @@
+use crate::jargon_learning::JargonProposal;
+use crate::jargon_sources::JargonSource;
+use crate::managers::domain_selector::ProfileFeedback;
+use crate::snippets::Snippet;
+use crate::style_presets::{default_style_presets, StylePreset, StylePresetAppRule};
+use crate::templates::TextTemplate;
+use crate::voice_commands::{VoiceCommandConfig, VoiceCommandKind};
 use log::{debug, warn};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
 pub const APPLE_INTELLIGENCE_PROVIDER_ID: &str = "apple_intelligence";
 pub const APPLE_INTELLIGENCE_DEFAULT_MODEL_ID: &str = "Apple Intelligence";
+pub const LOCAL_CLEANUP_PROVIDER_ID: &str = "local_cleanup";
+pub const LOCAL_CLEANUP_DEFAULT_MODEL_ID: &str = "flan-t5-small";
 
 #[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "lowercase")]
@@ -85,11 +95,31 @@ pub struct ShortcutBinding {
     pub current_binding: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Type)]
 pub struct LLMPrompt {
     pub id: String,
     pub name: String,
     pub prompt: String,
+    /// Words and phrases that point automatic prompt selection at this prompt,
+    /// on top of its id and name.
+    #[serde(default)]
+    pub triggers: Vec<String>,
+    /// Text containing any of these is never routed to this prompt.
+    #[serde(default)]
+    pub negative_keywords: Vec<String>,
+    /// Frontmost apps the prompt may be auto-selected in, matched like style
+    /// preset app rules. Empty means any app.
+    #[serde(default)]
+    pub apps: Vec<String>,
+    /// Raises (or lowers) the prompt's routing score in small steps; see
+    /// `managers::domain_selector`.
+    #[serde(default)]
+    pub priority: i32,
+    /// The prompt reshapes the transcript (notes, lists, drafts) rather than
+    /// cleaning it up, so the divergence guard only checks that protected
+    /// spans survive.
+    #[serde(default)]
+    pub restructures: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -102,6 +132,17 @@ pub struct JargonPack {
     pub corrections: Vec<crate::jargon::JargonCorrection>,
 }
 
+/// Request/response format a post-process provider speaks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WireProtocol {
+    /// OpenAI-compatible `/chat/completions`.
+    #[default]
+    OpenaiChat,
+    /// Anthropic `/messages`.
+    AnthropicMessages,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct PostProcessProvider {
     pub id: String,
@@ -111,6 +152,22 @@ pub struct PostProcessProvider {
     pub allow_base_url_edit: bool,
     #[serde(default)]
     pub models_endpoint: Option<String>,
+    #[serde(default)]
+    pub wire_protocol: WireProtocol,
+}
+
+/// A (provider, model) pair tried when the active post-process provider fails.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct PostProcessFallback {
+    pub provider_id: String,
+    pub model: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct PipelineStageConfig {
+    pub id: String,
+    #[serde(default = "default_pipeline_stage_enabled")]
+    pub enabled: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -324,6 +381,12 @@ pub struct AppSettings {
     pub model_unload_timeout: ModelUnloadTimeout,
     #[serde(default = "default_word_correction_threshold")]
     pub word_correction_threshold: f64,
+    /// Match jargon terms and custom words by sound as well as spelling.
+    #[serde(default = "default_phonetic_matching_enabled")]
+    pub phonetic_matching_enabled: bool,
+    /// Minimum confidence (0-1) for a phonetic match to replace text.
+    #[serde(default = "default_phonetic_match_threshold")]
+    pub phonetic_match_threshold: f64,
     #[serde(default = "default_history_limit")]
     pub history_limit: usize,
     #[serde(default = "default_recording_retention_period")]
@@ -353,9 +416,52 @@ pub struct AppSettings {
     #[serde(default)]
     pub post_process_selected_prompt_id: Option<String>,
     #[serde(default)]
+    pub post_process_streaming: bool,
+    #[serde(default)]
+    pub post_process_fallbacks: Vec<PostProcessFallback>,
+    #[serde(default = "default_post_process_timeout_ms")]
+    pub post_process_timeout_ms: u64,
+    #[serde(default = "default_post_process_divergence_guard")]
+    pub post_process_divergence_guard: bool,
+    #[serde(default)]
+    pub local_cleanup_model_dir: Option<String>,
+    #[serde(default)]
+    pub voice_commands_enabled: bool,
+    #[serde(default = "default_voice_commands")]
+    pub voice_commands: Vec<VoiceCommandConfig>,
+    #[serde(default)]
+    pub templates_enabled: bool,
+    #[serde(default = "default_templates")]
+    pub templates: Vec<TextTemplate>,
+    #[serde(default)]
+    pub template_llm_fill: bool,
+    #[serde(default)]
+    pub snippets_enabled: bool,
+    #[serde(default)]
+    pub snippets: Vec<Snippet>,
+    #[serde(default = "default_style_presets")]
+    pub style_presets: Vec<StylePreset>,
+    /// Binding id -> style preset id.
+    #[serde(default)]
+    pub style_preset_bindings: HashMap<String, String>,
+    /// Checked in order; the first rule matching the frontmost app wins over
+    /// the binding's preset.
+    #[serde(default)]
+    pub style_preset_app_rules: Vec<StylePresetAppRule>,
+    #[serde(default)]
+    pub cli_mode_enabled: bool,
+    #[serde(default)]
+    pub cli_mode_llm_assist: bool,
+    /// Extra regexes (case-insensitive) that keep a CLI command from being
+    /// auto-submitted, on top of the built-in deny list.
+    #[serde(default)]
+    pub cli_mode_deny_patterns: Vec<String>,
+    #[serde(default)]
     pub mute_while_recording: bool,
-    #[serde(default = "default_audio_segment_size_seconds")]
-    pub audio_segment_size_seconds: f64,
+    /// Trailing silence (ms) after which speech is transcribed and pasted
+    /// while recording continues. 0 turns segment-on-silence off.
+    #[serde(default)]
+    pub segment_on_silence_ms: u64,
     #[serde(default)]
     pub append_trailing_space: bool,
     #[serde(default = "default_app_language")]
@@ -392,8 +498,51 @@ pub struct AppSettings {
     pub domain_selector_hysteresis: f32,
     #[serde(default = "default_domain_selector_blend_manual_profiles")]
     pub domain_selector_blend_manual_profiles: bool,
+    /// Weight profiles by how often they were kept or corrected against.
+    #[serde(default)]
+    pub domain_selector_learned_weights: bool,
+    #[serde(default)]
+    pub domain_selector_profile_feedback: HashMap<String, ProfileFeedback>,
     #[serde(default)]
     pub jargon_packs: Vec<JargonPack>,
+    /// Files and folders with extra jargon, highest priority first.
+    #[serde(default)]
+    pub jargon_sources: Vec<JargonSource>,
+    /// Add file names and identifiers from the active workspace as jargon.
+    #[serde(default)]
+    pub workspace_jargon_enabled: bool,
+    /// Proposals learned from history edits that the user turned down.
+    #[serde(default)]
+    pub jargon_rejected_proposals: Vec<JargonProposal>,
+    #[serde(default)]
+    pub intent_router_enabled: bool,
+    /// Directory with `model.onnx` and `tokenizer.json`; lexical routing
+    /// is used without one.
+    #[serde(default)]
+    pub intent_router_model_dir: Option<String>,
+    #[serde(default = "default_intent_router_timeout_ms")]
+    pub intent_router_timeout_ms: u64,
+    #[serde(default = "default_intent_router_min_confidence")]
+    pub intent_router_min_confidence: f32,
+    /// Pick the best of the raw, rule-cleaned and LLM outputs instead of
+    /// always using the LLM output.
+    #[serde(default)]
+    pub reranker_enabled: bool,
+    /// Directory with a cross-encoder `model.onnx` and `tokenizer.json`;
+    /// lexical scoring is used without one.
+    #[serde(default)]
+    pub reranker_model_dir: Option<String>,
+    /// Prompt for an extra LLM candidate, run alongside the usual one.
+    #[serde(default)]
+    pub reranker_second_prompt_id: Option<String>,
+    #[serde(default = "default_reranker_timeout_ms")]
+    pub reranker_timeout_ms: u64,
+    #[serde(default = "default_pipeline_stages")]
+    pub pipeline_stages: Vec<PipelineStageConfig>,
+    /// Bumped on every write and never persisted, so caches built from a
+    /// snapshot can tell when it went stale.
+    #[serde(skip)]
+    pub revision: u64,
 }
 
 fn default_model() -> String {
@@ -408,10 +557,6 @@ fn default_translate_to_english() -> bool {
     false
 }
 
-fn default_audio_segment_size_seconds() -> f64 {
-    0.0
-}
-
 fn default_start_hidden() -> bool {
     false
 }
@@ -447,6 +592,14 @@ fn default_word_correction_threshold() -> f64 {
     0.18
 }
 
+fn default_phonetic_matching_enabled() -> bool {
+    false
+}
+
+fn default_phonetic_match_threshold() -> f64 {
+    0.8
+}
+
 fn default_paste_delay_ms() -> u64 {
     60
 }
@@ -501,6 +654,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.openai.com/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            wire_protocol: WireProtocol::OpenaiChat,
         },
         PostProcessProvider {
             id: "openrouter".to_string(),
@@ -508,6 +662,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://openrouter.ai/api/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            wire_protocol: WireProtocol::OpenaiChat,
         },
         PostProcessProvider {
             id: "anthropic".to_string(),
@@ -515,6 +670,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.anthropic.com/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            wire_protocol: WireProtocol::AnthropicMessages,
         },
         PostProcessProvider {
             id: "groq".to_string(),
@@ -522,6 +678,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.groq.com/openai/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            wire_protocol: WireProtocol::OpenaiChat,
         },
         PostProcessProvider {
             id: "cerebras".to_string(),
@@ -529,6 +686,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "https://api.cerebras.ai/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
+            wire_protocol: WireProtocol::OpenaiChat,
         },
     ];
 
@@ -544,9 +702,20 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             base_url: "apple-intelligence://local".to_string(),
             allow_base_url_edit: false,
             models_endpoint: None,
+            wire_protocol: WireProtocol::OpenaiChat,
         });
     }
 
+    // On-device flan-t5 cleanup; see cleanup.rs.
+    providers.push(PostProcessProvider {
+        id: LOCAL_CLEANUP_PROVIDER_ID.to_string(),
+        label: "Local Cleanup (offline)".to_string(),
+        base_url: "local-cleanup://local".to_string(),
+        allow_base_url_edit: false,
+        models_endpoint: None,
+        wire_protocol: WireProtocol::OpenaiChat,
+    });
+
     // Custom provider always comes last
     providers.push(PostProcessProvider {
         id: "custom".to_string(),
@@ -554,6 +723,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
         base_url: "http://localhost:11434/v1".to_string(),
         allow_base_url_edit: true,
         models_endpoint: Some("/models".to_string()),
+        wire_protocol: WireProtocol::OpenaiChat,
     });
 
     providers
@@ -571,6 +741,9 @@ fn default_model_for_provider(provider_id: &str) -> String {
     if provider_id == APPLE_INTELLIGENCE_PROVIDER_ID {
         return APPLE_INTELLIGENCE_DEFAULT_MODEL_ID.to_string();
     }
+    if provider_id == LOCAL_CLEANUP_PROVIDER_ID {
+        return LOCAL_CLEANUP_DEFAULT_MODEL_ID.to_string();
+    }
     String::new()
 }
 
@@ -586,78 +759,167 @@ fn default_post_process_models() -> HashMap<String, String> {
 }
 
 fn builtin_post_process_prompts() -> Vec<LLMPrompt> {
-    vec![
+    let prompts = vec![
         LLMPrompt {
             id: "default_improve_transcriptions".to_string(),
             name: "Improve Transcriptions".to_string(),
             prompt: "Clean this transcript for readability while preserving meaning:\n1. Fix spelling, capitalization, punctuation, and spacing\n2. Convert spoken number words to digits when clear\n3. Remove obvious filler words and false starts only when confidence is high\n4. Keep technical terms and identifiers exact\n\nReturn only the cleaned transcript text.\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_coding_assistant".to_string(),
             name: "Coding Assistant".to_string(),
             prompt: "Rewrite this transcript into an engineering update.\n\nOutput format:\n## Summary\n- 2-4 factual bullets\n## Tasks\n- [ ] Task\n## Notes\n- Optional short bullets\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_slack_message".to_string(),
             name: "Slack Message".to_string(),
             prompt: "Convert this transcript into a concise Slack update.\n1. Keep it direct, friendly, and skimmable\n2. Preserve decisions, blockers, owners, and dates exactly\n3. Keep to 80-140 words unless source is shorter\n\nReturn only the final Slack message body.\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_email_draft".to_string(),
             name: "Email Draft".to_string(),
             prompt: "Transform this transcript into a professional email draft.\n\nOutput format:\nSubject: <clear subject>\n<body paragraphs>\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_document_writer".to_string(),
             name: "Document Writer".to_string(),
             prompt: "Turn this transcript into a structured document draft.\n\nOutput format:\n# Title\n## Context\n## Details\n## Decisions\n## Next Steps\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_meeting_notes".to_string(),
             name: "Meeting Notes".to_string(),
             prompt: "Convert this transcript into clean meeting notes.\n\nOutput format:\n## Summary\n- Bullet points\n## Decisions\n- Bullet points\n## Open Questions\n- Bullet points\n## Action Items\n- [ ] Owner - Task (Due: date or TBA)\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_action_items".to_string(),
             name: "Action Items".to_string(),
             prompt: "Extract only actionable tasks from this transcript.\n\nOutput format:\n- [ ] Owner - Task (Due: date or TBA)\n\nRules:\n- Use \"Unassigned\" when owner is unknown\n- Do not include non-actionable commentary\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_standup_update".to_string(),
             name: "Standup Update".to_string(),
             prompt: "Rewrite this transcript into a daily standup update.\n\nOutput format:\nYesterday:\n- Bullet points\nToday:\n- Bullet points\nBlockers:\n- Bullet points or \"None\"\n\nRules:\n- Max 3 bullets per section\n- Keep under 120 words when possible\n- Do not add details not present in source\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_pr_description".to_string(),
             name: "PR Description".to_string(),
             prompt: "Turn this transcript into a pull request description.\n\nOutput format:\n## Summary\n## Changes\n## Testing\n## Reviewer Checklist\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_ticket_writer".to_string(),
             name: "Jira Ticket".to_string(),
             prompt: "Convert this transcript into a clear engineering ticket.\n\nOutput format:\nTitle: <specific title>\nDescription:\nAcceptance Criteria:\n- ...\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_commit_message".to_string(),
             name: "Commit Message".to_string(),
             prompt: "Create a conventional commit message from this transcript.\n\nRules:\n- Use one type: feat, fix, chore, refactor, docs, test\n- Keep subject <= 72 chars\n- Add body only if needed\n\nReturn only the commit message.\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_release_notes".to_string(),
             name: "Release Notes".to_string(),
             prompt: "Rewrite this transcript into end-user release notes.\n\nOutput format:\n## Added\n## Improved\n## Fixed\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_customer_support_reply".to_string(),
             name: "Support Reply".to_string(),
             prompt: "Turn this transcript into a customer support response.\n\nRules:\n- Keep tone empathetic and concise\n- Clearly state next steps\n- Ask only necessary follow-up questions\n\nReturn only the final reply.\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
         LLMPrompt {
             id: "default_brain_dump_to_outline".to_string(),
             name: "Brain Dump to Outline".to_string(),
             prompt: "Organize this transcript into a structured outline.\n\nOutput format:\n# Main Topic\n## Section\n- Bullet\n\nTranscript:\n${output}".to_string(),
+            ..Default::default()
         },
-    ]
+    ];
+    prompts
+        .into_iter()
+        .map(|prompt| LLMPrompt {
+            triggers: default_prompt_triggers(&prompt.id),
+            restructures: prompt.id != "default_improve_transcriptions",
+            ..prompt
+        })
+        .collect()
+}
+
+/// Words people use when asking for a built-in prompt's output.
+fn default_prompt_triggers(prompt_id: &str) -> Vec<String> {
+    let triggers: &[&str] = match prompt_id {
+        "default_action_items" => &[
+            "action item",
+            "todo",
+            "next steps",
+            "owner",
+            "deadline",
+            "task",
+            "follow-up",
+            "assignee",
+            "responsibilities",
+            "due date",
+            "checklist",
+        ],
+        "default_document_writer" => &[
+            "document",
+            "proposal",
+            "design doc",
+            "write-up",
+            "spec",
+            "draft",
+            "memo",
+            "requirements",
+            "sections",
+            "formal",
+        ],
+        "default_meeting_notes" => &[
+            "meeting",
+            "agenda",
+            "decisions",
+            "attendees",
+            "recap",
+            "notes",
+            "minutes",
+        ],
+        "default_slack_message" => &[
+            "slack",
+            "channel",
+            "team update",
+            "quick update",
+            "message",
+            "post",
+            "announcement",
+            "ping",
+        ],
+        "default_standup_update" => &[
+            "standup",
+            "daily",
+            "yesterday",
+            "today",
+            "blockers",
+            "scrum",
+        ],
+        "default_email_draft" => &["email", "subject line", "recipient"],
+        "default_pr_description" => &["pull request", "reviewer", "merge"],
+        "default_ticket_writer" => &["ticket", "jira", "acceptance criteria", "bug report"],
+        "default_commit_message" => &["commit message", "commit"],
+        "default_release_notes" => &["release notes", "changelog"],
+        "default_customer_support_reply" => &["customer", "support", "refund"],
+        "default_brain_dump_to_outline" => &["brain dump", "outline", "brainstorm"],
+        _ => &[],
+    };
+    triggers.iter().map(|trigger| trigger.to_string()).collect()
 }
 
 fn default_post_process_prompts() -> Vec<LLMPrompt> {
@@ -692,16 +954,73 @@ fn default_domain_selector_blend_manual_profiles() -> bool {
     true
 }
 
+fn default_intent_router_timeout_ms() -> u64 {
+    150
+}
+
+fn default_intent_router_min_confidence() -> f32 {
+    0.6
+}
+
+fn default_reranker_timeout_ms() -> u64 {
+    150
+}
+
+fn default_post_process_timeout_ms() -> u64 {
+    15000
+}
+
+fn default_post_process_divergence_guard() -> bool {
+    true
+}
+
+fn default_voice_commands() -> Vec<VoiceCommandConfig> {
+    VoiceCommandKind::ALL
+        .into_iter()
+        .map(VoiceCommandConfig::with_default_phrases)
+        .collect()
+}
+
+fn default_templates() -> Vec<TextTemplate> {
+    crate::templates::default_templates()
+}
+
+fn default_pipeline_stage_enabled() -> bool {
+    true
+}
+
+fn default_pipeline_stages() -> Vec<PipelineStageConfig> {
+    crate::pipeline_stages::BUILTIN_STAGE_IDS
+        .iter()
+        .map(|id| PipelineStageConfig {
+            id: id.to_string(),
+            enabled: true,
+        })
+        .collect()
+}
+
 fn ensure_post_process_defaults(settings: &mut AppSettings) -> bool {
     let mut changed = false;
     for provider in default_post_process_providers() {
-        if settings
+        match settings
             .post_process_providers
-            .iter()
-            .all(|existing| existing.id != provider.id)
+            .iter_mut()
+            .find(|existing| existing.id == provider.id)
         {
-            settings.post_process_providers.push(provider.clone());
-            changed = true;
+            None => {
+                settings.post_process_providers.push(provider.clone());
+                changed = true;
+            }
+            // Built-in endpoints are fixed, so their protocol follows the
+            // defaults; only the editable custom provider keeps its own.
+            Some(existing)
+                if !provider.allow_base_url_edit
+                    && existing.wire_protocol != provider.wire_protocol =>
+            {
+                existing.wire_protocol = provider.wire_protocol;
+                changed = true;
+            }
+            Some(_) => {}
         }
 
         if !settings.post_process_api_keys.contains_key(&provider.id) {
@@ -729,16 +1048,34 @@ fn ensure_post_process_defaults(settings: &mut AppSettings) -> bool {
     }
 
     for prompt in builtin_post_process_prompts() {
-        if settings
+        if !settings
             .post_process_prompts
             .iter()
-            .all(|existing| existing.id != prompt.id)
+            .any(|existing| existing.id == prompt.id)
         {
             settings.post_process_prompts.push(prompt);
             changed = true;
         }
     }
 
+    let mut seen_fallbacks = std::collections::HashSet::new();
+    let before = settings.post_process_fallbacks.len();
+    let providers = &settings.post_process_providers;
+    settings.post_process_fallbacks.retain(|fallback| {
+        providers.iter().any(|p| p.id == fallback.provider_id)
+            && !fallback.model.trim().is_empty()
+            && seen_fallbacks.insert((fallback.provider_id.clone(), fallback.model.clone()))
+    });
+    if settings.post_process_fallbacks.len() != before {
+        changed = true;
+    }
+
+    let clamped_timeout = settings.post_process_timeout_ms.clamp(1000, 120_000);
+    if settings.post_process_timeout_ms != clamped_timeout {
+        settings.post_process_timeout_ms = clamped_timeout;
+        changed = true;
+    }
+
     changed
 }
 
@@ -801,11 +1138,166 @@ fn ensure_jargon_pack_defaults(settings: &mut AppSettings) -> bool {
         changed = true;
     }
 
+    let clamped_router_timeout = settings.intent_router_timeout_ms.clamp(25, 2000);
+    if settings.intent_router_timeout_ms != clamped_router_timeout {
+        settings.intent_router_timeout_ms = clamped_router_timeout;
+        changed = true;
+    }
+
+    let clamped_confidence = settings.intent_router_min_confidence.clamp(0.0, 1.0);
+    if (settings.intent_router_min_confidence - clamped_confidence).abs() > f32::EPSILON {
+        settings.intent_router_min_confidence = clamped_confidence;
+        changed = true;
+    }
+
+    let clamped_reranker_timeout = settings.reranker_timeout_ms.clamp(25, 2000);
+    if settings.reranker_timeout_ms != clamped_reranker_timeout {
+        settings.reranker_timeout_ms = clamped_reranker_timeout;
+        changed = true;
+    }
+
+    let clamped_silence = crate::live_segments::clamp_silence_ms(settings.segment_on_silence_ms);
+    if settings.segment_on_silence_ms != clamped_silence {
+        settings.segment_on_silence_ms = clamped_silence;
+        changed = true;
+    }
+
     changed
 }
 
+fn ensure_pipeline_stage_defaults(settings: &mut AppSettings) -> bool {
+    let mut changed = false;
+    let mut seen_ids = std::collections::HashSet::new();
+    let before = settings.pipeline_stages.len();
+    settings.pipeline_stages.retain(|stage| {
+        crate::pipeline_stages::is_known_stage(&stage.id) && seen_ids.insert(stage.id.clone())
+    });
+    if settings.pipeline_stages.len() != before {
+        changed = true;
+    }
+
+    // Insert newly added built-in stages right after their default predecessor
+    // so user ordering is kept and new stages land where they were designed to run.
+    let builtin_ids = crate::pipeline_stages::BUILTIN_STAGE_IDS;
+    for (index, id) in builtin_ids.iter().enumerate() {
+        if settings.pipeline_stages.iter().any(|stage| stage.id == *id) {
+            continue;
+        }
+        let insert_at = builtin_ids[..index]
+            .iter()
+            .rev()
+            .find_map(|prev| {
+                settings
+                    .pipeline_stages
+                    .iter()
+                    .position(|stage| stage.id == *prev)
+            })
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        settings.pipeline_stages.insert(
+            insert_at,
+            PipelineStageConfig {
+                id: id.to_string(),
+                enabled: true,
+            },
+        );
+        changed = true;
+    }
+
+    changed
+}
+
+fn ensure_voice_command_defaults(settings: &mut AppSettings) -> bool {
+    let mut changed = false;
+    let mut seen_kinds = std::collections::HashSet::new();
+    let before = settings.voice_commands.len();
+    settings
+        .voice_commands
+        .retain(|command| seen_kinds.insert(command.kind));
+    if settings.voice_commands.len() != before {
+        changed = true;
+    }
+
+    for command in &mut settings.voice_commands {
+        let mut seen_phrases = std::collections::HashSet::new();
+        let phrases: Vec<String> = command
+            .phrases
+            .iter()
+            .map(|phrase| crate::voice_commands::normalize_phrase(phrase))
+            .filter(|phrase| !phrase.is_empty() && seen_phrases.insert(phrase.clone()))
+            .collect();
+        if phrases != command.phrases {
+            command.phrases = phrases;
+            changed = true;
+        }
+    }
+
+    // Commands added in newer versions start out with their default phrases.
+    for kind in VoiceCommandKind::ALL {
+        if !seen_kinds.contains(&kind) {
+            settings
+                .voice_commands
+                .push(VoiceCommandConfig::with_default_phrases(kind));
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Drop templates that could never be spoken (no name) or that repeat an
+/// earlier id, and normalise names so they match transcribed words.
+fn ensure_template_defaults(settings: &mut AppSettings) -> bool {
+    let mut changed = false;
+    let mut seen_ids = std::collections::HashSet::new();
+    let before = settings.templates.len();
+    settings.templates.retain(|template| {
+        !crate::voice_commands::normalize_phrase(&template.name).is_empty()
+            && seen_ids.insert(template.id.clone())
+    });
+    if settings.templates.len() != before {
+        changed = true;
+    }
+
+    for template in &mut settings.templates {
+        let name = crate::voice_commands::normalize_phrase(&template.name);
+        if name != template.name {
+            template.name = name;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Drop binding and app assignments that point at a preset which no longer
+/// exists.
+fn ensure_style_preset_defaults(settings: &mut AppSettings) -> bool {
+    let ids: std::collections::HashSet<String> = settings
+        .style_presets
+        .iter()
+        .map(|preset| preset.id.clone())
+        .collect();
+
+    let bindings_before = settings.style_preset_bindings.len();
+    settings
+        .style_preset_bindings
+        .retain(|_, preset_id| ids.contains(preset_id));
+    let rules_before = settings.style_preset_app_rules.len();
+    settings
+        .style_preset_app_rules
+        .retain(|rule| !rule.app.trim().is_empty() && ids.contains(&rule.preset_id));
+
+    settings.style_preset_bindings.len() != bindings_before
+        || settings.style_preset_app_rules.len() != rules_before
+}
+
 pub const SETTINGS_STORE_PATH: &str = "settings_store.json";
-const SETTINGS_SCHEMA_VERSION: u32 = 1;
+/// Version 2 added routing fields to post-process prompts, version 3 the
+/// `restructures` flag.
+const SETTINGS_SCHEMA_VERSION: u32 = 3;
+
+static SETTINGS_REVISION: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct PersistedSettings {
@@ -813,11 +1305,13 @@ struct PersistedSettings {
     settings: AppSettings,
 }
 
-fn decode_settings(value: serde_json::Value) -> Result<(AppSettings, bool), serde_json::Error> {
+/// Decode stored settings and the schema version they were written with;
+/// 0 for the unversioned legacy payload.
+fn decode_settings(value: serde_json::Value) -> Result<(AppSettings, u32), serde_json::Error> {
     if let Ok(persisted) = serde_json::from_value::<PersistedSettings>(value.clone()) {
-        return Ok((persisted.settings, true));
+        return Ok((persisted.settings, persisted.schema_version));
     }
-    serde_json::from_value::<AppSettings>(value).map(|settings| (settings, false))
+    serde_json::from_value::<AppSettings>(value).map(|settings| (settings, 0))
 }
 
 fn encode_settings(settings: &AppSettings) -> serde_json::Value {
@@ -841,8 +1335,59 @@ fn merge_default_bindings(settings: &mut AppSettings) -> bool {
     updated
 }
 
-fn migrate_settings(settings: &mut AppSettings) -> bool {
+/// Built-in prompts saved before routing fields existed (schema version 1
+/// and older) pick up their default triggers. Runs once, so triggers the
+/// user cleared later stay cleared.
+fn backfill_builtin_prompt_triggers(settings: &mut AppSettings) -> bool {
+    let mut changed = false;
+    for prompt in builtin_post_process_prompts() {
+        let Some(existing) = settings
+            .post_process_prompts
+            .iter_mut()
+            .find(|existing| existing.id == prompt.id)
+        else {
+            continue;
+        };
+        if existing.triggers.is_empty()
+            && existing.negative_keywords.is_empty()
+            && existing.apps.is_empty()
+            && existing.priority == 0
+            && !prompt.triggers.is_empty()
+        {
+            existing.triggers = prompt.triggers;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Built-in prompts saved before schema version 3 pick up their
+/// `restructures` flag, so the divergence guard does not reject their output.
+fn backfill_builtin_prompt_restructures(settings: &mut AppSettings) -> bool {
     let mut changed = false;
+    for prompt in builtin_post_process_prompts() {
+        if let Some(existing) = settings
+            .post_process_prompts
+            .iter_mut()
+            .find(|existing| existing.id == prompt.id && !existing.restructures)
+        {
+            if prompt.restructures {
+                existing.restructures = true;
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+fn migrate_settings(settings: &mut AppSettings, from_version: u32) -> bool {
+    let mut changed = false;
+    if from_version < 2 && backfill_builtin_prompt_triggers(settings) {
+        changed = true;
+    }
+    if from_version < 3 && backfill_builtin_prompt_restructures(settings) {
+        changed = true;
+    }
     if merge_default_bindings(settings) {
         changed = true;
     }
@@ -852,6 +1397,18 @@ fn migrate_settings(settings: &mut AppSettings) -> bool {
     if ensure_jargon_pack_defaults(settings) {
         changed = true;
     }
+    if ensure_pipeline_stage_defaults(settings) {
+        changed = true;
+    }
+    if ensure_voice_command_defaults(settings) {
+        changed = true;
+    }
+    if ensure_template_defaults(settings) {
+        changed = true;
+    }
+    if ensure_style_preset_defaults(settings) {
+        changed = true;
+    }
     changed
 }
 
@@ -896,6 +1453,21 @@ pub fn get_default_settings() -> AppSettings {
             current_binding: default_post_process_shortcut.to_string(),
         },
     );
+    #[cfg(target_os = "macos")]
+    let default_cli_shortcut = "option+ctrl+space";
+    #[cfg(not(target_os = "macos"))]
+    let default_cli_shortcut = "ctrl+alt+space";
+
+    bindings.insert(
+        "transcribe_cli".to_string(),
+        ShortcutBinding {
+            id: "transcribe_cli".to_string(),
+            name: "Transcribe Shell Command".to_string(),
+            description: "Converts your speech into a shell command.".to_string(),
+            default_binding: default_cli_shortcut.to_string(),
+            current_binding: default_cli_shortcut.to_string(),
+        },
+    );
     bindings.insert(
         "cancel".to_string(),
         ShortcutBinding {
@@ -906,6 +1478,37 @@ pub fn get_default_settings() -> AppSettings {
             current_binding: "escape".to_string(),
         },
     );
+    #[cfg(target_os = "macos")]
+    let default_undo_shortcut = "option+shift+z";
+    #[cfg(not(target_os = "macos"))]
+    let default_undo_shortcut = "ctrl+alt+shift+z";
+
+    bindings.insert(
+        "undo_last_dictation".to_string(),
+        ShortcutBinding {
+            id: "undo_last_dictation".to_string(),
+            name: "Undo Last Dictation".to_string(),
+            description: "Removes the text inserted by the last dictation.".to_string(),
+            default_binding: default_undo_shortcut.to_string(),
+            current_binding: default_undo_shortcut.to_string(),
+        },
+    );
+    #[cfg(target_os = "macos")]
+    let default_placeholder_shortcut = "option+shift+n";
+    #[cfg(not(target_os = "macos"))]
+    let default_placeholder_shortcut = "ctrl+alt+shift+n";
+
+    bindings.insert(
+        "template_next_placeholder".to_string(),
+        ShortcutBinding {
+            id: "template_next_placeholder".to_string(),
+            name: "Next Template Placeholder".to_string(),
+            description: "Moves to the next unfilled placeholder of the last inserted template."
+                .to_string(),
+            default_binding: default_placeholder_shortcut.to_string(),
+            current_binding: default_placeholder_shortcut.to_string(),
+        },
+    );
 
     AppSettings {
         bindings,
@@ -929,6 +1532,8 @@ pub fn get_default_settings() -> AppSettings {
         custom_words: Vec::new(),
         model_unload_timeout: ModelUnloadTimeout::Never,
         word_correction_threshold: default_word_correction_threshold(),
+        phonetic_matching_enabled: default_phonetic_matching_enabled(),
+        phonetic_match_threshold: default_phonetic_match_threshold(),
         history_limit: default_history_limit(),
         recording_retention_period: default_recording_retention_period(),
         paste_method: PasteMethod::default(),
@@ -943,8 +1548,26 @@ pub fn get_default_settings() -> AppSettings {
         post_process_models: default_post_process_models(),
         post_process_prompts: default_post_process_prompts(),
         post_process_selected_prompt_id: None,
+        post_process_streaming: false,
+        post_process_fallbacks: Vec::new(),
+        post_process_timeout_ms: default_post_process_timeout_ms(),
+        post_process_divergence_guard: default_post_process_divergence_guard(),
+        local_cleanup_model_dir: None,
+        voice_commands_enabled: false,
+        voice_commands: default_voice_commands(),
+        templates_enabled: false,
+        templates: default_templates(),
+        template_llm_fill: false,
+        snippets_enabled: false,
+        snippets: Vec::new(),
+        style_presets: default_style_presets(),
+        style_preset_bindings: HashMap::new(),
+        style_preset_app_rules: Vec::new(),
+        cli_mode_enabled: false,
+        cli_mode_llm_assist: false,
+        cli_mode_deny_patterns: Vec::new(),
         mute_while_recording: false,
-        audio_segment_size_seconds: default_audio_segment_size_seconds(),
+        segment_on_silence_ms: 0,
         append_trailing_space: false,
         app_language: default_app_language(),
         experimental_enabled: false,
@@ -963,7 +1586,22 @@ pub fn get_default_settings() -> AppSettings {
         domain_selector_min_score: default_domain_selector_min_score(),
         domain_selector_hysteresis: default_domain_selector_hysteresis(),
         domain_selector_blend_manual_profiles: default_domain_selector_blend_manual_profiles(),
+        domain_selector_learned_weights: false,
+        domain_selector_profile_feedback: HashMap::new(),
         jargon_packs: Vec::new(),
+        jargon_sources: Vec::new(),
+        workspace_jargon_enabled: false,
+        jargon_rejected_proposals: Vec::new(),
+        intent_router_enabled: false,
+        intent_router_model_dir: None,
+        intent_router_timeout_ms: default_intent_router_timeout_ms(),
+        intent_router_min_confidence: default_intent_router_min_confidence(),
+        reranker_enabled: false,
+        reranker_model_dir: None,
+        reranker_second_prompt_id: None,
+        reranker_timeout_ms: default_reranker_timeout_ms(),
+        pipeline_stages: default_pipeline_stages(),
+        revision: 0,
     }
 }
 
@@ -974,6 +1612,38 @@ impl AppSettings {
             .find(|provider| provider.id == self.post_process_provider_id)
     }
 
+    /// Providers and models to try for post-processing, in order: the active
+    /// provider with its configured model, then each configured fallback.
+    /// Unknown providers, empty models and repeated pairs are skipped.
+    pub fn post_process_attempt_targets(&self) -> Vec<(&PostProcessProvider, String)> {
+        let primary = self.active_post_process_provider().map(|provider| {
+            let model = self
+                .post_process_models
+                .get(&provider.id)
+                .cloned()
+                .unwrap_or_default();
+            (provider, model)
+        });
+        let fallbacks = self.post_process_fallbacks.iter().filter_map(|fallback| {
+            self.post_process_provider(&fallback.provider_id)
+                .map(|provider| (provider, fallback.model.clone()))
+        });
+
+        let mut targets: Vec<(&PostProcessProvider, String)> = Vec::new();
+        for (provider, model) in primary.into_iter().chain(fallbacks) {
+            let model = model.trim().to_string();
+            if model.is_empty()
+                || targets
+                    .iter()
+                    .any(|(p, m)| p.id == provider.id && *m == model)
+            {
+                continue;
+            }
+            targets.push((provider, model));
+        }
+        targets
+    }
+
     pub fn post_process_provider(&self, provider_id: &str) -> Option<&PostProcessProvider> {
         self.post_process_providers
             .iter()
@@ -996,44 +1666,48 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
 
-    let (mut settings, was_versioned) = if let Some(settings_value) = store.get("settings") {
+    let (mut settings, version) = if let Some(settings_value) = store.get("settings") {
         match decode_settings(settings_value) {
-            Ok((settings, was_versioned)) => (settings, was_versioned),
+            Ok((settings, version)) => (settings, version),
             Err(e) => {
                 warn!("Failed to parse settings: {}", e);
-                (get_default_settings(), false)
+                (get_default_settings(), 0)
             }
         }
     } else {
-        (get_default_settings(), false)
+        (get_default_settings(), 0)
     };
 
-    let migrated = migrate_settings(&mut settings);
-    if migrated || !was_versioned {
+    let migrated = migrate_settings(&mut settings, version);
+    if migrated || version < SETTINGS_SCHEMA_VERSION {
         store.set("settings", encode_settings(&settings));
     }
     settings
 }
 
 pub fn get_settings(app: &AppHandle) -> AppSettings {
+    // Read before the store so a concurrent write can only make the
+    // revision look older than the contents, never newer.
+    let revision = SETTINGS_REVISION.load(Ordering::Acquire);
     let store = app
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
 
-    let (mut settings, was_versioned) = if let Some(settings_value) = store.get("settings") {
+    let (mut settings, version) = if let Some(settings_value) = store.get("settings") {
         match decode_settings(settings_value) {
-            Ok((settings, was_versioned)) => (settings, was_versioned),
-            Err(_) => (get_default_settings(), false),
+            Ok((settings, version)) => (settings, version),
+            Err(_) => (get_default_settings(), 0),
         }
     } else {
-        (get_default_settings(), false)
+        (get_default_settings(), 0)
     };
 
-    let migrated = migrate_settings(&mut settings);
-    if migrated || !was_versioned {
+    let migrated = migrate_settings(&mut settings, version);
+    if migrated || version < SETTINGS_SCHEMA_VERSION {
         store.set("settings", encode_settings(&settings));
     }
 
+    settings.revision = revision;
     settings
 }
 
@@ -1043,6 +1717,7 @@ pub fn write_settings(app: &AppHandle, settings: AppSettings) {
         .expect("Failed to initialize store");
 
     store.set("settings", encode_settings(&settings));
+    SETTINGS_REVISION.fetch_add(1, Ordering::AcqRel);
 }
 
 pub fn get_bindings(app: &AppHandle) -> HashMap<String, ShortcutBinding> {
@@ -1084,8 +1759,8 @@ mod tests {
     fn decode_legacy_settings_payload() {
         let settings = get_default_settings();
         let value = serde_json::to_value(&settings).expect("serialize legacy settings");
-        let (decoded, was_versioned) = decode_settings(value).expect("decode settings");
-        assert!(!was_versioned);
+        let (decoded, version) = decode_settings(value).expect("decode settings");
+        assert_eq!(version, 0);
         assert_eq!(decoded.selected_model, settings.selected_model);
     }
 
@@ -1093,8 +1768,208 @@ mod tests {
     fn decode_versioned_settings_payload() {
         let settings = get_default_settings();
         let value = encode_settings(&settings);
-        let (decoded, was_versioned) = decode_settings(value).expect("decode settings");
-        assert!(was_versioned);
+        let (decoded, version) = decode_settings(value).expect("decode settings");
+        assert_eq!(version, SETTINGS_SCHEMA_VERSION);
         assert_eq!(decoded.bindings.len(), settings.bindings.len());
     }
+
+    #[test]
+    fn builtin_prompt_triggers_are_backfilled_only_once() {
+        let mut settings = get_default_settings();
+        for prompt in settings.post_process_prompts.iter_mut() {
+            prompt.triggers.clear();
+        }
+
+        // Cleared by the user on the current schema: left alone.
+        let mut current = settings.clone();
+        migrate_settings(&mut current, SETTINGS_SCHEMA_VERSION);
+        assert!(current
+            .post_process_prompts
+            .iter()
+            .all(|prompt| prompt.triggers.is_empty()));
+
+        // Saved before routing fields existed: defaults filled in.
+        assert!(migrate_settings(&mut settings, 1));
+        assert!(settings
+            .post_process_prompts
+            .iter()
+            .any(|prompt| !prompt.triggers.is_empty()));
+    }
+
+    #[test]
+    fn builtin_prompt_restructures_is_backfilled_from_version_2() {
+        let mut settings = get_default_settings();
+        for prompt in settings.post_process_prompts.iter_mut() {
+            prompt.restructures = false;
+        }
+
+        let mut current = settings.clone();
+        migrate_settings(&mut current, SETTINGS_SCHEMA_VERSION);
+        assert!(current
+            .post_process_prompts
+            .iter()
+            .all(|prompt| !prompt.restructures));
+
+        assert!(migrate_settings(&mut settings, 2));
+        let restructures = |id: &str| {
+            settings
+                .post_process_prompts
+                .iter()
+                .find(|prompt| prompt.id == id)
+                .map(|prompt| prompt.restructures)
+        };
+        assert_eq!(restructures("default_meeting_notes"), Some(true));
+        assert_eq!(restructures("default_improve_transcriptions"), Some(false));
+    }
+
+    #[test]
+    fn pipeline_stage_migration_keeps_order_and_restores_missing_stages() {
+        let mut settings = get_default_settings();
+        settings.pipeline_stages = vec![
+            PipelineStageConfig {
+                id: crate::pipeline_stages::AT_FILE_EXPANSION_STAGE_ID.to_string(),
+                enabled: true,
+            },
+            PipelineStageConfig {
+                id: "removed_stage".to_string(),
+                enabled: true,
+            },
+            PipelineStageConfig {
+                id: crate::pipeline_stages::CHINESE_VARIANT_STAGE_ID.to_string(),
+                enabled: false,
+            },
+        ];
+
+        assert!(ensure_pipeline_stage_defaults(&mut settings));
+        let ids: Vec<&str> = settings
+            .pipeline_stages
+            .iter()
+            .map(|stage| stage.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                crate::pipeline_stages::AT_FILE_EXPANSION_STAGE_ID,
+                crate::pipeline_stages::CHINESE_VARIANT_STAGE_ID,
+                crate::pipeline_stages::SNIPPET_EXPANSION_STAGE_ID,
+                crate::pipeline_stages::LLM_POST_PROCESS_STAGE_ID,
+                crate::pipeline_stages::STYLE_PRESET_STAGE_ID,
+            ]
+        );
+        assert!(!settings.pipeline_stages[1].enabled);
+        assert!(!ensure_pipeline_stage_defaults(&mut settings));
+    }
+
+    #[test]
+    fn post_process_attempt_targets_put_active_provider_first() {
+        let mut settings = get_default_settings();
+        settings.post_process_provider_id = "groq".to_string();
+        settings
+            .post_process_models
+            .insert("groq".to_string(), "llama-3.1-8b-instant".to_string());
+        settings.post_process_fallbacks = vec![
+            PostProcessFallback {
+                provider_id: "groq".to_string(),
+                model: "llama-3.1-8b-instant".to_string(),
+            },
+            PostProcessFallback {
+                provider_id: "openrouter".to_string(),
+                model: "openai/gpt-4o-mini".to_string(),
+            },
+            PostProcessFallback {
+                provider_id: "missing".to_string(),
+                model: "any".to_string(),
+            },
+        ];
+
+        let targets = settings.post_process_attempt_targets();
+        let pairs: Vec<(&str, &str)> = targets
+            .iter()
+            .map(|(provider, model)| (provider.id.as_str(), model.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("groq", "llama-3.1-8b-instant"),
+                ("openrouter", "openai/gpt-4o-mini")
+            ]
+        );
+    }
+
+    #[test]
+    fn post_process_defaults_restore_builtin_wire_protocols() {
+        let mut settings = get_default_settings();
+        for provider in settings.post_process_providers.iter_mut() {
+            provider.wire_protocol = WireProtocol::OpenaiChat;
+        }
+        settings
+            .post_process_provider_mut("custom")
+            .unwrap()
+            .wire_protocol = WireProtocol::AnthropicMessages;
+
+        assert!(ensure_post_process_defaults(&mut settings));
+        assert_eq!(
+            settings
+                .post_process_provider("anthropic")
+                .unwrap()
+                .wire_protocol,
+            WireProtocol::AnthropicMessages
+        );
+        assert_eq!(
+            settings
+                .post_process_provider("custom")
+                .unwrap()
+                .wire_protocol,
+            WireProtocol::AnthropicMessages
+        );
+    }
+
+    #[test]
+    fn post_process_defaults_drop_invalid_fallbacks_and_clamp_timeout() {
+        let mut settings = get_default_settings();
+        settings.post_process_timeout_ms = 10;
+        settings.post_process_fallbacks = vec![
+            PostProcessFallback {
+                provider_id: "openrouter".to_string(),
+                model: " ".to_string(),
+            },
+            PostProcessFallback {
+                provider_id: "missing".to_string(),
+                model: "any".to_string(),
+            },
+            PostProcessFallback {
+                provider_id: "cerebras".to_string(),
+                model: "llama3.1-8b".to_string(),
+            },
+        ];
+
+        assert!(ensure_post_process_defaults(&mut settings));
+        assert_eq!(settings.post_process_timeout_ms, 1000);
+        assert_eq!(settings.post_process_fallbacks.len(), 1);
+        assert_eq!(settings.post_process_fallbacks[0].provider_id, "cerebras");
+    }
+
+    #[test]
+    fn voice_command_migration_restores_missing_commands_and_normalizes_phrases() {
+        let mut settings = get_default_settings();
+        assert!(!ensure_voice_command_defaults(&mut settings));
+
+        settings.voice_commands = vec![
+            VoiceCommandConfig {
+                kind: VoiceCommandKind::NewLine,
+                phrases: vec![
+                    "  Next  Line ".to_string(),
+                    "next line".to_string(),
+                    "".to_string(),
+                ],
+                enabled: false,
+            },
+            VoiceCommandConfig::with_default_phrases(VoiceCommandKind::NewLine),
+        ];
+
+        assert!(ensure_voice_command_defaults(&mut settings));
+        assert_eq!(settings.voice_commands.len(), VoiceCommandKind::ALL.len());
+        assert_eq!(settings.voice_commands[0].phrases, vec!["next line"]);
+        assert!(!settings.voice_commands[0].enabled);
+    }
 }
@@ -12,11 +12,15 @@ use crate::managers::audio::AudioRecordingManager;
 use crate::settings::get_settings;
 use crate::ManagedToggleState;
 
+/// Bindings whose action runs once per key press.
+const ONE_SHOT_BINDINGS: &[&str] = &["undo_last_dictation", "template_next_placeholder"];
+
 /// Handle a shortcut event from either implementation.
 ///
 /// This function contains the shared logic for:
 /// - Looking up the action in ACTION_MAP
 /// - Handling the cancel binding (only fires when recording)
+/// - Handling one-shot bindings such as undo (fire on press, in any mode)
 /// - Handling push-to-talk mode (start on press, stop on release)
 /// - Handling toggle mode (toggle state on press only)
 ///
@@ -50,6 +54,14 @@ pub fn handle_shortcut_event(
         return;
     }
 
+    // One-shot bindings, unaffected by push-to-talk or toggling
+    if ONE_SHOT_BINDINGS.contains(&binding_id) {
+        if is_pressed {
+            action.start(app, binding_id, hotkey_string);
+        }
+        return;
+    }
+
     // Push-to-talk mode: start on press, stop on release
     if settings.push_to_talk {
         if is_pressed {
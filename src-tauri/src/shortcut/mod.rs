@@ -17,15 +17,29 @@ mod tauri_impl;
 use log::{error, info, warn};
 use serde::Serialize;
 use specta::Type;
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 
+use crate::cleanup::{self, CleanupManager};
+use crate::config_pack::{self, ConfigPackImportSummary, ConfigPackPreview, ImportStrategy};
+use crate::jargon_sources::JargonSource;
+use crate::managers::domain_selector::{
+    DomainContext, DomainSelectorManager, PromptRouteExplanation, MAX_PROMPT_PRIORITY,
+};
+use crate::managers::jargon_sources::{JargonSourceManager, JargonSourceStatus};
 use crate::settings::{
     self, get_settings, AutoSubmitKey, ClipboardHandling, JargonPack, KeyboardImplementation,
-    LLMPrompt, OverlayPosition, PasteMethod, ShortcutBinding, SoundTheme, TypingTool,
-    APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
+    LLMPrompt, OverlayPosition, PasteMethod, PipelineStageConfig, PostProcessFallback,
+    ShortcutBinding, SoundTheme, TypingTool, WireProtocol, APPLE_INTELLIGENCE_DEFAULT_MODEL_ID,
+    APPLE_INTELLIGENCE_PROVIDER_ID, LOCAL_CLEANUP_DEFAULT_MODEL_ID, LOCAL_CLEANUP_PROVIDER_ID,
 };
+use crate::snippets::{self, Snippet};
+use crate::style_presets::{self, StylePreset, StylePresetAppRule};
+use crate::templates::{self, TextTemplate};
+use crate::text_model;
 use crate::tray;
+use crate::voice_commands::{self, VoiceCommandConfig};
 
 // Note: Commands are accessed via shortcut::handy_keys:: in lib.rs
 
@@ -397,6 +411,10 @@ fn register_all_shortcuts_for_implementation(
         if id == "transcribe_with_post_process" && !current_settings.post_process_enabled {
             continue;
         }
+        // Skip CLI shortcut when CLI mode is disabled
+        if id == "transcribe_cli" && !current_settings.cli_mode_enabled {
+            continue;
+        }
 
         let mut binding = current_settings
             .bindings
@@ -659,6 +677,30 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_phonetic_matching_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.phonetic_matching_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_phonetic_match_threshold_setting(
+    app: AppHandle,
+    threshold: f64,
+) -> Result<(), String> {
+    if !(0.5..=1.0).contains(&threshold) {
+        return Err("Phonetic match threshold must be between 0.5 and 1.0".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.phonetic_match_threshold = threshold;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
@@ -795,6 +837,329 @@ pub fn change_post_process_auto_prompt_selection_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_streaming_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.post_process_streaming = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_divergence_guard_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.post_process_divergence_guard = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_voice_commands_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.voice_commands_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_voice_commands(
+    app: AppHandle,
+    mut commands: Vec<VoiceCommandConfig>,
+) -> Result<(), String> {
+    let mut seen_phrases = std::collections::HashSet::new();
+    for command in &mut commands {
+        for phrase in &mut command.phrases {
+            let normalized = voice_commands::normalize_phrase(phrase);
+            if normalized.is_empty() {
+                return Err(format!("Empty phrase for voice command {:?}", command.kind));
+            }
+            if normalized.split(' ').next() == Some(voice_commands::ESCAPE_WORD) {
+                return Err(format!(
+                    "Voice command phrase '{}' cannot start with '{}'",
+                    phrase,
+                    voice_commands::ESCAPE_WORD
+                ));
+            }
+            if !seen_phrases.insert(normalized.clone()) {
+                return Err(format!(
+                    "Voice command phrase '{}' is used more than once",
+                    phrase
+                ));
+            }
+            *phrase = normalized;
+        }
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.voice_commands = commands;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_templates_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.templates_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_template_llm_fill_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.template_llm_fill = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_templates(app: AppHandle, templates: Vec<TextTemplate>) -> Result<(), String> {
+    let templates = templates::validate_templates(templates)?;
+    let mut settings = settings::get_settings(&app);
+    settings.templates = templates;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_snippets_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.snippets_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_snippet(app: AppHandle, trigger: String, body: String) -> Result<Snippet, String> {
+    let mut settings = settings::get_settings(&app);
+    let trigger = snippets::validate_new_trigger(&trigger, &settings.snippets, None)?;
+    if body.trim().is_empty() {
+        return Err("Snippet text cannot be empty".to_string());
+    }
+
+    let snippet = Snippet {
+        id: format!("snippet_{}", chrono::Utc::now().timestamp_millis()),
+        trigger,
+        body,
+        enabled: true,
+    };
+    settings.snippets.push(snippet.clone());
+    settings::write_settings(&app, settings);
+
+    Ok(snippet)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_snippet(
+    app: AppHandle,
+    id: String,
+    trigger: String,
+    body: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let trigger = snippets::validate_new_trigger(&trigger, &settings.snippets, Some(&id))?;
+    if body.trim().is_empty() {
+        return Err("Snippet text cannot be empty".to_string());
+    }
+
+    let snippet = settings
+        .snippets
+        .iter_mut()
+        .find(|snippet| snippet.id == id)
+        .ok_or_else(|| format!("Snippet with id '{}' not found", id))?;
+    snippet.trigger = trigger;
+    snippet.body = body;
+    snippet.enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_snippet(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let original_len = settings.snippets.len();
+    settings.snippets.retain(|snippet| snippet.id != id);
+    if settings.snippets.len() == original_len {
+        return Err(format!("Snippet with id '{}' not found", id));
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_style_presets(app: AppHandle, presets: Vec<StylePreset>) -> Result<(), String> {
+    let presets = style_presets::validate_presets(presets)?;
+    let mut settings = settings::get_settings(&app);
+    settings.style_presets = presets;
+    // Forget assignments to presets that were just removed.
+    let ids: Vec<&str> = settings
+        .style_presets
+        .iter()
+        .map(|preset| preset.id.as_str())
+        .collect();
+    settings
+        .style_preset_bindings
+        .retain(|_, preset_id| ids.contains(&preset_id.as_str()));
+    settings
+        .style_preset_app_rules
+        .retain(|rule| ids.contains(&rule.preset_id.as_str()));
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_style_preset_bindings(
+    app: AppHandle,
+    assignments: std::collections::HashMap<String, String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    for (binding_id, preset_id) in &assignments {
+        if !settings.bindings.contains_key(binding_id) {
+            return Err(format!("Binding '{}' not found", binding_id));
+        }
+        if !settings.style_presets.iter().any(|p| &p.id == preset_id) {
+            return Err(format!("Style preset '{}' not found", preset_id));
+        }
+    }
+    settings.style_preset_bindings = assignments;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_style_preset_app_rules(
+    app: AppHandle,
+    rules: Vec<StylePresetAppRule>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let mut validated = Vec::with_capacity(rules.len());
+    for mut rule in rules {
+        rule.app = rule.app.trim().to_string();
+        if rule.app.is_empty() {
+            return Err("App name cannot be empty".to_string());
+        }
+        if !settings
+            .style_presets
+            .iter()
+            .any(|p| p.id == rule.preset_id)
+        {
+            return Err(format!("Style preset '{}' not found", rule.preset_id));
+        }
+        validated.push(rule);
+    }
+    settings.style_preset_app_rules = validated;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_cli_mode_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.cli_mode_enabled = enabled;
+    settings::write_settings(&app, settings.clone());
+
+    // Register or unregister the CLI shortcut
+    if let Some(binding) = settings.bindings.get("transcribe_cli").cloned() {
+        if enabled {
+            let _ = register_shortcut(&app, binding);
+        } else {
+            let _ = unregister_shortcut(&app, binding);
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_cli_mode_llm_assist_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.cli_mode_llm_assist = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_cli_mode_deny_patterns(app: AppHandle, patterns: Vec<String>) -> Result<(), String> {
+    let patterns: Vec<String> = patterns
+        .into_iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    for pattern in &patterns {
+        crate::cli_mode::compile_deny_pattern(pattern)?;
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.cli_mode_deny_patterns = patterns;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_post_process_fallbacks(
+    app: AppHandle,
+    fallbacks: Vec<PostProcessFallback>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    for fallback in &fallbacks {
+        if settings
+            .post_process_provider(&fallback.provider_id)
+            .is_none()
+        {
+            return Err(format!("Provider '{}' not found", fallback.provider_id));
+        }
+        if fallback.model.trim().is_empty() {
+            return Err(format!(
+                "Fallback for provider '{}' has no model",
+                fallback.provider_id
+            ));
+        }
+    }
+
+    if fallbacks
+        .iter()
+        .any(|fallback| fallback.provider_id == LOCAL_CLEANUP_PROVIDER_ID)
+    {
+        app.state::<Arc<CleanupManager>>().prefetch();
+    }
+    settings.post_process_fallbacks = fallbacks;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_timeout_ms_setting(
+    app: AppHandle,
+    timeout_ms: u64,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.post_process_timeout_ms = timeout_ms.clamp(1000, 120_000);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_experimental_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -833,6 +1198,64 @@ pub fn change_post_process_base_url_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_local_cleanup_model_dir_setting(
+    app: AppHandle,
+    model_dir: Option<String>,
+) -> Result<(), String> {
+    let model_dir = model_dir
+        .map(|dir| dir.trim().to_string())
+        .filter(|dir| !dir.is_empty());
+
+    if let Some(dir) = &model_dir {
+        let path = std::path::Path::new(dir);
+        if !path.is_dir() {
+            return Err(format!("'{}' is not a directory", dir));
+        }
+        let missing = cleanup::missing_model_files(path);
+        if !missing.is_empty() {
+            return Err(format!(
+                "'{}' is missing cleanup model files: {}",
+                dir,
+                missing.join(", ")
+            ));
+        }
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.local_cleanup_model_dir = model_dir.clone();
+    settings::write_settings(&app, settings);
+
+    app.state::<Arc<CleanupManager>>()
+        .set_local_model_dir(model_dir.map(std::path::PathBuf::from));
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_wire_protocol_setting(
+    app: AppHandle,
+    provider_id: String,
+    wire_protocol: WireProtocol,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let provider = settings
+        .post_process_provider_mut(&provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
+
+    if provider.id != "custom" {
+        return Err(format!(
+            "Provider '{}' does not allow changing the wire protocol",
+            provider.label
+        ));
+    }
+
+    provider.wire_protocol = wire_protocol;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 /// Generic helper to validate provider exists
 fn validate_provider_exists(
     settings: &settings::AppSettings,
@@ -881,6 +1304,10 @@ pub fn change_post_process_model_setting(
 pub fn set_post_process_provider(app: AppHandle, provider_id: String) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
     validate_provider_exists(&settings, &provider_id)?;
+    if provider_id == LOCAL_CLEANUP_PROVIDER_ID {
+        // Fetch the model now rather than failing the first dictations.
+        app.state::<Arc<CleanupManager>>().prefetch();
+    }
     settings.post_process_provider_id = provider_id;
     settings::write_settings(&app, settings);
     Ok(())
@@ -902,6 +1329,7 @@ pub fn add_post_process_prompt(
         id: id.clone(),
         name,
         prompt,
+        ..Default::default()
     };
 
     settings.post_process_prompts.push(new_prompt.clone());
@@ -934,6 +1362,84 @@ pub fn update_post_process_prompt(
     }
 }
 
+/// Set how automatic prompt selection treats a prompt. Blank entries are
+/// dropped and the priority is clamped to the range routing honours.
+#[tauri::command]
+#[specta::specta]
+pub fn update_post_process_prompt_routing(
+    app: AppHandle,
+    id: String,
+    triggers: Vec<String>,
+    negative_keywords: Vec<String>,
+    apps: Vec<String>,
+    priority: i32,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let existing_prompt = settings
+        .post_process_prompts
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Prompt with id '{}' not found", id))?;
+
+    let clean = |entries: Vec<String>| -> Vec<String> {
+        entries
+            .into_iter()
+            .map(|entry| entry.trim().to_string())
+            .filter(|entry| !entry.is_empty())
+            .collect()
+    };
+    existing_prompt.triggers = clean(triggers);
+    existing_prompt.negative_keywords = clean(negative_keywords);
+    existing_prompt.apps = clean(apps);
+    existing_prompt.priority = priority.clamp(-MAX_PROMPT_PRIORITY, MAX_PROMPT_PRIORITY);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Mark whether a prompt restructures the text; see `LLMPrompt::restructures`.
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_prompt_restructures(
+    app: AppHandle,
+    id: String,
+    restructures: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let existing_prompt = settings
+        .post_process_prompts
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Prompt with id '{}' not found", id))?;
+    existing_prompt.restructures = restructures;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Explain which prompt `text` would be routed to, as if dictated in
+/// `app_name`, and why.
+#[tauri::command]
+#[specta::specta]
+pub fn explain_prompt_route(
+    app: AppHandle,
+    text: String,
+    app_name: Option<String>,
+) -> Result<PromptRouteExplanation, String> {
+    let settings = settings::get_settings(&app);
+    let selector = app
+        .try_state::<Arc<DomainSelectorManager>>()
+        .ok_or_else(|| "Domain selector is not available".to_string())?;
+    Ok(selector.explain_prompt_route(
+        &settings,
+        &DomainContext {
+            text,
+            app: app_name
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+        },
+        &settings.post_process_prompts,
+    ))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), String> {
@@ -957,6 +1463,9 @@ pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), Stri
         settings.post_process_selected_prompt_id =
             settings.post_process_prompts.first().map(|p| p.id.clone());
     }
+    if settings.reranker_second_prompt_id.as_ref() == Some(&id) {
+        settings.reranker_second_prompt_id = None;
+    }
 
     settings::write_settings(&app, settings);
     Ok(())
@@ -989,6 +1498,10 @@ pub async fn fetch_post_process_models(
         }
     }
 
+    if provider.id == LOCAL_CLEANUP_PROVIDER_ID {
+        return Ok(vec![LOCAL_CLEANUP_DEFAULT_MODEL_ID.to_string()]);
+    }
+
     // Get API key
     let api_key = settings
         .post_process_api_keys
@@ -1033,13 +1546,9 @@ pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Res
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_audio_segment_size_seconds_setting(
-    app: AppHandle,
-    _seconds: f64,
-) -> Result<(), String> {
-    // Segment-on-silence is disabled; always persist 0.0 for compatibility.
+pub fn change_segment_on_silence_ms_setting(app: AppHandle, ms: u64) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.audio_segment_size_seconds = 0.0;
+    settings.segment_on_silence_ms = crate::live_segments::clamp_silence_ms(ms);
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -1062,11 +1571,45 @@ pub fn change_at_file_expansion_setting(app: AppHandle, enabled: bool) -> Result
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn get_available_pipeline_stages() -> Vec<String> {
+    crate::pipeline_stages::BUILTIN_STAGE_IDS
+        .iter()
+        .map(|id| id.to_string())
+        .collect()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_pipeline_stages(
+    app: AppHandle,
+    stages: Vec<PipelineStageConfig>,
+) -> Result<Vec<PipelineStageConfig>, String> {
+    let mut seen = std::collections::HashSet::new();
+    for stage in &stages {
+        if !crate::pipeline_stages::is_known_stage(&stage.id) {
+            return Err(format!("Unknown pipeline stage '{}'", stage.id));
+        }
+        if !seen.insert(stage.id.clone()) {
+            return Err(format!("Pipeline stage '{}' is listed twice", stage.id));
+        }
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.pipeline_stages = stages;
+    settings::write_settings(&app, settings);
+
+    // Re-read so any built-in stages missing from the request are restored.
+    Ok(settings::get_settings(&app).pipeline_stages)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn update_jargon_profiles(app: AppHandle, profiles: Vec<String>) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.jargon_enabled_profiles = profiles;
+    let previous = std::mem::replace(&mut settings.jargon_enabled_profiles, profiles);
+    crate::managers::domain_selector::record_profile_toggles(&mut settings, &previous);
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -1092,6 +1635,18 @@ pub fn update_jargon_custom_corrections(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn update_workspace_jargon_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.workspace_jargon_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_jargon_builtin_profiles(
@@ -1165,6 +1720,147 @@ pub fn update_domain_selector_blend_manual_profiles_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn update_domain_selector_learned_weights_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.domain_selector_learned_weights = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reset_domain_selector_feedback(app: AppHandle) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.domain_selector_profile_feedback.clear();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_intent_router_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.intent_router_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_intent_router_model_dir_setting(
+    app: AppHandle,
+    model_dir: Option<String>,
+) -> Result<(), String> {
+    let model_dir = text_model::validate_model_dir(model_dir, "intent router")?;
+
+    let mut settings = settings::get_settings(&app);
+    settings.intent_router_model_dir = model_dir;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_intent_router_timeout_ms_setting(
+    app: AppHandle,
+    timeout_ms: u64,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.intent_router_timeout_ms = timeout_ms.clamp(25, 2000);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_intent_router_min_confidence_setting(
+    app: AppHandle,
+    min_confidence: f32,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.intent_router_min_confidence = min_confidence.clamp(0.0, 1.0);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_reranker_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.reranker_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_reranker_model_dir_setting(
+    app: AppHandle,
+    model_dir: Option<String>,
+) -> Result<(), String> {
+    let model_dir = text_model::validate_model_dir(model_dir, "re-ranker")?;
+
+    let mut settings = settings::get_settings(&app);
+    settings.reranker_model_dir = model_dir;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_reranker_second_prompt_setting(
+    app: AppHandle,
+    prompt_id: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    if let Some(id) = &prompt_id {
+        if !settings.post_process_prompts.iter().any(|p| &p.id == id) {
+            return Err(format!("Prompt with id '{}' not found", id));
+        }
+    }
+    settings.reranker_second_prompt_id = prompt_id;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_reranker_timeout_ms_setting(app: AppHandle, timeout_ms: u64) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.reranker_timeout_ms = timeout_ms.clamp(25, 2000);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn accept_jargon_proposals(
+    app: AppHandle,
+    proposals: Vec<crate::jargon_learning::JargonProposal>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    crate::jargon_learning::accept(&mut settings, &proposals);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn reject_jargon_proposals(
+    app: AppHandle,
+    proposals: Vec<crate::jargon_learning::JargonProposal>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    crate::jargon_learning::reject(&mut settings, &proposals);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 fn sanitize_pack(pack: &JargonPack) -> Option<JargonPack> {
     let id = pack.id.trim();
     let label = pack.label.trim();
@@ -1181,8 +1877,7 @@ fn sanitize_pack(pack: &JargonPack) -> Option<JargonPack> {
     let corrections = pack
         .corrections
         .iter()
-        .filter(|item| !item.from.trim().is_empty() && !item.to.trim().is_empty())
-        .cloned()
+        .filter_map(crate::jargon::JargonCorrection::sanitized)
         .collect::<Vec<_>>();
 
     Some(JargonPack {
@@ -1289,6 +1984,93 @@ pub fn export_jargon_packs_json(
         .map_err(|e| format!("Failed to serialize jargon packs: {}", e))
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn preview_config_pack(app: AppHandle, json: String) -> Result<ConfigPackPreview, String> {
+    let pack = config_pack::parse(&json)?;
+    let settings = settings::get_settings(&app);
+    Ok(config_pack::preview(&settings, &pack))
+}
+
+/// Import a config pack. `strategies` overrides `default_strategy` for
+/// individual item keys from `preview_config_pack`. Nothing is written
+/// unless the whole pack imports cleanly.
+#[tauri::command]
+#[specta::specta]
+pub fn import_config_pack(
+    app: AppHandle,
+    json: String,
+    default_strategy: ImportStrategy,
+    strategies: std::collections::HashMap<String, ImportStrategy>,
+) -> Result<ConfigPackImportSummary, String> {
+    let pack = config_pack::parse(&json)?;
+    let settings = settings::get_settings(&app);
+    let (updated, summary) = config_pack::import(&settings, pack, |key| {
+        strategies.get(key).copied().unwrap_or(default_strategy)
+    })?;
+    settings::write_settings(&app, updated);
+    Ok(summary)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn export_config_pack(app: AppHandle) -> Result<String, String> {
+    let settings = settings::get_settings(&app);
+    serde_json::to_string_pretty(&config_pack::export(&settings))
+        .map_err(|e| format!("Failed to serialize config pack: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_jargon_sources(
+    app: AppHandle,
+    sources: Vec<JargonSource>,
+) -> Result<Vec<JargonSourceStatus>, String> {
+    let mut settings = settings::get_settings(&app);
+    let mut ids = std::collections::HashSet::new();
+    let mut validated = Vec::with_capacity(sources.len());
+    for mut source in sources {
+        source.id = source.id.trim().to_string();
+        source.label = source.label.trim().to_string();
+        source.path = source.path.trim().to_string();
+        if source.id.is_empty() || source.label.is_empty() || source.path.is_empty() {
+            return Err("Jargon sources need an id, a label and a path".to_string());
+        }
+        if !ids.insert(source.id.clone()) {
+            return Err(format!("Duplicate jargon source id '{}'", source.id));
+        }
+        if let Some(binding_id) = source
+            .binding_ids
+            .iter()
+            .find(|id| !settings.bindings.contains_key(*id))
+        {
+            return Err(format!("Binding '{}' not found", binding_id));
+        }
+        if let Some(prompt_id) = source.prompt_ids.iter().find(|id| {
+            !settings
+                .post_process_prompts
+                .iter()
+                .any(|prompt| &prompt.id == *id)
+        }) {
+            return Err(format!("Prompt '{}' not found", prompt_id));
+        }
+        validated.push(source);
+    }
+    settings.jargon_sources = validated;
+    settings::write_settings(&app, settings.clone());
+
+    let manager = app.state::<Arc<JargonSourceManager>>();
+    manager.refresh();
+    Ok(manager.statuses(&settings))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_jargon_source_statuses(app: AppHandle) -> Vec<JargonSourceStatus> {
+    let settings = settings::get_settings(&app);
+    app.state::<Arc<JargonSourceManager>>().statuses(&settings)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_app_language_setting(app: AppHandle, language: String) -> Result<(), String> {
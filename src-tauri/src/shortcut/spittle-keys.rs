@@ -437,6 +437,10 @@ pub fn init_shortcuts(app: &AppHandle) -> Result<(), String> {
         if id == "transcribe_with_post_process" && !user_settings.post_process_enabled {
             continue;
         }
+        // Skip CLI shortcut when CLI mode is disabled
+        if id == "transcribe_cli" && !user_settings.cli_mode_enabled {
+            continue;
+        }
 
         let binding = user_settings
             .bindings
@@ -0,0 +1,332 @@
+//! Snippets: spoken trigger phrases ("insert my signature") that expand to
+//! stored text blocks before anything is written out.
+//!
+//! Triggers are matched anywhere in the dictation with the same n-gram,
+//! Levenshtein and Soundex scoring `apply_custom_words` uses, so "insert my
+//! signatures" still fires. Single-word triggers only match exactly. Bodies
+//! may use `{{date}}`, `{{time}}` and `{{clipboard}}`.
+//!
+//! Expansion runs as a pipeline stage ahead of LLM post-processing. The
+//! expanded text is masked while the LLM runs and put back afterwards, so
+//! the model never rewrites it.
+
+use crate::audio_toolkit::text::{build_ngram, find_best_match, word_spans};
+use crate::templates::PLACEHOLDER_RE;
+use crate::voice_commands::normalize_phrase;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Maximum combined score (see `find_best_match`) for a trigger to fire.
+/// Tighter than the custom-word default since a false match inserts a block.
+const MATCH_THRESHOLD: f64 = 0.2;
+
+/// `find_best_match` ignores candidates longer than this.
+const MAX_TRIGGER_CHARS: usize = 50;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct Snippet {
+    pub id: String,
+    /// Phrase that is replaced by `body` when spoken.
+    pub trigger: String,
+    /// Inserted text; may contain `{{date}}`, `{{time}}` and `{{clipboard}}`.
+    pub body: String,
+    #[serde(default = "default_snippet_enabled")]
+    pub enabled: bool,
+}
+
+fn default_snippet_enabled() -> bool {
+    true
+}
+
+/// Normalise a trigger for storage and check it can be matched.
+pub fn validate_trigger(trigger: &str) -> Result<String, String> {
+    let normalized = normalize_phrase(trigger);
+    if normalized.is_empty() {
+        return Err("Snippet trigger cannot be empty".to_string());
+    }
+    if normalized.replace(' ', "").len() > MAX_TRIGGER_CHARS {
+        return Err(format!(
+            "Snippet trigger '{}' is longer than {} characters",
+            trigger, MAX_TRIGGER_CHARS
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Check that `trigger` is valid and not already used by a snippet other
+/// than `except_id`. Returns the normalised trigger.
+pub fn validate_new_trigger(
+    trigger: &str,
+    snippets: &[Snippet],
+    except_id: Option<&str>,
+) -> Result<String, String> {
+    let normalized = validate_trigger(trigger)?;
+    if snippets
+        .iter()
+        .any(|snippet| Some(snippet.id.as_str()) != except_id && snippet.trigger == normalized)
+    {
+        return Err(format!(
+            "Snippet trigger '{}' is already in use",
+            normalized
+        ));
+    }
+    Ok(normalized)
+}
+
+// ============================================================================
+// Expansion
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expansion {
+    pub text: String,
+    /// Rendered bodies in the order they were inserted.
+    pub inserted: Vec<String>,
+}
+
+/// Replace every spoken trigger in `text` with its snippet body. Variables
+/// in bodies are resolved through `variable`; unknown ones are left as is.
+/// Everything around the triggers, line breaks included, is kept as is.
+pub fn expand(
+    text: &str,
+    snippets: &[Snippet],
+    variable: impl Fn(&str) -> Option<String>,
+) -> Expansion {
+    let (active, triggers): (Vec<&Snippet>, Vec<String>) = snippets
+        .iter()
+        .filter(|snippet| snippet.enabled)
+        .map(|snippet| (snippet, normalize_phrase(&snippet.trigger)))
+        .filter(|(_, trigger)| !trigger.is_empty())
+        .unzip();
+    if active.is_empty() {
+        return Expansion {
+            text: text.to_string(),
+            inserted: Vec::new(),
+        };
+    }
+
+    let triggers_nospace: Vec<String> = triggers
+        .iter()
+        .map(|trigger| trigger.replace(' ', ""))
+        .collect();
+    // Allow one extra word for a trigger the transcriber split in two.
+    let max_words = triggers
+        .iter()
+        .map(|trigger| trigger.split(' ').count())
+        .max()
+        .unwrap_or(1)
+        + 1;
+
+    let spans = word_spans(text);
+    let words: Vec<&str> = spans
+        .iter()
+        .map(|&(start, end)| &text[start..end])
+        .collect();
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut inserted = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let matched = (1..=max_words.min(words.len() - i)).rev().find_map(|n| {
+            let candidate = build_ngram(&words[i..i + n]);
+            let (trigger, _) =
+                find_best_match(&candidate, &triggers, &triggers_nospace, MATCH_THRESHOLD)?;
+            let index = triggers.iter().position(|t| t == trigger)?;
+            let single_word = !trigger.contains(' ');
+            if single_word && candidate != triggers_nospace[index] {
+                return None;
+            }
+            Some((index, n))
+        });
+
+        match matched {
+            Some((index, n)) => {
+                let body = render(&active[index].body, &variable);
+                result.push_str(&text[copied..spans[i].0]);
+                result.push_str(&body);
+                copied = spans[i + n - 1].1;
+                inserted.push(body);
+                i += n;
+            }
+            None => i += 1,
+        }
+    }
+    result.push_str(&text[copied..]);
+
+    Expansion {
+        text: result,
+        inserted,
+    }
+}
+
+/// Fill `{{name}}` variables in a snippet body.
+pub fn render(body: &str, variable: &impl Fn(&str) -> Option<String>) -> String {
+    PLACEHOLDER_RE
+        .replace_all(body, |captures: &regex::Captures| {
+            variable(&captures[1].to_lowercase()).unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Resolve the built-in snippet variables.
+pub fn builtin_variable(app: &AppHandle, name: &str) -> Option<String> {
+    let now = chrono::Local::now();
+    match name {
+        "date" => Some(now.format("%Y-%m-%d").to_string()),
+        "time" => Some(now.format("%H:%M").to_string()),
+        "clipboard" => app.clipboard().read_text().ok(),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Protection from LLM rewrites
+// ============================================================================
+
+/// Swap each inserted body in `text` for a placeholder. Returns the masked
+/// text and the placeholder/body pairs needed to restore it.
+pub(crate) fn mask_inserted(text: &str, inserted: &[String]) -> (String, Vec<(String, String)>) {
+    let mut masked = text.to_string();
+    let mut pairs = Vec::new();
+    for body in inserted {
+        if body.is_empty() || !masked.contains(body.as_str()) {
+            continue;
+        }
+        let placeholder = format!("\u{27E6}N{}\u{27E7}", pairs.len()); // ⟦N0⟧, ⟦N1⟧ ...
+        masked = masked.replacen(body.as_str(), &placeholder, 1);
+        pairs.push((placeholder, body.clone()));
+    }
+    (masked, pairs)
+}
+
+/// Put masked bodies back. Fails if the LLM dropped or duplicated one.
+pub(crate) fn restore_inserted(text: &str, pairs: &[(String, String)]) -> Result<String, String> {
+    let mut restored = text.to_string();
+    for (placeholder, body) in pairs {
+        if restored.matches(placeholder.as_str()).count() != 1 {
+            return Err(format!("snippet placeholder {} was not kept", placeholder));
+        }
+        restored = restored.replace(placeholder.as_str(), body);
+    }
+    Ok(restored)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice_commands::{parse, resolve, typed_text, VoiceCommandConfig, VoiceCommandKind};
+
+    fn snippet(trigger: &str, body: &str) -> Snippet {
+        Snippet {
+            id: trigger.replace(' ', "_"),
+            trigger: trigger.to_string(),
+            body: body.to_string(),
+            enabled: true,
+        }
+    }
+
+    fn no_variables(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn triggers_expand_with_fuzzy_matching() {
+        let snippets = vec![
+            snippet("insert my signature", "Best,\nAda"),
+            snippet("standup template", "Yesterday:\nToday:\nBlockers:"),
+        ];
+        let expansion = expand(
+            "Thanks for the help. Insert my signatures.",
+            &snippets,
+            no_variables,
+        );
+        assert_eq!(expansion.text, "Thanks for the help. Best,\nAda");
+        assert_eq!(expansion.inserted, vec!["Best,\nAda".to_string()]);
+
+        let expansion = expand("stand up template", &snippets, no_variables);
+        assert_eq!(expansion.text, "Yesterday:\nToday:\nBlockers:");
+
+        let untouched = expand("insert my name", &snippets, no_variables);
+        assert!(untouched.inserted.is_empty());
+    }
+
+    #[test]
+    fn line_breaks_around_triggers_are_kept() {
+        let snippets = vec![snippet("insert my signature", "Best,\nAda")];
+        let commands: Vec<VoiceCommandConfig> = VoiceCommandKind::ALL
+            .into_iter()
+            .map(VoiceCommandConfig::with_default_phrases)
+            .collect();
+        let dictated = typed_text(&resolve(
+            &parse("first new line insert my signature", &commands),
+            true,
+        ));
+        assert_eq!(dictated, "first\ninsert my signature");
+        assert_eq!(
+            expand(&dictated, &snippets, no_variables).text,
+            "first\nBest,\nAda"
+        );
+
+        let text = "one\n\ntwo  three";
+        assert_eq!(expand(text, &snippets, no_variables).text, text);
+    }
+
+    #[test]
+    fn single_word_triggers_match_exactly() {
+        let mut snippets = vec![snippet("sig", "-- Ada")];
+        assert_eq!(expand("six", &snippets, no_variables).text, "six");
+        assert_eq!(expand("sig", &snippets, no_variables).text, "-- Ada");
+
+        snippets[0].enabled = false;
+        assert_eq!(expand("sig", &snippets, no_variables).text, "sig");
+    }
+
+    #[test]
+    fn variables_are_rendered() {
+        let snippets = vec![snippet(
+            "date stamp",
+            "Today is {{date}}, see {{ Clipboard }} {{other}}",
+        )];
+        let expansion = expand("date stamp", &snippets, |name| match name {
+            "date" => Some("2024-01-02".to_string()),
+            "clipboard" => Some("notes".to_string()),
+            _ => None,
+        });
+        assert_eq!(expansion.text, "Today is 2024-01-02, see notes {{other}}");
+    }
+
+    #[test]
+    fn masked_bodies_survive_or_fail_loudly() {
+        let inserted = vec!["Best,\nAda".to_string()];
+        let (masked, pairs) = mask_inserted("thanks a lot Best,\nAda", &inserted);
+        assert_eq!(masked, "thanks a lot \u{27E6}N0\u{27E7}");
+        assert_eq!(
+            restore_inserted("Thanks a lot. \u{27E6}N0\u{27E7}", &pairs),
+            Ok("Thanks a lot. Best,\nAda".to_string())
+        );
+        assert!(restore_inserted("Thanks a lot.", &pairs).is_err());
+    }
+
+    #[test]
+    fn triggers_are_validated() {
+        let snippets = vec![snippet("insert my signature", "x")];
+        assert_eq!(
+            validate_new_trigger("Standup Template!", &snippets, None),
+            Ok("standup template".to_string())
+        );
+        assert!(validate_new_trigger("Insert my signature", &snippets, None).is_err());
+        assert!(validate_new_trigger(
+            "insert my signature",
+            &snippets,
+            Some("insert_my_signature")
+        )
+        .is_ok());
+        assert!(validate_trigger("  ").is_err());
+    }
+}
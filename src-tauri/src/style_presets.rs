@@ -0,0 +1,668 @@
+//! Style presets: deterministic formatting rules plus optional rewrite
+//! instructions for the LLM.
+//!
+//! The rules (punctuation, capitalization, filler removal, number format and
+//! sentence length) are plain text transforms, so a preset still applies
+//! when no post-processing provider is configured. Rewrite instructions are
+//! appended to the post-processing prompt when the LLM runs.
+//!
+//! A preset is picked per frontmost app first, then per binding. Spans that
+//! `jargon::mask_protected_spans` recognises (paths, URLs, flags, code) are
+//! left untouched, as are placeholders such as inserted snippets.
+
+use crate::jargon;
+use crate::settings::AppSettings;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PunctuationPolicy {
+    #[default]
+    Keep,
+    /// Drop the full stop at the very end, as in chat messages.
+    NoTrailingPeriod,
+    /// Remove sentence punctuation from the ends of words.
+    Strip,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CapitalizationPolicy {
+    #[default]
+    Keep,
+    /// Capitalize the first letter of every sentence.
+    Sentence,
+    Lowercase,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberFormat {
+    #[default]
+    Keep,
+    /// Write numbers from ten up as digits ("twenty five" -> "25").
+    Digits,
+    /// Spell out single digits ("3" -> "three").
+    SpellSmall,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct StylePreset {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub punctuation: PunctuationPolicy,
+    #[serde(default)]
+    pub capitalization: CapitalizationPolicy,
+    #[serde(default)]
+    pub remove_fillers: bool,
+    #[serde(default)]
+    pub numbers: NumberFormat,
+    /// Sentences longer than this many words are split at a comma or
+    /// conjunction where possible.
+    #[serde(default)]
+    pub max_sentence_words: Option<u32>,
+    /// Extra instructions for the LLM; unused without post-processing.
+    #[serde(default)]
+    pub rewrite_instructions: String,
+}
+
+/// Assigns a preset to every frontmost app whose name contains `app`,
+/// ignoring case.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct StylePresetAppRule {
+    pub app: String,
+    pub preset_id: String,
+}
+
+/// Shortest sentence limit a preset may set; lower values chop every clause.
+const MIN_SENTENCE_WORDS: u32 = 5;
+
+pub fn default_style_presets() -> Vec<StylePreset> {
+    vec![
+        StylePreset {
+            id: "chat".to_string(),
+            name: "Chat".to_string(),
+            punctuation: PunctuationPolicy::NoTrailingPeriod,
+            capitalization: CapitalizationPolicy::Keep,
+            remove_fillers: true,
+            numbers: NumberFormat::Digits,
+            max_sentence_words: None,
+            rewrite_instructions: "Keep it short and conversational.".to_string(),
+        },
+        StylePreset {
+            id: "formal".to_string(),
+            name: "Formal".to_string(),
+            punctuation: PunctuationPolicy::Keep,
+            capitalization: CapitalizationPolicy::Sentence,
+            remove_fillers: true,
+            numbers: NumberFormat::SpellSmall,
+            max_sentence_words: Some(30),
+            rewrite_instructions: "Use a professional tone and complete sentences.".to_string(),
+        },
+        StylePreset {
+            id: "notes".to_string(),
+            name: "Notes".to_string(),
+            punctuation: PunctuationPolicy::Strip,
+            capitalization: CapitalizationPolicy::Lowercase,
+            remove_fillers: true,
+            numbers: NumberFormat::Digits,
+            max_sentence_words: None,
+            rewrite_instructions: String::new(),
+        },
+    ]
+}
+
+/// Check presets before saving: names are required, ids must be unique and
+/// sentence limits sensible.
+pub fn validate_presets(presets: Vec<StylePreset>) -> Result<Vec<StylePreset>, String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut validated = Vec::with_capacity(presets.len());
+    for mut preset in presets {
+        preset.id = preset.id.trim().to_string();
+        preset.name = preset.name.trim().to_string();
+        if preset.id.is_empty() || preset.name.is_empty() {
+            return Err("Style presets need an id and a name".to_string());
+        }
+        if !seen.insert(preset.id.clone()) {
+            return Err(format!("Duplicate style preset id '{}'", preset.id));
+        }
+        if let Some(limit) = preset.max_sentence_words {
+            if limit < MIN_SENTENCE_WORDS {
+                return Err(format!(
+                    "Sentence limit for '{}' must be at least {} words",
+                    preset.name, MIN_SENTENCE_WORDS
+                ));
+            }
+        }
+        preset.rewrite_instructions = preset.rewrite_instructions.trim().to_string();
+        validated.push(preset);
+    }
+    Ok(validated)
+}
+
+/// The preset for a run started from `binding_id` while `app_name` was in
+/// front. App rules win over binding assignments.
+pub fn resolve(
+    settings: &AppSettings,
+    binding_id: &str,
+    app_name: Option<&str>,
+) -> Option<StylePreset> {
+    let app_name = app_name.map(str::to_lowercase);
+    let from_app = app_name.as_deref().and_then(|name| {
+        settings
+            .style_preset_app_rules
+            .iter()
+            .find(|rule| {
+                let app = rule.app.trim().to_lowercase();
+                !app.is_empty() && name.contains(&app)
+            })
+            .map(|rule| rule.preset_id.as_str())
+    });
+    let preset_id = from_app.or_else(|| {
+        settings
+            .style_preset_bindings
+            .get(binding_id)
+            .map(String::as_str)
+    })?;
+    settings
+        .style_presets
+        .iter()
+        .find(|preset| preset.id == preset_id)
+        .cloned()
+}
+
+/// Instructions to append to the post-processing prompt, if any.
+pub fn llm_instructions(preset: &StylePreset) -> Option<String> {
+    let instructions = preset.rewrite_instructions.trim();
+    (!instructions.is_empty()).then(|| format!("Style: {}", instructions))
+}
+
+// ============================================================================
+// Deterministic rules
+// ============================================================================
+
+static SPOKEN_FILLERS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:you know|i mean|basically|literally),\s*").unwrap());
+
+/// Apply the preset's rules to `text`. Lines are styled one at a time so
+/// paragraphs and lists keep their layout.
+pub fn apply_rules(text: &str, preset: &StylePreset) -> String {
+    let (masked, spans) = jargon::mask_protected_spans(text);
+    let mut text = masked
+        .split('\n')
+        .map(|line| {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let styled = apply_line_rules(line.trim(), preset);
+            if styled.is_empty() {
+                styled
+            } else {
+                format!("{}{}", indent, styled)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if preset.punctuation == PunctuationPolicy::NoTrailingPeriod {
+        let trimmed = text.trim_end();
+        if let Some(stripped) = trimmed.strip_suffix('.') {
+            if !stripped.ends_with('.') {
+                text = stripped.to_string();
+            }
+        }
+    }
+
+    jargon::restore_protected_spans(&text, &spans)
+}
+
+fn apply_line_rules(line: &str, preset: &StylePreset) -> String {
+    let mut line = line.to_string();
+    if preset.remove_fillers {
+        line = SPOKEN_FILLERS.replace_all(&line, "").into_owned();
+        line = crate::audio_toolkit::text::remove_filler_words(&line);
+    }
+    line = match preset.numbers {
+        NumberFormat::Keep => line,
+        NumberFormat::Digits => number_words_to_digits(&line),
+        NumberFormat::SpellSmall => spell_small_numbers(&line),
+    };
+    if let Some(limit) = preset.max_sentence_words {
+        line = split_long_sentences(&line, limit.max(MIN_SENTENCE_WORDS) as usize);
+    }
+    line = match preset.capitalization {
+        CapitalizationPolicy::Keep => line,
+        CapitalizationPolicy::Sentence => capitalize_sentences(&line),
+        CapitalizationPolicy::Lowercase => lowercase_outside_placeholders(&line),
+    };
+    if preset.punctuation == PunctuationPolicy::Strip {
+        line = strip_punctuation(&line);
+    }
+    line
+}
+
+/// Placeholders (`⟦S0⟧`, `⟦N0⟧`) stand in for text that must not change.
+fn is_placeholder(word: &str) -> bool {
+    word.starts_with('\u{27E6}')
+}
+
+fn split_punctuation(word: &str) -> (&str, &str) {
+    let core = word.trim_end_matches(|c: char| matches!(c, '.' | ',' | ';' | ':' | '!' | '?'));
+    (core, &word[core.len()..])
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with(['.', '!', '?'])
+}
+
+/// Uppercase the first letter of `word`, skipping leading quotes or brackets.
+fn capitalize_first(word: &str) -> String {
+    match word.char_indices().find(|(_, c)| c.is_alphanumeric()) {
+        Some((index, first)) if first.is_alphabetic() => {
+            let rest = &word[index + first.len_utf8()..];
+            format!("{}{}{}", &word[..index], first.to_uppercase(), rest)
+        }
+        _ => word.to_string(),
+    }
+}
+
+fn capitalize_sentences(text: &str) -> String {
+    let mut sentence_start = true;
+    text.split(' ')
+        .map(|word| {
+            let is_word = is_placeholder(word) || word.chars().any(char::is_alphanumeric);
+            if !is_word {
+                // Bullets and dashes do not use up the sentence start.
+                return word.to_string();
+            }
+            let out = if sentence_start && !is_placeholder(word) {
+                capitalize_first(word)
+            } else {
+                word.to_string()
+            };
+            sentence_start = ends_sentence(word);
+            out
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn lowercase_outside_placeholders(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut inside = false;
+    for c in text.chars() {
+        match c {
+            '\u{27E6}' => inside = true,
+            '\u{27E7}' => inside = false,
+            _ => {}
+        }
+        if inside {
+            out.push(c);
+        } else {
+            out.extend(c.to_lowercase());
+        }
+    }
+    out
+}
+
+fn strip_punctuation(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| split_punctuation(word).0)
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split sentences longer than `limit` words after a comma or before a
+/// conjunction, preferring the last such point that keeps the first piece
+/// within the limit. Sentences with no such point are left alone.
+fn split_long_sentences(text: &str, limit: usize) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut sentence_start = 0;
+    for word in text.split_whitespace() {
+        out.push(word.to_string());
+        if ends_sentence(word) {
+            sentence_start = out.len();
+            continue;
+        }
+        if out.len() - sentence_start <= limit {
+            continue;
+        }
+
+        let last = out.len() - 1;
+        let within = sentence_start..(sentence_start + limit).min(last);
+        let at = within
+            .rev()
+            .find(|&at| is_break_point(&out, at))
+            .or_else(|| ((sentence_start + limit)..last).find(|&at| is_break_point(&out, at)));
+        if let Some(at) = at {
+            out[at] = format!("{}.", split_punctuation(&out[at]).0);
+            if !is_placeholder(&out[at + 1]) {
+                out[at + 1] = capitalize_first(&out[at + 1]);
+            }
+            sentence_start = at + 1;
+        }
+    }
+    out.join(" ")
+}
+
+/// Whether a sentence may end after `out[at]`: it ends in a comma, or the
+/// next word is a conjunction.
+fn is_break_point(out: &[String], at: usize) -> bool {
+    out[at].ends_with(',')
+        || out.get(at + 1).is_some_and(|next| {
+            matches!(
+                split_punctuation(next).0.to_lowercase().as_str(),
+                "and" | "but" | "so"
+            )
+        })
+}
+
+// ----------------------------------------------------------------------------
+// Numbers
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq)]
+enum NumberWord {
+    Zero,
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+}
+
+fn number_word(word: &str) -> Option<NumberWord> {
+    use NumberWord::*;
+    Some(match word {
+        "zero" => Zero,
+        "one" => Unit(1),
+        "two" => Unit(2),
+        "three" => Unit(3),
+        "four" => Unit(4),
+        "five" => Unit(5),
+        "six" => Unit(6),
+        "seven" => Unit(7),
+        "eight" => Unit(8),
+        "nine" => Unit(9),
+        "ten" => Teen(10),
+        "eleven" => Teen(11),
+        "twelve" => Teen(12),
+        "thirteen" => Teen(13),
+        "fourteen" => Teen(14),
+        "fifteen" => Teen(15),
+        "sixteen" => Teen(16),
+        "seventeen" => Teen(17),
+        "eighteen" => Teen(18),
+        "nineteen" => Teen(19),
+        "twenty" => Tens(20),
+        "thirty" => Tens(30),
+        "forty" => Tens(40),
+        "fifty" => Tens(50),
+        "sixty" => Tens(60),
+        "seventy" => Tens(70),
+        "eighty" => Tens(80),
+        "ninety" => Tens(90),
+        "hundred" => Hundred,
+        "thousand" => Scale(1_000),
+        "million" => Scale(1_000_000),
+        "billion" => Scale(1_000_000_000),
+        _ => return None,
+    })
+}
+
+/// Whether `next` may follow `prev` within one spoken number.
+fn may_follow(prev: NumberWord, next: NumberWord, last_scale: u64) -> bool {
+    use NumberWord::*;
+    let scale_ok = |scale: u64| last_scale == 0 || scale < last_scale;
+    match (prev, next) {
+        (Zero, _) => false,
+        (Unit(_) | Teen(_), Hundred) => true,
+        (Unit(_) | Teen(_) | Tens(_) | Hundred, Scale(scale)) => scale_ok(scale),
+        (Tens(_), Unit(_)) => true,
+        (Hundred | Scale(_), Unit(_) | Teen(_) | Tens(_)) => true,
+        _ => false,
+    }
+}
+
+/// Parse the longest spoken number starting at `words[start]`. Returns the
+/// value and how many words it used.
+fn parse_number(words: &[&str], start: usize) -> Option<(u64, usize)> {
+    let mut total = 0u64;
+    let mut current = 0u64;
+    let mut last_scale = 0u64;
+    let mut prev: Option<NumberWord> = None;
+    let mut used = 0;
+    let mut i = start;
+
+    while i < words.len() {
+        let (core, punctuation) = split_punctuation(words[i]);
+        let lowered = core.to_lowercase();
+        // "one hundred and five": "and" only counts when a number follows.
+        if lowered == "and"
+            && punctuation.is_empty()
+            && matches!(prev, Some(NumberWord::Hundred | NumberWord::Scale(_)))
+        {
+            let next = words
+                .get(i + 1)
+                .map(|w| split_punctuation(w).0.to_lowercase());
+            match next.as_deref().and_then(number_word) {
+                Some(NumberWord::Unit(_) | NumberWord::Teen(_) | NumberWord::Tens(_)) => {
+                    i += 1;
+                    continue;
+                }
+                _ => break,
+            }
+        }
+        let Some(word) = number_word(&lowered) else {
+            break;
+        };
+        let allowed = match prev {
+            Some(prev) => may_follow(prev, word, last_scale),
+            // "a thousand" is not a number to rewrite.
+            None => !matches!(word, NumberWord::Hundred | NumberWord::Scale(_)),
+        };
+        if !allowed {
+            break;
+        }
+        match word {
+            NumberWord::Zero => {}
+            NumberWord::Unit(n) | NumberWord::Teen(n) | NumberWord::Tens(n) => current += n,
+            NumberWord::Hundred => current = current.max(1) * 100,
+            NumberWord::Scale(scale) => {
+                total += current.max(1) * scale;
+                current = 0;
+                last_scale = scale;
+            }
+        }
+        prev = Some(word);
+        i += 1;
+        used = i - start;
+        if !punctuation.is_empty() {
+            break;
+        }
+    }
+
+    (used > 0).then_some((total + current, used))
+}
+
+fn number_words_to_digits(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut out: Vec<String> = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        // Hyphenated forms ("twenty-five") are parsed as separate words.
+        let parts: Vec<&str> = words[i].split('-').collect();
+        let hyphenated = parts.len() == 2 && parts.iter().all(|part| !part.is_empty());
+        let parsed = if hyphenated {
+            parse_number(&parts, 0)
+                .filter(|(_, used)| *used == 2)
+                .map(|(value, _)| (value, 1, split_punctuation(parts[1]).1))
+        } else {
+            parse_number(&words, i)
+                .map(|(value, used)| (value, used, split_punctuation(words[i + used - 1]).1))
+        };
+        match parsed {
+            // Small numbers read better as words ("one of them").
+            Some((value, used, punctuation)) if value >= 10 => {
+                out.push(format!("{}{}", value, punctuation));
+                i += used;
+            }
+            _ => {
+                out.push(words[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join(" ")
+}
+
+const SMALL_NUMBERS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn spell_small_numbers(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let (core, punctuation) = split_punctuation(word);
+            match core.parse::<usize>() {
+                Ok(n) if core.len() == 1 && n < SMALL_NUMBERS.len() => {
+                    format!("{}{}", SMALL_NUMBERS[n], punctuation)
+                }
+                _ => word.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    fn preset() -> StylePreset {
+        StylePreset {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            punctuation: PunctuationPolicy::Keep,
+            capitalization: CapitalizationPolicy::Keep,
+            remove_fillers: false,
+            numbers: NumberFormat::Keep,
+            max_sentence_words: None,
+            rewrite_instructions: String::new(),
+        }
+    }
+
+    #[test]
+    fn punctuation_and_capitalization_policies() {
+        let mut chat = preset();
+        chat.punctuation = PunctuationPolicy::NoTrailingPeriod;
+        assert_eq!(apply_rules("Sounds good.", &chat), "Sounds good");
+        assert_eq!(apply_rules("Wait...", &chat), "Wait...");
+
+        let mut notes = preset();
+        notes.punctuation = PunctuationPolicy::Strip;
+        notes.capitalization = CapitalizationPolicy::Lowercase;
+        assert_eq!(
+            apply_rules("Open https://Example.com/Docs, then call Ada.", &notes),
+            "open https://Example.com/Docs, then call ada"
+        );
+
+        let mut formal = preset();
+        formal.capitalization = CapitalizationPolicy::Sentence;
+        assert_eq!(
+            apply_rules("this works. does it? yes!", &formal),
+            "This works. Does it? Yes!"
+        );
+        assert_eq!(
+            apply_rules("todo:\n\n  - fix it\n  - ship it", &formal),
+            "Todo:\n\n  - Fix it\n  - Ship it"
+        );
+    }
+
+    #[test]
+    fn fillers_are_removed() {
+        let mut style = preset();
+        style.remove_fillers = true;
+        assert_eq!(
+            apply_rules("So, um, you know, we should ship it.", &style),
+            "So, we should ship it."
+        );
+    }
+
+    #[test]
+    fn numbers_are_formatted() {
+        let mut digits = preset();
+        digits.numbers = NumberFormat::Digits;
+        assert_eq!(
+            apply_rules(
+                "I need twenty-five tickets and one hundred and five chairs.",
+                &digits
+            ),
+            "I need 25 tickets and 105 chairs."
+        );
+        assert_eq!(
+            apply_rules("one of them costs two thousand three hundred", &digits),
+            "one of them costs 2300"
+        );
+        assert_eq!(
+            apply_rules("about seventy five percent, a thousand times", &digits),
+            "about 75 percent, a thousand times"
+        );
+
+        let mut words = preset();
+        words.numbers = NumberFormat::SpellSmall;
+        assert_eq!(
+            apply_rules("Take 3 steps, not 12 or v2.", &words),
+            "Take three steps, not 12 or v2."
+        );
+    }
+
+    #[test]
+    fn long_sentences_are_split() {
+        let mut style = preset();
+        style.max_sentence_words = Some(6);
+        assert_eq!(
+            apply_rules(
+                "we looked at the logs and we found the bug in the parser.",
+                &style
+            ),
+            "we looked at the logs. And we found the bug in the parser."
+        );
+        // Without a comma or conjunction there is nowhere to split.
+        let unbroken = "one two three four five six seven eight.";
+        assert_eq!(apply_rules(unbroken, &style), unbroken);
+    }
+
+    #[test]
+    fn app_rules_win_over_binding_assignments() {
+        let mut settings = get_default_settings();
+        settings.style_presets = default_style_presets();
+        settings
+            .style_preset_bindings
+            .insert("transcribe".to_string(), "formal".to_string());
+        settings.style_preset_app_rules = vec![StylePresetAppRule {
+            app: "Slack".to_string(),
+            preset_id: "chat".to_string(),
+        }];
+
+        let id = |app: Option<&str>| resolve(&settings, "transcribe", app).map(|p| p.id);
+        assert_eq!(
+            id(Some("com.tinyspeck.slackmacgap")),
+            Some("chat".to_string())
+        );
+        assert_eq!(id(Some("code.exe")), Some("formal".to_string()));
+        assert_eq!(id(None), Some("formal".to_string()));
+        assert_eq!(
+            resolve(&settings, "transcribe_with_post_process", None),
+            None
+        );
+    }
+}
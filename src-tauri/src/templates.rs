@@ -0,0 +1,560 @@
+//! Template command mode: "template commit, summary fix login redirect ..."
+//! inserts a named template with its `{{placeholder}}` fields filled from the
+//! rest of the utterance.
+//!
+//! Fields are filled by saying their name followed by the value; words
+//! spoken before any field name go to the first field. With
+//! `template_llm_fill` set, the post-processing provider is asked to fill
+//! them instead. Fields left empty stay in the text as `{{name}}`, and the
+//! `template_next_placeholder` shortcut walks through them afterwards.
+
+use crate::clipboard::TextEdit;
+use crate::input::{self, EnigoState};
+use crate::utils;
+use crate::voice_commands::normalize_phrase;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// First spoken word that switches a dictation into template mode.
+pub const TRIGGER_WORD: &str = "template";
+
+pub(crate) static PLACEHOLDER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").unwrap());
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct TextTemplate {
+    pub id: String,
+    /// Spoken after the trigger word to pick this template.
+    pub name: String,
+    /// Template text with `{{placeholder}}` fields.
+    pub body: String,
+    #[serde(default = "default_template_enabled")]
+    pub enabled: bool,
+}
+
+fn default_template_enabled() -> bool {
+    true
+}
+
+pub fn default_templates() -> Vec<TextTemplate> {
+    let template = |id: &str, name: &str, body: &str| TextTemplate {
+        id: id.to_string(),
+        name: name.to_string(),
+        body: body.to_string(),
+        enabled: true,
+    };
+    vec![
+        template(
+            "pr",
+            "pull request",
+            "## Summary\n{{summary}}\n\n## Changes\n{{changes}}\n\n## Testing\n{{testing}}",
+        ),
+        template("commit", "commit", "{{type}}: {{summary}}\n\n{{body}}"),
+        template(
+            "plan",
+            "plan",
+            "# {{title}}\n\n## Goal\n{{goal}}\n\n## Steps\n{{steps}}\n\n## Risks\n{{risks}}",
+        ),
+        template(
+            "prompt",
+            "prompt",
+            "You are {{role}}.\n\nTask: {{task}}\n\nContext: {{context}}\n\nRespond with {{format}}.",
+        ),
+    ]
+}
+
+/// Normalise template names and check that ids and names are unique and
+/// that every template has a name and a body.
+pub fn validate_templates(templates: Vec<TextTemplate>) -> Result<Vec<TextTemplate>, String> {
+    let mut ids = std::collections::HashSet::new();
+    let mut names = std::collections::HashSet::new();
+    let mut validated = Vec::with_capacity(templates.len());
+    for mut template in templates {
+        template.id = template.id.trim().to_string();
+        template.name = normalize_phrase(&template.name);
+        if template.id.is_empty() {
+            return Err("Template id cannot be empty".to_string());
+        }
+        if template.name.is_empty() {
+            return Err(format!("Template '{}' needs a spoken name", template.id));
+        }
+        if template.body.trim().is_empty() {
+            return Err(format!("Template '{}' has an empty body", template.name));
+        }
+        if !ids.insert(template.id.clone()) {
+            return Err(format!("Duplicate template id '{}'", template.id));
+        }
+        if !names.insert(template.name.clone()) {
+            return Err(format!("Duplicate template name '{}'", template.name));
+        }
+        validated.push(template);
+    }
+    Ok(validated)
+}
+
+/// Placeholder names in the order they first appear in `body`.
+pub fn placeholders(body: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for captures in PLACEHOLDER_RE.captures_iter(body) {
+        let name = captures[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+// ============================================================================
+// Parsing and filling
+// ============================================================================
+
+/// A dictation that asked for a template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateInvocation {
+    pub template: TextTemplate,
+    /// Everything spoken after the template name.
+    pub spoken: String,
+}
+
+/// Recognise "template <name> ..." at the start of a transcription. The
+/// longest matching template name wins.
+pub fn parse_invocation(text: &str, templates: &[TextTemplate]) -> Option<TemplateInvocation> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if normalize_phrase(words.first()?) != TRIGGER_WORD {
+        return None;
+    }
+    match_template_name(&words[1..], templates, false)
+}
+
+/// Like `parse_invocation`, for a transcription that starts with the
+/// template name itself. Used when the intent router has already decided
+/// the utterance asks for a template. Without the trigger word a bare
+/// template name is too likely to be ordinary speech, so words must follow.
+pub fn parse_untriggered(text: &str, templates: &[TextTemplate]) -> Option<TemplateInvocation> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    match_template_name(&words, templates, true)
+}
+
+fn match_template_name(
+    words: &[&str],
+    templates: &[TextTemplate],
+    needs_spoken: bool,
+) -> Option<TemplateInvocation> {
+    let normalized: Vec<String> = words.iter().map(|word| normalize_phrase(word)).collect();
+    let mut best: Option<(&TextTemplate, usize)> = None;
+    for template in templates.iter().filter(|template| template.enabled) {
+        let name = normalize_phrase(&template.name);
+        let name: Vec<&str> = name.split(' ').collect();
+        let len = name.len();
+        let long_enough = if needs_spoken {
+            normalized.len() > len
+        } else {
+            normalized.len() >= len
+        };
+        let matches = long_enough
+            && normalized[..len]
+                .iter()
+                .zip(&name)
+                .all(|(word, part)| word == part);
+        if matches && !matches!(best, Some((_, best_len)) if best_len >= len) {
+            best = Some((template, len));
+        }
+    }
+
+    let (template, len) = best?;
+    Some(TemplateInvocation {
+        template: template.clone(),
+        spoken: words[len..].join(" "),
+    })
+}
+
+/// Fill placeholders from speech like "summary fix the redirect body the
+/// login page looped". A placeholder is addressed by its name with
+/// underscores spoken as spaces.
+pub fn fill_from_speech(placeholders: &[String], spoken: &str) -> HashMap<String, String> {
+    let mut keys: Vec<(Vec<String>, &String)> = placeholders
+        .iter()
+        .map(|name| {
+            let words = normalize_phrase(&name.replace('_', " "))
+                .split(' ')
+                .map(str::to_string)
+                .collect();
+            (words, name)
+        })
+        .collect();
+    // Try longer names first so "first step" wins over "first".
+    keys.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+
+    let words: Vec<&str> = spoken.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|word| normalize_phrase(word)).collect();
+
+    let mut values: HashMap<String, Vec<&str>> = HashMap::new();
+    let mut current = placeholders.first();
+    let mut i = 0;
+    while i < words.len() {
+        let key = keys.iter().find(|(key_words, _)| {
+            normalized.len() >= i + key_words.len()
+                && normalized[i..i + key_words.len()] == key_words[..]
+        });
+        if let Some((key_words, name)) = key {
+            current = Some(*name);
+            i += key_words.len();
+            continue;
+        }
+        if let Some(name) = current {
+            values.entry(name.clone()).or_default().push(words[i]);
+        }
+        i += 1;
+    }
+
+    values
+        .into_iter()
+        .map(|(name, words)| (name, clean_value(&words.join(" "))))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+/// Drop the separators people put between a field name and its value.
+fn clean_value(value: &str) -> String {
+    value
+        .trim_start_matches(|c: char| c == ',' || c == ':' || c.is_whitespace())
+        .trim_end_matches(|c: char| c == ',' || c == ';' || c == ':' || c.is_whitespace())
+        .to_string()
+}
+
+/// Substitute filled placeholders. Empty ones are left as `{{name}}`.
+pub fn render(body: &str, values: &HashMap<String, String>) -> String {
+    PLACEHOLDER_RE
+        .replace_all(body, |captures: &regex::Captures| {
+            let name = &captures[1];
+            match values.get(name) {
+                Some(value) if !value.trim().is_empty() => value.clone(),
+                _ => format!("{{{{{}}}}}", name),
+            }
+        })
+        .into_owned()
+}
+
+/// System message and prompt asking an LLM to fill the placeholders as JSON.
+pub fn llm_fill_request(template: &TextTemplate, spoken: &str) -> (String, String) {
+    let names = placeholders(&template.body);
+    let system = "You fill in text templates from dictated notes. Reply with a single JSON \
+                  object mapping each field name to its text, and nothing else. Use only \
+                  what the notes say; give an empty string for fields they do not cover."
+        .to_string();
+    let prompt = format!(
+        "Template ({}):\n{}\n\nFields: {}\n\nNotes:\n{}",
+        template.name,
+        template.body,
+        names.join(", "),
+        spoken
+    );
+    (system, prompt)
+}
+
+/// Read the JSON object an LLM returned for `llm_fill_request`, ignoring any
+/// text around it and any field the template does not have.
+pub fn parse_llm_values(content: &str, placeholders: &[String]) -> Option<HashMap<String, String>> {
+    let start = content.find('{')?;
+    let end = content.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    let object: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&content[start..=end]).ok()?;
+    Some(
+        object
+            .into_iter()
+            .filter(|(name, _)| placeholders.contains(name))
+            .filter_map(|(name, value)| {
+                let value = match value {
+                    serde_json::Value::String(text) => text,
+                    serde_json::Value::Null => return None,
+                    other => other.to_string(),
+                };
+                Some((name, value.trim().to_string()))
+            })
+            .filter(|(_, value)| !value.is_empty())
+            .collect(),
+    )
+}
+
+// ============================================================================
+// Placeholder navigation
+// ============================================================================
+
+/// An unfilled placeholder, located by counting characters back from the
+/// end of the inserted template. Those counts stay valid while the user
+/// types at a position to the left of the placeholder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PendingPlaceholder {
+    end_from_end: usize,
+    len: usize,
+}
+
+struct PlaceholderNavigation {
+    focused_app: String,
+    /// Characters between the cursor and the end of the inserted template.
+    cursor_from_end: usize,
+    /// Left to right.
+    pending: Vec<PendingPlaceholder>,
+}
+
+#[derive(Default)]
+pub struct TemplateNavigationState(Mutex<Option<PlaceholderNavigation>>);
+
+/// Cursor movement and deletion that take the cursor to the next placeholder.
+#[derive(Debug, PartialEq)]
+struct Jump {
+    right: usize,
+    left: usize,
+    delete: usize,
+}
+
+fn pending_placeholders(rendered: &str) -> Vec<PendingPlaceholder> {
+    let total = rendered.chars().count();
+    PLACEHOLDER_RE
+        .find_iter(rendered)
+        .map(|found| {
+            let end = rendered[..found.end()].chars().count();
+            PendingPlaceholder {
+                end_from_end: total - end,
+                len: found.as_str().chars().count(),
+            }
+        })
+        .collect()
+}
+
+/// Plan the jump to the next pending placeholder and advance past it.
+fn next_jump(navigation: &mut PlaceholderNavigation) -> Option<Jump> {
+    if navigation.pending.is_empty() {
+        return None;
+    }
+    let target = navigation.pending.remove(0);
+    let jump = if target.end_from_end >= navigation.cursor_from_end {
+        Jump {
+            right: 0,
+            left: target.end_from_end - navigation.cursor_from_end,
+            delete: target.len,
+        }
+    } else {
+        Jump {
+            right: navigation.cursor_from_end - target.end_from_end,
+            left: 0,
+            delete: target.len,
+        }
+    };
+    navigation.cursor_from_end = target.end_from_end;
+    Some(jump)
+}
+
+/// Remember the placeholders left in `rendered`, which was just pasted with
+/// the cursor at its end.
+pub fn record_insertion(app: &AppHandle, rendered: &str) {
+    let pending = pending_placeholders(rendered);
+    let navigation = match crate::context_providers::focused_app_id() {
+        Some(focused_app) if !pending.is_empty() => Some(PlaceholderNavigation {
+            focused_app,
+            cursor_from_end: 0,
+            pending,
+        }),
+        _ => None,
+    };
+    if let Some(state) = app.try_state::<TemplateNavigationState>() {
+        if let Ok(mut current) = state.0.lock() {
+            *current = navigation;
+        }
+    }
+}
+
+/// Move to the next unfilled placeholder of the last inserted template and
+/// clear it, leaving the cursor where its value goes.
+pub fn jump_to_next_placeholder(app: &AppHandle) -> Result<(), String> {
+    let state = app
+        .try_state::<TemplateNavigationState>()
+        .ok_or("Template navigation state not initialized")?;
+    let mut current = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock template navigation: {}", e))?;
+
+    let navigation = current.as_mut().ok_or("No template placeholders left")?;
+    if crate::context_providers::focused_app_id().as_deref()
+        != Some(navigation.focused_app.as_str())
+    {
+        *current = None;
+        return Err("Focused app changed since the template was inserted".to_string());
+    }
+    let jump = next_jump(navigation).ok_or("No template placeholders left")?;
+    if navigation.pending.is_empty() {
+        *current = None;
+    }
+    drop(current);
+
+    debug!("Jumping to template placeholder: {:?}", jump);
+    let ah = app.clone();
+    app.run_on_main_thread(move || {
+        if let Err(e) = perform_jump(&ah, &jump) {
+            warn!("Failed to jump to template placeholder: {}", e);
+        }
+    })
+    .map_err(|e| format!("Failed to run placeholder jump on main thread: {:?}", e))
+}
+
+fn perform_jump(app: &AppHandle, jump: &Jump) -> Result<(), String> {
+    if jump.right > 0 {
+        let enigo_state = app
+            .try_state::<EnigoState>()
+            .ok_or("Enigo state not initialized")?;
+        let mut enigo = enigo_state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+        input::send_right_arrow(&mut enigo, jump.right)?;
+    }
+    info!("Clearing template placeholder ({} chars)", jump.delete);
+    utils::apply_text_diff(
+        &[TextEdit {
+            cursor_left: jump.left,
+            delete_chars: jump.delete,
+            insert: String::new(),
+        }],
+        0,
+        app.clone(),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit() -> TextTemplate {
+        default_templates()
+            .into_iter()
+            .find(|template| template.id == "commit")
+            .unwrap()
+    }
+
+    #[test]
+    fn invocation_needs_trigger_word_and_known_name() {
+        let templates = default_templates();
+        let invocation =
+            parse_invocation("Template pull request. Summary, adds caching.", &templates).unwrap();
+        assert_eq!(invocation.template.id, "pr");
+        assert_eq!(invocation.spoken, "Summary, adds caching.");
+
+        assert!(parse_invocation("commit the changes", &templates).is_none());
+        assert!(parse_invocation("template unknown thing", &templates).is_none());
+        let bare = parse_invocation("Template commit.", &templates).unwrap();
+        assert_eq!(bare.template.id, "commit");
+        assert_eq!(bare.spoken, "");
+        assert!(parse_untriggered("commit", &templates).is_none());
+        assert_eq!(
+            parse_untriggered("pull request. Summary, adds caching.", &templates)
+                .unwrap()
+                .template
+                .id,
+            "pr"
+        );
+
+        let mut disabled = templates.clone();
+        disabled
+            .iter_mut()
+            .for_each(|template| template.enabled = false);
+        assert!(parse_invocation("template commit fix", &disabled).is_none());
+    }
+
+    #[test]
+    fn spoken_field_names_route_values() {
+        let names = placeholders(&commit().body);
+        assert_eq!(names, vec!["type", "summary", "body"]);
+
+        let values = fill_from_speech(
+            &names,
+            "fix summary, stop the login redirect loop. Body: the cookie was never cleared.",
+        );
+        assert_eq!(values.get("type").map(String::as_str), Some("fix"));
+        assert_eq!(
+            values.get("summary").map(String::as_str),
+            Some("stop the login redirect loop.")
+        );
+        assert_eq!(
+            values.get("body").map(String::as_str),
+            Some("the cookie was never cleared.")
+        );
+    }
+
+    #[test]
+    fn render_keeps_unfilled_placeholders() {
+        let mut values = HashMap::new();
+        values.insert("summary".to_string(), "add caching".to_string());
+        assert_eq!(
+            render(&commit().body, &values),
+            "{{type}}: add caching\n\n{{body}}"
+        );
+    }
+
+    #[test]
+    fn llm_values_are_read_from_surrounding_text() {
+        let names = placeholders(&commit().body);
+        let values = parse_llm_values(
+            "Sure:\n{\"type\": \"feat\", \"summary\": \"add caching\", \"body\": \"\", \"extra\": \"x\"}",
+            &names,
+        )
+        .unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["type"], "feat");
+        assert!(parse_llm_values("no json here", &names).is_none());
+    }
+
+    #[test]
+    fn jumps_walk_placeholders_left_to_right() {
+        // "{{type}}: add caching\n\n{{body}}" is 31 chars; the cursor is at the end.
+        let rendered = "{{type}}: add caching\n\n{{body}}";
+        let mut navigation = PlaceholderNavigation {
+            focused_app: "app".to_string(),
+            cursor_from_end: 0,
+            pending: pending_placeholders(rendered),
+        };
+
+        assert_eq!(
+            next_jump(&mut navigation),
+            Some(Jump {
+                right: 0,
+                left: 23,
+                delete: 8
+            })
+        );
+        // Typing "feat" at the cursor leaves the counts from the end intact.
+        assert_eq!(
+            next_jump(&mut navigation),
+            Some(Jump {
+                right: 23,
+                left: 0,
+                delete: 8
+            })
+        );
+        assert_eq!(next_jump(&mut navigation), None);
+    }
+
+    #[test]
+    fn validation_normalizes_names_and_rejects_duplicates() {
+        let mut templates = default_templates();
+        templates[0].name = "  Pull   Request ".to_string();
+        let validated = validate_templates(templates.clone()).unwrap();
+        assert_eq!(validated[0].name, "pull request");
+
+        templates[1].name = "pull request".to_string();
+        assert!(validate_templates(templates).is_err());
+    }
+}
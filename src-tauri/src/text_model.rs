@@ -0,0 +1,198 @@
+//! ONNX text models for the sidecars: the intent router's sentence embedder
+//! and the re-ranker's cross-encoder. Each is a `model.onnx` with its
+//! `tokenizer.json` in a directory picked in the settings, and takes the
+//! usual BERT inputs (`input_ids`, `attention_mask`, `token_type_ids`).
+
+use anyhow::{Context, Result};
+use log::info;
+use ort::session::{Session, SessionInputValue};
+use ort::value::Tensor;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokenizers::{EncodeInput, Tokenizer};
+
+/// Files a text model directory must contain.
+pub const TEXT_MODEL_FILES: &[&str] = &["model.onnx", "tokenizer.json"];
+
+/// Return the required model files that are missing from `dir`.
+pub fn missing_model_files(dir: &Path) -> Vec<&'static str> {
+    TEXT_MODEL_FILES
+        .iter()
+        .copied()
+        .filter(|file| !dir.join(file).is_file())
+        .collect()
+}
+
+/// The model directory from a setting, if one is set.
+pub fn model_dir(setting: Option<&str>) -> Option<PathBuf> {
+    setting
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Trim a model directory picked in the settings and check that it holds a
+/// text model. `kind` names the model in the error ("re-ranker").
+pub fn validate_model_dir(model_dir: Option<String>, kind: &str) -> Result<Option<String>, String> {
+    let model_dir = model_dir
+        .map(|dir| dir.trim().to_string())
+        .filter(|dir| !dir.is_empty());
+
+    if let Some(dir) = &model_dir {
+        let path = Path::new(dir);
+        if !path.is_dir() {
+            return Err(format!("'{}' is not a directory", dir));
+        }
+        let missing = missing_model_files(path);
+        if !missing.is_empty() {
+            return Err(format!(
+                "'{}' is missing {} model files: {}",
+                dir,
+                kind,
+                missing.join(", ")
+            ));
+        }
+    }
+    Ok(model_dir)
+}
+
+/// One output of a model run, copied out of the session.
+pub struct TextModelOutput {
+    pub shape: Vec<i64>,
+    pub data: Vec<f32>,
+    /// Attention mask of the truncated input, for pooling token states.
+    pub attention_mask: Vec<i64>,
+}
+
+pub struct TextModel {
+    pub dir: PathBuf,
+    kind: &'static str,
+    session: Session,
+    tokenizer: Tokenizer,
+    input_names: Vec<String>,
+    output_name: String,
+    max_tokens: usize,
+}
+
+impl TextModel {
+    /// Load the model in `dir`. The output named `output` is read when the
+    /// model has one, and its first output otherwise. Inputs are cut to
+    /// `max_tokens` tokens.
+    pub fn load(
+        dir: &Path,
+        kind: &'static str,
+        output: Option<&str>,
+        max_tokens: usize,
+    ) -> Result<Self> {
+        let missing = missing_model_files(dir);
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "The {} model directory {:?} is missing: {}",
+                kind,
+                dir,
+                missing.join(", ")
+            );
+        }
+
+        let started = Instant::now();
+        let session = Session::builder()
+            .with_context(|| format!("Failed to create {} session builder", kind))?
+            .commit_from_file(dir.join("model.onnx"))
+            .with_context(|| format!("Failed to load {} ONNX model", kind))?;
+        let tokenizer = Tokenizer::from_file(dir.join("tokenizer.json"))
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+
+        let input_names = session
+            .inputs
+            .iter()
+            .map(|input| input.name.clone())
+            .collect();
+        let output_name = session
+            .outputs
+            .iter()
+            .map(|output| output.name.clone())
+            .find(|name| Some(name.as_str()) == output)
+            .or_else(|| session.outputs.first().map(|output| output.name.clone()))
+            .ok_or_else(|| anyhow::anyhow!("The {} model has no outputs", kind))?;
+
+        info!(
+            "Loaded {} model from {:?} in {:?}",
+            kind,
+            dir,
+            started.elapsed()
+        );
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            kind,
+            session,
+            tokenizer,
+            input_names,
+            output_name,
+            max_tokens,
+        })
+    }
+
+    /// Tokenize `input` (a text or a text pair) and run the model on it.
+    pub fn run<'s>(&mut self, input: impl Into<EncodeInput<'s>>) -> Result<TextModelOutput> {
+        let encoding = self
+            .tokenizer
+            .encode(input, true)
+            .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
+        let len = encoding.get_ids().len().min(self.max_tokens);
+        let to_i64 = |values: &[u32]| -> Vec<i64> {
+            values[..len].iter().map(|&value| value as i64).collect()
+        };
+        let input_ids = to_i64(encoding.get_ids());
+        let attention_mask = to_i64(encoding.get_attention_mask());
+        let type_ids = to_i64(encoding.get_type_ids());
+        let shape = vec![1i64, len as i64];
+
+        let mut inputs: Vec<(String, SessionInputValue)> = Vec::new();
+        for name in &self.input_names {
+            let values = match name.as_str() {
+                "input_ids" => input_ids.clone(),
+                "attention_mask" => attention_mask.clone(),
+                "token_type_ids" => type_ids.clone(),
+                other => anyhow::bail!("Unexpected {} model input '{}'", self.kind, other),
+            };
+            inputs.push((
+                name.clone(),
+                Tensor::from_array((shape.clone(), values))?.into(),
+            ));
+        }
+
+        let outputs = self.session.run(inputs)?;
+        let (shape, data) = outputs[self.output_name.as_str()].try_extract_tensor::<f32>()?;
+        Ok(TextModelOutput {
+            shape: shape.to_vec(),
+            data: data.to_vec(),
+            attention_mask,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn model_dirs_are_trimmed_and_checked() {
+        assert_eq!(model_dir(Some("  ")), None);
+        assert_eq!(
+            model_dir(Some(" /models/router ")),
+            Some(PathBuf::from("/models/router"))
+        );
+        assert_eq!(
+            validate_model_dir(Some(" ".to_string()), "re-ranker"),
+            Ok(None)
+        );
+
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("model.onnx"), b"").unwrap();
+        assert_eq!(missing_model_files(dir.path()), vec!["tokenizer.json"]);
+        let err =
+            validate_model_dir(Some(dir.path().display().to_string()), "re-ranker").unwrap_err();
+        assert!(err.ends_with("is missing re-ranker model files: tokenizer.json"));
+    }
+}
@@ -29,7 +29,11 @@ pub fn cancel_current_operation(app: &AppHandle) {
         warn!("Failed to lock toggle state manager during cancellation");
     }
 
-    // Cancel any ongoing recording
+    // Abort any streaming post-processing request
+    crate::llm_client::abort_active_streams();
+
+    // Cancel any ongoing recording, and paste no more of its segments
+    crate::live_segments::cancel();
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
 
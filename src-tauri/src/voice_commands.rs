@@ -0,0 +1,546 @@
+//! Spoken editing commands ("new line", "scratch that", ...) recognised in
+//! the filtered transcription before it is written out.
+//!
+//! Commands that only change the dictated text, such as line breaks or
+//! dropping the last sentence, are applied to the text itself. Commands that
+//! act on the target app, such as undo, become key presses sent through
+//! `EnigoState`. Saying the escape word first ("literally new line") types
+//! the phrase instead of running it.
+
+use crate::audio_toolkit::text::word_spans;
+use crate::input::{self, EnigoState};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+
+/// Spoken before a command phrase to type the phrase literally.
+pub const ESCAPE_WORD: &str = "literally";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceCommandKind {
+    NewLine,
+    NewParagraph,
+    /// Drop the text dictated since the previous command.
+    ScratchThat,
+    DeleteLastSentence,
+    /// Send the platform undo shortcut to the focused app.
+    UndoLast,
+    CapitalizeLastWord,
+    /// Extend the selection over the word before the cursor.
+    SelectLastWord,
+}
+
+impl VoiceCommandKind {
+    pub const ALL: [VoiceCommandKind; 7] = [
+        VoiceCommandKind::NewLine,
+        VoiceCommandKind::NewParagraph,
+        VoiceCommandKind::ScratchThat,
+        VoiceCommandKind::DeleteLastSentence,
+        VoiceCommandKind::UndoLast,
+        VoiceCommandKind::CapitalizeLastWord,
+        VoiceCommandKind::SelectLastWord,
+    ];
+
+    pub fn default_phrases(self) -> &'static [&'static str] {
+        match self {
+            VoiceCommandKind::NewLine => &["new line"],
+            VoiceCommandKind::NewParagraph => &["new paragraph"],
+            VoiceCommandKind::ScratchThat => &["scratch that"],
+            VoiceCommandKind::DeleteLastSentence => &["delete last sentence"],
+            VoiceCommandKind::UndoLast => &["undo last", "undo that"],
+            VoiceCommandKind::CapitalizeLastWord => &["capitalize last word", "capitalize that"],
+            VoiceCommandKind::SelectLastWord => &["select last word"],
+        }
+    }
+
+    fn key_action(self) -> Option<KeyAction> {
+        match self {
+            VoiceCommandKind::UndoLast => Some(KeyAction::Undo),
+            VoiceCommandKind::SelectLastWord => Some(KeyAction::SelectPreviousWord),
+            _ => None,
+        }
+    }
+}
+
+/// A spoken command and the phrases that trigger it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct VoiceCommandConfig {
+    pub kind: VoiceCommandKind,
+    pub phrases: Vec<String>,
+    #[serde(default = "default_voice_command_enabled")]
+    pub enabled: bool,
+}
+
+fn default_voice_command_enabled() -> bool {
+    true
+}
+
+impl VoiceCommandConfig {
+    pub fn with_default_phrases(kind: VoiceCommandKind) -> Self {
+        Self {
+            kind,
+            phrases: kind
+                .default_phrases()
+                .iter()
+                .map(|phrase| phrase.to_string())
+                .collect(),
+            enabled: true,
+        }
+    }
+}
+
+/// Normalise a phrase for matching: lowercase words separated by single spaces.
+pub fn normalize_phrase(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .map(normalize_word)
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+        .to_lowercase()
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoiceToken {
+    Text(String),
+    Command {
+        kind: VoiceCommandKind,
+        /// The words as transcribed, used when the command cannot run.
+        spoken: String,
+    },
+}
+
+/// Treat the whole text as dictation, with no commands.
+pub fn literal(text: &str) -> Vec<VoiceToken> {
+    if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![VoiceToken::Text(text.to_string())]
+    }
+}
+
+/// Split `text` into dictated text and the enabled commands it contains.
+/// Phrases match whole words, ignoring case and surrounding punctuation;
+/// the longest phrase wins when several match at the same position.
+/// Dictated text keeps the spacing and line breaks it was written with.
+pub fn parse(text: &str, commands: &[VoiceCommandConfig]) -> Vec<VoiceToken> {
+    let mut phrases: Vec<(Vec<String>, VoiceCommandKind)> = commands
+        .iter()
+        .filter(|command| command.enabled)
+        .flat_map(|command| {
+            command.phrases.iter().filter_map(move |phrase| {
+                let words: Vec<String> = normalize_phrase(phrase)
+                    .split(' ')
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                (!words.is_empty()).then_some((words, command.kind))
+            })
+        })
+        .collect();
+    if phrases.is_empty() {
+        return literal(text);
+    }
+    phrases.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+    let spans = word_spans(text);
+    let words: Vec<&str> = spans
+        .iter()
+        .map(|&(start, end)| &text[start..end])
+        .collect();
+    let normalized: Vec<String> = words.iter().map(|word| normalize_word(word)).collect();
+    let match_at = |start: usize| {
+        phrases.iter().find(|(phrase, _)| {
+            normalized.len() - start >= phrase.len()
+                && normalized[start..start + phrase.len()] == phrase[..]
+        })
+    };
+
+    // Dictated text is sliced out of `text` so the spacing between its words
+    // survives. `run` is the stretch of text since the last command or
+    // escape word; `pending` holds what came before an escape word.
+    let mut tokens = Vec::new();
+    let mut pending = String::new();
+    let mut run: Option<(usize, usize)> = None;
+    let mut escaped = false;
+    let mut i = 0;
+    while i < words.len() {
+        if normalized[i] == ESCAPE_WORD {
+            if let Some((phrase, _)) = match_at(i + 1) {
+                if let Some((start, _)) = run {
+                    pending.push_str(&text[start..spans[i].0]);
+                }
+                run = Some((spans[i + 1].0, spans[i + phrase.len()].1));
+                escaped = true;
+                i += 1 + phrase.len();
+                continue;
+            }
+        }
+        if let Some((phrase, kind)) = match_at(i) {
+            let run_text = run.take().map(|(start, end)| &text[start..end]);
+            flush_text(&mut tokens, &mut pending, run_text, true);
+            tokens.push(VoiceToken::Command {
+                kind: *kind,
+                spoken: words[i..i + phrase.len()].join(" "),
+            });
+            i += phrase.len();
+            continue;
+        }
+        run = Some((run.map_or(spans[i].0, |(start, _)| start), spans[i].1));
+        i += 1;
+    }
+
+    let has_command = tokens
+        .iter()
+        .any(|token| matches!(token, VoiceToken::Command { .. }));
+    if !has_command && !escaped {
+        return literal(text);
+    }
+    let run_text = run.map(|(start, end)| &text[start..end]);
+    flush_text(&mut tokens, &mut pending, run_text, false);
+    tokens
+}
+
+fn flush_text(
+    tokens: &mut Vec<VoiceToken>,
+    pending: &mut String,
+    run: Option<&str>,
+    before_command: bool,
+) {
+    if let Some(run) = run {
+        pending.push_str(run);
+    }
+    if pending.is_empty() {
+        return;
+    }
+    let mut text = std::mem::take(pending);
+    // A comma the recogniser put before a command marks the pause, not the text.
+    if before_command {
+        text = text.trim_end_matches(',').to_string();
+    }
+    tokens.push(VoiceToken::Text(text));
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// A key press sent to the focused app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Undo,
+    SelectPreviousWord,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DictationAction {
+    Type(String),
+    Key(KeyAction),
+}
+
+/// Apply text commands and turn key commands into [`DictationAction::Key`].
+/// With `allow_keys` unset, key commands are kept as their spoken words,
+/// for paths that rewrite the text before it is written out.
+pub fn resolve(tokens: &[VoiceToken], allow_keys: bool) -> Vec<DictationAction> {
+    let mut actions = Vec::new();
+    let mut buffer = String::new();
+    // Where the most recent dictated text starts in `buffer`.
+    let mut last_text_start: Option<usize> = None;
+
+    for token in tokens {
+        match token {
+            VoiceToken::Text(text) => {
+                last_text_start = Some(buffer.len());
+                append_text(&mut buffer, text);
+            }
+            VoiceToken::Command { kind, spoken } => match (kind.key_action(), allow_keys) {
+                (Some(action), true) => {
+                    if !buffer.is_empty() {
+                        actions.push(DictationAction::Type(std::mem::take(&mut buffer)));
+                    }
+                    last_text_start = None;
+                    actions.push(DictationAction::Key(action));
+                }
+                (Some(_), false) => {
+                    last_text_start = Some(buffer.len());
+                    append_text(&mut buffer, spoken);
+                }
+                (None, _) => {
+                    apply_text_command(*kind, &mut buffer, last_text_start.take());
+                }
+            },
+        }
+    }
+
+    if !buffer.is_empty() {
+        actions.push(DictationAction::Type(buffer));
+    }
+    actions
+}
+
+/// The text typed by `actions`, ignoring key presses.
+pub fn typed_text(actions: &[DictationAction]) -> String {
+    actions
+        .iter()
+        .filter_map(|action| match action {
+            DictationAction::Type(text) => Some(text.as_str()),
+            DictationAction::Key(_) => None,
+        })
+        .collect()
+}
+
+/// Split trailing key presses off `actions`, for a path that rewrites the
+/// text before writing it out. Returns the typed text, joined back together,
+/// and the keys to send once it is written; `None` when a key press comes
+/// before typed text, since sending it afterwards would act on the wrong text.
+pub fn split_trailing_key_actions(actions: &[DictationAction]) -> Option<(String, Vec<KeyAction>)> {
+    let mut text = String::new();
+    let mut keys = Vec::new();
+    for action in actions {
+        match action {
+            DictationAction::Type(_) if !keys.is_empty() => return None,
+            DictationAction::Type(typed) => append_text(&mut text, typed),
+            DictationAction::Key(key) => keys.push(*key),
+        }
+    }
+    Some((text, keys))
+}
+
+fn append_text(buffer: &mut String, text: &str) {
+    if !buffer.is_empty() && !buffer.ends_with('\n') {
+        buffer.push(' ');
+    }
+    buffer.push_str(text);
+}
+
+fn apply_text_command(kind: VoiceCommandKind, buffer: &mut String, last_text_start: Option<usize>) {
+    match kind {
+        VoiceCommandKind::NewLine => {
+            trim_trailing_spaces(buffer);
+            buffer.push('\n');
+        }
+        VoiceCommandKind::NewParagraph => {
+            trim_trailing_spaces(buffer);
+            buffer.push_str("\n\n");
+        }
+        VoiceCommandKind::ScratchThat => match last_text_start {
+            Some(start) => {
+                buffer.truncate(start);
+                trim_trailing_spaces(buffer);
+            }
+            None => debug!("'scratch that' with no preceding dictation; ignoring"),
+        },
+        VoiceCommandKind::DeleteLastSentence => {
+            trim_trailing_spaces(buffer);
+            let body = buffer.trim_end_matches(is_sentence_end);
+            let cut = body
+                .rfind(|c| is_sentence_end(c) || c == '\n')
+                .map(|pos| pos + 1)
+                .unwrap_or(0);
+            buffer.truncate(cut);
+            trim_trailing_spaces(buffer);
+        }
+        VoiceCommandKind::CapitalizeLastWord => {
+            let word_start = buffer
+                .trim_end()
+                .char_indices()
+                .rev()
+                .find(|(_, c)| c.is_whitespace())
+                .map(|(pos, c)| pos + c.len_utf8())
+                .unwrap_or(0);
+            if let Some(first) = buffer[word_start..].chars().next() {
+                let upper: String = first.to_uppercase().collect();
+                buffer.replace_range(word_start..word_start + first.len_utf8(), &upper);
+            }
+        }
+        VoiceCommandKind::UndoLast | VoiceCommandKind::SelectLastWord => {}
+    }
+}
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?')
+}
+
+fn trim_trailing_spaces(buffer: &mut String) {
+    let len = buffer.trim_end_matches(' ').len();
+    buffer.truncate(len);
+}
+
+// ============================================================================
+// Key actions
+// ============================================================================
+
+pub fn perform_key_action(app: &AppHandle, action: KeyAction) -> Result<(), String> {
+    let enigo_state = app
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    debug!("Performing voice command key action {:?}", action);
+    match action {
+        KeyAction::Undo => input::send_undo(&mut enigo),
+        KeyAction::SelectPreviousWord => input::send_select_previous_word(&mut enigo),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<VoiceCommandConfig> {
+        VoiceCommandKind::ALL
+            .into_iter()
+            .map(VoiceCommandConfig::with_default_phrases)
+            .collect()
+    }
+
+    fn run(text: &str) -> Vec<DictationAction> {
+        resolve(&parse(text, &defaults()), true)
+    }
+
+    #[test]
+    fn new_line_and_paragraph_become_line_breaks() {
+        assert_eq!(
+            run("Dear team, new line. Thanks for the update. New paragraph. Cheers"),
+            vec![DictationAction::Type(
+                "Dear team\nThanks for the update.\n\nCheers".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn escape_word_types_the_phrase() {
+        assert_eq!(
+            run("press literally new line to continue"),
+            vec![DictationAction::Type(
+                "press new line to continue".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn scratch_that_drops_the_previous_text() {
+        assert_eq!(
+            run("Meet at noon. Scratch that. Meet at one."),
+            vec![DictationAction::Type("Meet at one.".to_string())]
+        );
+        assert_eq!(
+            run("First line new line wrong words scratch that right words"),
+            vec![DictationAction::Type("First line\nright words".to_string())]
+        );
+    }
+
+    #[test]
+    fn delete_last_sentence_keeps_earlier_sentences() {
+        assert_eq!(
+            run("This stays. This goes away. Delete last sentence."),
+            vec![DictationAction::Type("This stays.".to_string())]
+        );
+    }
+
+    #[test]
+    fn capitalize_last_word_uppercases_its_first_letter() {
+        assert_eq!(
+            run("ship it on friday capitalize last word"),
+            vec![DictationAction::Type("ship it on Friday".to_string())]
+        );
+        // Ideographic space is three bytes long.
+        let tokens = [
+            VoiceToken::Text("ship it on\u{3000}friday".to_string()),
+            VoiceToken::Command {
+                kind: VoiceCommandKind::CapitalizeLastWord,
+                spoken: "capitalize last word".to_string(),
+            },
+        ];
+        assert_eq!(
+            resolve(&tokens, true),
+            vec![DictationAction::Type(
+                "ship it on\u{3000}Friday".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn spacing_in_dictated_text_is_kept() {
+        let text = "first line\n\tindented  twice";
+        assert_eq!(parse(text, &defaults()), literal(text));
+        assert_eq!(
+            run("one\ttwo  three new line four\n five"),
+            vec![DictationAction::Type(
+                "one\ttwo  three\nfour\n five".to_string()
+            )]
+        );
+        assert_eq!(
+            run("a\tb literally new line  c"),
+            vec![DictationAction::Type("a\tb new line  c".to_string())]
+        );
+    }
+
+    #[test]
+    fn key_commands_split_the_typed_text() {
+        assert_eq!(
+            run("hello world undo last goodbye"),
+            vec![
+                DictationAction::Type("hello world".to_string()),
+                DictationAction::Key(KeyAction::Undo),
+                DictationAction::Type("goodbye".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_key_commands_split_off_before_rewriting() {
+        let actions = run("hello world goodbye undo last select last word");
+        assert_eq!(
+            split_trailing_key_actions(&actions),
+            Some((
+                "hello world goodbye".to_string(),
+                vec![KeyAction::Undo, KeyAction::SelectPreviousWord]
+            ))
+        );
+    }
+
+    #[test]
+    fn key_commands_before_typed_text_are_not_split_off() {
+        let actions = run("hello world undo last goodbye");
+        assert_eq!(split_trailing_key_actions(&actions), None);
+    }
+
+    #[test]
+    fn key_commands_stay_spoken_when_keys_are_not_allowed() {
+        let actions = resolve(&parse("hello world undo last", &defaults()), false);
+        assert_eq!(
+            actions,
+            vec![DictationAction::Type("hello world undo last".to_string())]
+        );
+    }
+
+    #[test]
+    fn disabled_commands_are_typed() {
+        let mut commands = defaults();
+        for command in &mut commands {
+            command.enabled = command.kind != VoiceCommandKind::NewLine;
+        }
+        assert_eq!(
+            resolve(&parse("one new line two", &commands), true),
+            vec![DictationAction::Type("one new line two".to_string())]
+        );
+    }
+}
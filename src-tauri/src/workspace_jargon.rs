@@ -0,0 +1,218 @@
+//! Jargon derived from the active workspace: the file names and compound
+//! identifiers (`HistoryManager`, `parse_at_tokens`) found in its source
+//! files, ranked by how often they occur.
+//!
+//! Each identifier becomes a term, and the most frequent ones also get a
+//! correction from their spoken form ("history manager") so dictation lands
+//! on the exact spelling. The corrections only apply while the coding profile
+//! is active. `WorkspaceJargonManager` keeps the profile for the current
+//! workspace root.
+
+use crate::at_file_expansion::walk_workspace;
+use crate::jargon::{JargonCorrection, JargonProfile};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Most terms a workspace contributes.
+const MAX_TERMS: usize = 150;
+/// Most spoken-form corrections a workspace contributes.
+const MAX_CORRECTIONS: usize = 60;
+/// Source files read for identifiers; file names are always counted.
+const MAX_SCANNED_FILES: usize = 2_000;
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Shorter spoken forms ("is empty") are everyday phrases.
+const MIN_SPOKEN_LEN: usize = 10;
+/// An identifier must occur this often before its spoken form is replaced.
+const MIN_CORRECTION_COUNT: u32 = 3;
+
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "py", "go", "java", "kt", "swift", "c", "h", "cc", "cpp",
+    "hpp", "cs", "rb", "php", "scala", "m", "mm",
+];
+
+/// Build the workspace profile for `root`.
+pub fn build_profile(root: &Path) -> JargonProfile {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    let mut scanned = 0;
+
+    for path in walk_workspace(root) {
+        let is_source = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+        if !is_source {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+            if is_compound_identifier(stem) {
+                *counts.entry(stem.to_string()).or_default() += 1;
+            }
+        }
+
+        if scanned >= MAX_SCANNED_FILES
+            || std::fs::metadata(&path).map_or(true, |m| m.len() > MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        scanned += 1;
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for identifier in identifiers(&content).filter(|id| is_compound_identifier(id)) {
+            *counts.entry(identifier.to_string()).or_default() += 1;
+        }
+    }
+
+    profile_from_counts(root, counts)
+}
+
+fn profile_from_counts(root: &Path, counts: HashMap<String, u32>) -> JargonProfile {
+    let mut ranked: Vec<(String, u32)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(MAX_TERMS);
+
+    // One correction per spoken form, for its most frequent spelling.
+    let mut corrections: Vec<JargonCorrection> = Vec::new();
+    for (identifier, count) in &ranked {
+        if corrections.len() >= MAX_CORRECTIONS || *count < MIN_CORRECTION_COUNT {
+            continue;
+        }
+        let spoken = spoken_form(identifier);
+        if spoken.len() >= MIN_SPOKEN_LEN && corrections.iter().all(|c| c.from != spoken) {
+            corrections.push(JargonCorrection::new(spoken, identifier.clone()));
+        }
+    }
+
+    let label = root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Workspace".to_string());
+    JargonProfile {
+        label,
+        terms: ranked
+            .into_iter()
+            .map(|(identifier, _)| identifier)
+            .collect(),
+        corrections,
+    }
+}
+
+/// Identifier-shaped tokens in `content`, without parsing the language.
+fn identifiers(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|token| token.starts_with(|c: char| c.is_ascii_alphabetic()))
+}
+
+/// Names built from several words, like `HistoryManager` or
+/// `parse_at_tokens`. Plain words and `SCREAMING_CASE` constants are left
+/// out: the speech model already spells the first, nobody says the second.
+fn is_compound_identifier(identifier: &str) -> bool {
+    (4..=64).contains(&identifier.len())
+        && identifier.starts_with(|c: char| c.is_ascii_alphabetic())
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && identifier.chars().any(|c| c.is_ascii_lowercase())
+        && split_words(identifier).len() >= 2
+}
+
+/// `HTTPServer` -> `HTTP`, `Server`; `parse_at_tokens` -> `parse`, `at`,
+/// `tokens`.
+fn split_words(identifier: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = identifier.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, &(at, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if at > start {
+                words.push(&identifier[start..at]);
+            }
+            start = at + 1;
+            continue;
+        }
+        let Some(&(_, prev)) = i.checked_sub(1).and_then(|i| chars.get(i)) else {
+            continue;
+        };
+        let next_is_lower = chars
+            .get(i + 1)
+            .is_some_and(|&(_, n)| n.is_ascii_lowercase());
+        let boundary = c.is_ascii_uppercase()
+            && (prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_is_lower));
+        if boundary && at > start {
+            words.push(&identifier[start..at]);
+            start = at;
+        }
+    }
+    if start < identifier.len() {
+        words.push(&identifier[start..]);
+    }
+    words
+}
+
+/// How an identifier is said: its words, lowercase, separated by spaces.
+fn spoken_form(identifier: &str) -> String {
+    split_words(identifier)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_split_into_spoken_words() {
+        assert_eq!(split_words("HistoryManager"), vec!["History", "Manager"]);
+        assert_eq!(
+            split_words("parse_at_tokens"),
+            vec!["parse", "at", "tokens"]
+        );
+        assert_eq!(split_words("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split_words("getUrl2Path"), vec!["get", "Url2", "Path"]);
+        assert_eq!(spoken_form("HistoryManager"), "history manager");
+
+        assert!(is_compound_identifier("walk_workspace"));
+        assert!(is_compound_identifier("useSettings"));
+        assert!(!is_compound_identifier("settings"));
+        assert!(!is_compound_identifier("MAX_ENTRIES"));
+        assert!(!is_compound_identifier("_private"));
+    }
+
+    #[test]
+    fn workspace_profile_ranks_names_and_identifiers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src").join("history_manager.rs"),
+            "pub struct HistoryManager;\n\
+             impl HistoryManager { fn parse_at_tokens() {} }\n\
+             // HistoryManager is_empty is_empty is_empty\n\
+             const MAX_ENTRIES: usize = 1;",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "IgnoredName IgnoredName").unwrap();
+
+        let profile = build_profile(dir.path());
+        assert_eq!(
+            profile.terms,
+            vec![
+                "HistoryManager",
+                "is_empty",
+                "history_manager",
+                "parse_at_tokens"
+            ]
+        );
+        assert_eq!(profile.corrections.len(), 1);
+        assert_eq!(profile.corrections[0].from, "history manager");
+        assert_eq!(profile.corrections[0].to, "HistoryManager");
+    }
+}
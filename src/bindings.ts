@@ -109,6 +109,22 @@ async changeWordCorrectionThresholdSetting(threshold: number) : Promise<Result<n
     else return { status: "error", error: e  as any };
 }
 },
+async changePhoneticMatchingSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_phonetic_matching_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changePhoneticMatchThresholdSetting(threshold: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_phonetic_match_threshold_setting", { threshold }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePasteMethodSetting(method: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_paste_method_setting", { method }) };
@@ -168,6 +184,158 @@ async changePostProcessAutoPromptSelectionSetting(enabled: boolean) : Promise<Re
     else return { status: "error", error: e  as any };
 }
 },
+async changePostProcessStreamingSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_streaming_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changePostProcessDivergenceGuardSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_divergence_guard_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeVoiceCommandsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_voice_commands_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updatePostProcessFallbacks(fallbacks: PostProcessFallback[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_fallbacks", { fallbacks }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateVoiceCommands(commands: VoiceCommandConfig[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_voice_commands", { commands }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeTemplatesEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_templates_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeTemplateLlmFillSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_template_llm_fill_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateTemplates(templates: TextTemplate[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_templates", { templates }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeSnippetsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_snippets_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addSnippet(trigger: string, body: string) : Promise<Result<Snippet, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_snippet", { trigger, body }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSnippet(id: string, trigger: string, body: string, enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_snippet", { id, trigger, body, enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteSnippet(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_snippet", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateStylePresets(presets: StylePreset[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_style_presets", { presets }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateStylePresetBindings(assignments: Partial<{ [key in string]: string }>) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_style_preset_bindings", { assignments }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateStylePresetAppRules(rules: StylePresetAppRule[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_style_preset_app_rules", { rules }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeCliModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_cli_mode_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeCliModeLlmAssistSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_cli_mode_llm_assist_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateCliModeDenyPatterns(patterns: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_cli_mode_deny_patterns", { patterns }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changePostProcessTimeoutMsSetting(timeoutMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_timeout_ms_setting", { timeoutMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changeExperimentalEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_experimental_enabled_setting", { enabled }) };
@@ -184,6 +352,22 @@ async changePostProcessBaseUrlSetting(providerId: string, baseUrl: string) : Pro
     else return { status: "error", error: e  as any };
 }
 },
+async changePostProcessWireProtocolSetting(providerId: string, wireProtocol: WireProtocol) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_wire_protocol_setting", { providerId, wireProtocol }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeLocalCleanupModelDirSetting(modelDir: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_local_cleanup_model_dir_setting", { modelDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePostProcessApiKeySetting(providerId: string, apiKey: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_post_process_api_key_setting", { providerId, apiKey }) };
@@ -232,6 +416,41 @@ async updatePostProcessPrompt(id: string, name: string, prompt: string) : Promis
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Set how automatic prompt selection treats a prompt. Blank entries are
+ * dropped and the priority is clamped to the range routing honours.
+ */
+async updatePostProcessPromptRouting(id: string, triggers: string[], negativeKeywords: string[], apps: string[], priority: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_prompt_routing", { id, triggers, negativeKeywords, apps, priority }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Mark whether a prompt restructures the text; see `LLMPrompt::restructures`.
+ */
+async changePostProcessPromptRestructures(id: string, restructures: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_prompt_restructures", { id, restructures }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Explain which prompt `text` would be routed to, as if dictated in
+ * `app_name`, and why.
+ */
+async explainPromptRoute(text: string, appName: string | null) : Promise<Result<PromptRouteExplanation, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("explain_prompt_route", { text, appName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async deletePostProcessPrompt(id: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("delete_post_process_prompt", { id }) };
@@ -287,9 +506,9 @@ async changeMuteWhileRecordingSetting(enabled: boolean) : Promise<Result<null, s
     else return { status: "error", error: e  as any };
 }
 },
-async changeAudioSegmentSizeSecondsSetting(seconds: number) : Promise<Result<null, string>> {
+async changeSegmentOnSilenceMsSetting(ms: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_audio_segment_size_seconds_setting", { seconds }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_segment_on_silence_ms_setting", { ms }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -311,6 +530,17 @@ async changeAtFileExpansionSetting(enabled: boolean) : Promise<Result<null, stri
     else return { status: "error", error: e  as any };
 }
 },
+async getAvailablePipelineStages() : Promise<string[]> {
+    return await TAURI_INVOKE("get_available_pipeline_stages");
+},
+async updatePipelineStages(stages: PipelineStageConfig[]) : Promise<Result<PipelineStageConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_pipeline_stages", { stages }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateJargonProfiles(profiles: string[]) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_jargon_profiles", { profiles }) };
@@ -335,6 +565,14 @@ async updateJargonCustomCorrections(corrections: JargonCorrection[]) : Promise<R
     else return { status: "error", error: e  as any };
 }
 },
+async updateWorkspaceJargonEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_workspace_jargon_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getJargonBuiltinProfiles() : Promise<Partial<{ [key in string]: JargonProfile }>> {
     return await TAURI_INVOKE("get_jargon_builtin_profiles");
 },
@@ -386,9 +624,105 @@ async updateDomainSelectorBlendManualProfilesSetting(enabled: boolean) : Promise
     else return { status: "error", error: e  as any };
 }
 },
+async updateDomainSelectorLearnedWeightsSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_domain_selector_learned_weights_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetDomainSelectorFeedback() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_domain_selector_feedback") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateIntentRouterEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_intent_router_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeIntentRouterModelDirSetting(modelDir: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_intent_router_model_dir_setting", { modelDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateIntentRouterTimeoutMsSetting(timeoutMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_intent_router_timeout_ms_setting", { timeoutMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateIntentRouterMinConfidenceSetting(minConfidence: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_intent_router_min_confidence_setting", { minConfidence }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRerankerEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_reranker_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeRerankerModelDirSetting(modelDir: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_reranker_model_dir_setting", { modelDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeRerankerSecondPromptSetting(promptId: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_reranker_second_prompt_setting", { promptId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRerankerTimeoutMsSetting(timeoutMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_reranker_timeout_ms_setting", { timeoutMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getJargonPacks() : Promise<JargonPack[]> {
     return await TAURI_INVOKE("get_jargon_packs");
 },
+async acceptJargonProposals(proposals: JargonProposal[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("accept_jargon_proposals", { proposals }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async rejectJargonProposals(proposals: JargonProposal[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reject_jargon_proposals", { proposals }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateJargonPacks(packs: JargonPack[]) : Promise<Result<JargonPack[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_jargon_packs", { packs }) };
@@ -413,6 +747,41 @@ async exportJargonPacksJson(selectedPackIds: string[] | null) : Promise<Result<s
     else return { status: "error", error: e  as any };
 }
 },
+async previewConfigPack(json: string) : Promise<Result<ConfigPackPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_config_pack", { json }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importConfigPack(json: string, defaultStrategy: ImportStrategy, strategies: Partial<{ [key in string]: ImportStrategy }>) : Promise<Result<ConfigPackImportSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_config_pack", { json, defaultStrategy, strategies }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportConfigPack() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_config_pack") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateJargonSources(sources: JargonSource[]) : Promise<Result<JargonSourceStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_jargon_sources", { sources }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getJargonSourceStatuses() : Promise<JargonSourceStatus[]> {
+    return await TAURI_INVOKE("get_jargon_source_statuses");
+},
 async changeAppLanguageSetting(language: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_app_language_setting", { language }) };
@@ -814,6 +1183,14 @@ async deleteHistoryEntry(id: number) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async updateHistoryEntryText(id: number, text: string) : Promise<Result<JargonProposal[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_history_entry_text", { id, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateHistoryLimit(limit: number) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_history_limit", { limit }) };
@@ -856,14 +1233,38 @@ async isLaptop() : Promise<Result<boolean, string>> {
 
 /** user-defined types **/
 
-export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; auto_submit?: boolean; auto_submit_key?: AutoSubmitKey; post_process_enabled?: boolean; post_process_auto_prompt_selection?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; mute_while_recording?: boolean; audio_segment_size_seconds?: number; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; show_tray_icon?: boolean; paste_delay_ms?: number; typing_tool?: TypingTool; at_file_expansion_enabled?: boolean; recent_workspace_roots?: string[]; jargon_enabled_profiles?: string[]; jargon_custom_terms?: string[]; jargon_custom_corrections?: JargonCorrection[]; domain_selector_enabled?: boolean; domain_selector_timeout_ms?: number; domain_selector_top_k?: number; domain_selector_min_score?: number; domain_selector_hysteresis?: number; domain_selector_blend_manual_profiles?: boolean; jargon_packs?: JargonPack[] }
+export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; phonetic_matching_enabled?: boolean; phonetic_match_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; auto_submit?: boolean; auto_submit_key?: AutoSubmitKey; post_process_enabled?: boolean; post_process_auto_prompt_selection?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; post_process_streaming?: boolean; mute_while_recording?: boolean; segment_on_silence_ms?: number; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; show_tray_icon?: boolean; paste_delay_ms?: number; typing_tool?: TypingTool; at_file_expansion_enabled?: boolean; recent_workspace_roots?: string[]; jargon_enabled_profiles?: string[]; jargon_custom_terms?: string[]; jargon_custom_corrections?: JargonCorrection[]; domain_selector_enabled?: boolean; domain_selector_timeout_ms?: number; domain_selector_top_k?: number; domain_selector_min_score?: number; domain_selector_hysteresis?: number; domain_selector_blend_manual_profiles?: boolean; jargon_packs?: JargonPack[]; pipeline_stages?: PipelineStageConfig[]; post_process_fallbacks?: PostProcessFallback[]; post_process_timeout_ms?: number; post_process_divergence_guard?: boolean; local_cleanup_model_dir?: string | null; voice_commands_enabled?: boolean; voice_commands?: VoiceCommandConfig[]; templates_enabled?: boolean; templates?: TextTemplate[]; template_llm_fill?: boolean; cli_mode_enabled?: boolean; cli_mode_llm_assist?: boolean; cli_mode_deny_patterns?: string[]; snippets_enabled?: boolean; snippets?: Snippet[]; style_presets?: StylePreset[]; style_preset_bindings?: Partial<{ [key in string]: string }>; style_preset_app_rules?: StylePresetAppRule[]; jargon_sources?: JargonSource[]; workspace_jargon_enabled?: boolean; jargon_rejected_proposals?: JargonProposal[]; intent_router_enabled?: boolean; intent_router_model_dir?: string | null; intent_router_timeout_ms?: number; intent_router_min_confidence?: number; reranker_enabled?: boolean; reranker_model_dir?: string | null; reranker_second_prompt_id?: string | null; reranker_timeout_ms?: number; domain_selector_learned_weights?: boolean; domain_selector_profile_feedback?: Partial<{ [key in string]: ProfileFeedback }> }
 export type AudioDevice = { index: string; name: string; is_default: boolean }
 export type AutoSubmitKey = "enter" | "ctrl_enter" | "cmd_enter"
 export type BindingResponse = { success: boolean; binding: ShortcutBinding | null; error: string | null }
+export type CapitalizationPolicy = "keep" | 
+/**
+ * Capitalize the first letter of every sentence.
+ */
+"sentence" | "lowercase"
 export type ClipboardHandling = "dont_modify" | "copy_to_clipboard"
+export type ConfigPackImportSummary = { added: number; replaced: number; merged: number; kept: number; skipped: number }
+/**
+ * One importable entry, keyed `"<section>:<key>"` for per-item strategies.
+ */
+export type ConfigPackItem = { key: string; section: string; label: string; 
+/**
+ * An item with the same key is already configured.
+ */
+exists: boolean }
+export type ConfigPackPreview = { version: number; items: ConfigPackItem[] }
 export type CustomSounds = { start: boolean; stop: boolean }
 export type EngineType = "Whisper" | "Parakeet" | "Moonshine" | "SenseVoice"
-export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null }
+export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null; 
+/**
+ * Every post-processing attempt in the order it was made. Empty when
+ * post-processing did not run.
+ */
+post_process_attempts: PostProcessAttempt[]; 
+/**
+ * `transcription_text` as the user corrected it, if they edited it.
+ */
+edited_text: string | null }
 /**
  * Result of changing keyboard implementation
  */
@@ -872,22 +1273,270 @@ export type ImplementationChangeResult = { success: boolean;
  * List of binding IDs that were reset to defaults due to incompatibility
  */
 reset_bindings: string[] }
-export type JargonCorrection = { from: string; to: string }
+/**
+ * What to do with an imported item.
+ */
+export type ImportStrategy = 
+/**
+ * Add the item if new, otherwise keep the existing one.
+ */
+"keep_existing" | 
+/**
+ * Add the item, overwriting an existing one with the same key.
+ */
+"replace" | 
+/**
+ * Combine with an existing item. Jargon packs get the union of their
+ * terms and corrections; other items take the imported version.
+ */
+"merge" | 
+/**
+ * Leave the item out of the import.
+ */
+"skip"
+export type JargonCorrection = { from: string; to: string; 
+/**
+ * Only apply when one of these words is a neighbour; empty means always.
+ */
+near?: string[]; 
+/**
+ * Never apply when one of these words is a neighbour.
+ */
+not_near?: string[]; 
+/**
+ * Match `from` with its exact casing instead of ignoring case.
+ */
+case_sensitive?: boolean; 
+/**
+ * Only apply when `from` is all that was said.
+ */
+whole_utterance?: boolean }
 export type JargonPack = { id: string; label: string; terms?: string[]; corrections?: JargonCorrection[] }
 export type JargonProfile = { label: string; terms: string[]; corrections: JargonCorrection[] }
+export type JargonProposal = { kind: ProposalKind; 
+/**
+ * The span as it was transcribed.
+ */
+from: string; 
+/**
+ * The span as the user edited it.
+ */
+to: string }
+export type JargonSource = { id: string; label: string; 
+/**
+ * File or folder on disk.
+ */
+path: string; enabled?: boolean; 
+/**
+ * Only used for these bindings; empty means every binding.
+ */
+binding_ids?: string[]; 
+/**
+ * Only used when post-processing with these prompts; empty means always.
+ */
+prompt_ids?: string[] }
+export type JargonSourceStatus = { id: string; terms: number; corrections: number; 
+/**
+ * Why the source could not be read; its last good contents stay in use.
+ */
+error: string | null }
 export type KeyboardImplementation = "tauri" | "handy_keys"
-export type LLMPrompt = { id: string; name: string; prompt: string }
+export type LLMPrompt = { id: string; name: string; prompt: string; 
+/**
+ * Words and phrases that point automatic prompt selection at this prompt,
+ * on top of its id and name.
+ */
+triggers?: string[]; 
+/**
+ * Text containing any of these is never routed to this prompt.
+ */
+negative_keywords?: string[]; 
+/**
+ * Frontmost apps the prompt may be auto-selected in, matched like style
+ * preset app rules. Empty means any app.
+ */
+apps?: string[]; 
+/**
+ * Raises (or lowers) the prompt's routing score in small steps; see
+ * `managers::domain_selector`.
+ */
+priority?: number; 
+/**
+ * The prompt reshapes the transcript (notes, lists, drafts) rather than
+ * cleaning it up, so the divergence guard only checks that protected
+ * spans survive.
+ */
+restructures?: boolean }
 export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
 export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[]; is_custom: boolean }
 export type ModelLoadStatus = { is_loaded: boolean; current_model: string | null }
 export type ModelUnloadTimeout = "never" | "immediately" | "min_2" | "min_5" | "min_10" | "min_15" | "hour_1" | "sec_5"
+export type NumberFormat = "keep" | 
+/**
+ * Write numbers from ten up as digits ("twenty five" -> "25").
+ */
+"digits" | 
+/**
+ * Spell out single digits ("3" -> "three").
+ */
+"spell_small"
 export type OverlayPosition = "none" | "top" | "bottom"
 export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v"
-export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null }
+export type PipelineStageConfig = { id: string; enabled?: boolean }
+/**
+ * One provider/model tried while post-processing a transcription.
+ * Stored as a JSON array alongside the history entry.
+ */
+export type PostProcessAttempt = { provider_id: string; model: string; succeeded: boolean; elapsed_ms: number; 
+/**
+ * Why the attempt failed; `None` for the attempt that succeeded.
+ */
+error: string | null }
+/**
+ * A (provider, model) pair tried when the active post-process provider fails.
+ */
+export type PostProcessFallback = { provider_id: string; model: string }
+export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null; wire_protocol?: WireProtocol }
+export type ProfileFeedback = { 
+/**
+ * Times the profile was enabled, or a dictation was edited towards its terms.
+ */
+kept: number; 
+/**
+ * Times the profile was disabled, or its terms were edited out.
+ */
+corrected: number }
+export type PromptRouteCandidate = { prompt_id: string; name: string; 
+/**
+ * Routing score after priority; 0 when nothing matched.
+ */
+score: number; 
+/**
+ * Words of the text found in the prompt's id, name or triggers.
+ */
+matched_words: string[]; matched_triggers: string[]; 
+/**
+ * Negative keyword that ruled the prompt out.
+ */
+blocked_by: string | null; 
+/**
+ * The prompt is limited to other apps.
+ */
+wrong_app: boolean; priority: number }
+export type PromptRouteExplanation = { 
+/**
+ * Prompt the text routes to, or `None` to fall back to the selected one.
+ */
+prompt_id: string | null; outcome: PromptRouteOutcome; min_score: number; 
+/**
+ * Every prompt, in settings order.
+ */
+candidates: PromptRouteCandidate[] }
+/**
+ * How prompt routing treated a text, as reported by `explain_prompt_route`.
+ */
+export type PromptRouteOutcome = 
+/**
+ * Automatic prompt selection is off; the selected prompt is used.
+ */
+"disabled" | 
+/**
+ * No prompt reached the minimum score; the selected prompt is used.
+ */
+"below_min_score" | 
+/**
+ * The best-scoring prompt is used.
+ */
+"best_match" | 
+/**
+ * The previous prompt is used: the best one did not beat it by the
+ * hysteresis margin.
+ */
+"kept_previous"
+export type ProposalKind = 
+/**
+ * Replace `from` with `to` in future transcriptions.
+ */
+"correction" | 
+/**
+ * Add `to` as a term, spelled the way it was typed.
+ */
+"custom_word"
+export type PunctuationPolicy = "keep" | 
+/**
+ * Drop the full stop at the very end, as in chat messages.
+ */
+"no_trailing_period" | 
+/**
+ * Remove sentence punctuation from the ends of words.
+ */
+"strip"
 export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3"
 export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string }
+export type Snippet = { id: string; 
+/**
+ * Phrase that is replaced by `body` when spoken.
+ */
+trigger: string; 
+/**
+ * Inserted text; may contain `{{date}}`, `{{time}}` and `{{clipboard}}`.
+ */
+body: string; enabled?: boolean }
 export type SoundTheme = "marimba" | "pop" | "custom"
+export type StylePreset = { id: string; name: string; punctuation?: PunctuationPolicy; capitalization?: CapitalizationPolicy; remove_fillers?: boolean; numbers?: NumberFormat; 
+/**
+ * Sentences longer than this many words are split at a comma or
+ * conjunction where possible.
+ */
+max_sentence_words?: number | null; 
+/**
+ * Extra instructions for the LLM; unused without post-processing.
+ */
+rewrite_instructions?: string }
+/**
+ * Assigns a preset to every frontmost app whose name contains `app`,
+ * ignoring case.
+ */
+export type StylePresetAppRule = { app: string; preset_id: string }
+export type TextTemplate = { id: string; 
+/**
+ * Spoken after the trigger word to pick this template.
+ */
+name: string; 
+/**
+ * Template text with `{{placeholder}}` fields.
+ */
+body: string; enabled?: boolean }
 export type TypingTool = "auto" | "wtype" | "kwtype" | "dotool" | "ydotool" | "xdotool"
+/**
+ * A spoken command and the phrases that trigger it.
+ */
+export type VoiceCommandConfig = { kind: VoiceCommandKind; phrases: string[]; enabled?: boolean }
+export type VoiceCommandKind = "new_line" | "new_paragraph" | 
+/**
+ * Drop the text dictated since the previous command.
+ */
+"scratch_that" | "delete_last_sentence" | 
+/**
+ * Send the platform undo shortcut to the focused app.
+ */
+"undo_last" | "capitalize_last_word" | 
+/**
+ * Extend the selection over the word before the cursor.
+ */
+"select_last_word"
+/**
+ * Request/response format a post-process provider speaks.
+ */
+export type WireProtocol = 
+/**
+ * OpenAI-compatible `/chat/completions`.
+ */
+"openai_chat" | 
+/**
+ * Anthropic `/messages`.
+ */
+"anthropic_messages"
 
 /** tauri-specta globals **/
 
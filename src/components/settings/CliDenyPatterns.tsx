@@ -0,0 +1,47 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+interface CliDenyPatternsProps {
+  grouped?: boolean;
+}
+
+export const CliDenyPatterns: React.FC<CliDenyPatternsProps> = React.memo(
+  ({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const patterns = getSetting("cli_mode_deny_patterns") ?? [];
+
+    const save = (value: string) => {
+      const next = value
+        .split("\n")
+        .map((line) => line.trim())
+        .filter((line) => line.length > 0);
+      if (next.join("\n") === patterns.join("\n")) {
+        return;
+      }
+      updateSetting("cli_mode_deny_patterns", next);
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.cliMode.denyPatterns.title")}
+        description={t("settings.advanced.cliMode.denyPatterns.description")}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <Textarea
+          defaultValue={patterns.join("\n")}
+          onBlur={(event) => save(event.target.value)}
+          placeholder={t("settings.advanced.cliMode.denyPatterns.placeholder")}
+          variant="compact"
+          className="w-full font-mono"
+          disabled={isUpdating("cli_mode_deny_patterns")}
+        />
+      </SettingContainer>
+    );
+  },
+);
@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface CliLlmAssistToggleProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const CliLlmAssistToggle: React.FC<CliLlmAssistToggleProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("cli_mode_llm_assist") || false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) => updateSetting("cli_mode_llm_assist", enabled)}
+        isUpdating={isUpdating("cli_mode_llm_assist")}
+        label={t("settings.advanced.cliMode.llmAssist.label")}
+        description={t("settings.advanced.cliMode.llmAssist.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
@@ -0,0 +1,226 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { readTextFile, writeTextFile } from "@tauri-apps/plugin-fs";
+import {
+  commands,
+  type ConfigPackPreview,
+  type ImportStrategy,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface ConfigPackSettingsProps {
+  grouped?: boolean;
+}
+
+const STRATEGIES: ImportStrategy[] = [
+  "keep_existing",
+  "replace",
+  "merge",
+  "skip",
+];
+
+export const ConfigPackSettings: React.FC<ConfigPackSettingsProps> =
+  React.memo(({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { refreshSettings } = useSettings();
+
+    const [json, setJson] = useState<string | null>(null);
+    const [preview, setPreview] = useState<ConfigPackPreview | null>(null);
+    const [defaultStrategy, setDefaultStrategy] =
+      useState<ImportStrategy>("keep_existing");
+    const [strategies, setStrategies] = useState<
+      Record<string, ImportStrategy>
+    >({});
+    const [message, setMessage] = useState<string | null>(null);
+    const [error, setError] = useState<string | null>(null);
+    const [busy, setBusy] = useState(false);
+
+    const strategyOptions = STRATEGIES.map((value) => ({
+      value,
+      label: t(`settings.advanced.configPacks.strategies.${value}`),
+    }));
+
+    const reset = () => {
+      setJson(null);
+      setPreview(null);
+      setStrategies({});
+    };
+
+    const choosePack = async () => {
+      setMessage(null);
+      setError(null);
+      try {
+        const selected = await open({
+          title: t("settings.advanced.configPacks.importDialogTitle"),
+          filters: [{ name: "JSON", extensions: ["json"] }],
+          multiple: false,
+        });
+        if (!selected || Array.isArray(selected)) {
+          return;
+        }
+        const text = await readTextFile(selected);
+        const result = await commands.previewConfigPack(text);
+        if (result.status === "error") {
+          setError(result.error);
+          reset();
+          return;
+        }
+        setJson(text);
+        setPreview(result.data);
+        setStrategies({});
+      } catch (e) {
+        console.error("Failed to read config pack:", e);
+      }
+    };
+
+    const applyImport = async () => {
+      if (!json) {
+        return;
+      }
+      setBusy(true);
+      try {
+        const result = await commands.importConfigPack(
+          json,
+          defaultStrategy,
+          strategies,
+        );
+        if (result.status === "error") {
+          setError(result.error);
+          return;
+        }
+        setError(null);
+        setMessage(t("settings.advanced.configPacks.imported", result.data));
+        reset();
+        await refreshSettings();
+      } catch (e) {
+        console.error("Failed to import config pack:", e);
+      } finally {
+        setBusy(false);
+      }
+    };
+
+    const exportPack = async () => {
+      setMessage(null);
+      setError(null);
+      try {
+        const result = await commands.exportConfigPack();
+        if (result.status === "error") {
+          setError(result.error);
+          return;
+        }
+        const path = await save({
+          title: t("settings.advanced.configPacks.exportDialogTitle"),
+          defaultPath: "spittle-config-pack.json",
+          filters: [{ name: "JSON", extensions: ["json"] }],
+        });
+        if (!path) {
+          return;
+        }
+        await writeTextFile(path, result.data);
+      } catch (e) {
+        console.error("Failed to export config pack:", e);
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.configPacks.title")}
+        description={t("settings.advanced.configPacks.description")}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          <div className="flex flex-wrap gap-2">
+            <Button
+              onClick={choosePack}
+              variant="secondary"
+              size="sm"
+              disabled={busy}
+            >
+              {t("settings.advanced.configPacks.import")}
+            </Button>
+            <Button
+              onClick={exportPack}
+              variant="secondary"
+              size="sm"
+              disabled={busy}
+            >
+              {t("settings.advanced.configPacks.export")}
+            </Button>
+          </div>
+
+          {preview && (
+            <div className="p-3 border border-mid-gray/20 rounded-lg space-y-2">
+              <div className="flex items-center gap-2 text-sm">
+                <span className="flex-1">
+                  {t("settings.advanced.configPacks.defaultStrategy")}
+                </span>
+                <Dropdown
+                  options={strategyOptions}
+                  selectedValue={defaultStrategy}
+                  onSelect={(value) =>
+                    setDefaultStrategy(value as ImportStrategy)
+                  }
+                  disabled={busy}
+                />
+              </div>
+              {preview.items.map((item) => (
+                <div key={item.key} className="flex items-center gap-2 text-sm">
+                  <span className="w-28 text-xs text-mid-gray">
+                    {t(
+                      `settings.advanced.configPacks.sections.${item.section}`,
+                    )}
+                  </span>
+                  <span className="flex-1 truncate">
+                    {item.label}
+                    {item.exists && (
+                      <span className="ml-2 text-xs text-mid-gray">
+                        {t("settings.advanced.configPacks.exists")}
+                      </span>
+                    )}
+                  </span>
+                  <Dropdown
+                    options={strategyOptions}
+                    selectedValue={strategies[item.key] ?? defaultStrategy}
+                    onSelect={(value) =>
+                      setStrategies((prev) => ({
+                        ...prev,
+                        [item.key]: value as ImportStrategy,
+                      }))
+                    }
+                    disabled={busy}
+                  />
+                </div>
+              ))}
+              <div className="flex gap-2">
+                <Button
+                  onClick={applyImport}
+                  variant="primary"
+                  size="sm"
+                  disabled={busy || preview.items.length === 0}
+                >
+                  {t("settings.advanced.configPacks.apply")}
+                </Button>
+                <Button
+                  onClick={reset}
+                  variant="secondary"
+                  size="sm"
+                  disabled={busy}
+                >
+                  {t("common.cancel")}
+                </Button>
+              </div>
+            </div>
+          )}
+
+          {message && <p className="text-xs text-mid-gray">{message}</p>}
+          {error && <p className="text-xs text-red-400">{error}</p>}
+        </div>
+      </SettingContainer>
+    );
+  });
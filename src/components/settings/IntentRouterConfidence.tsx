@@ -0,0 +1,38 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface IntentRouterConfidenceProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const IntentRouterConfidence: React.FC<IntentRouterConfidenceProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const minConfidence = getSetting("intent_router_min_confidence") ?? 0.6;
+
+    return (
+      <Slider
+        value={minConfidence}
+        onChange={(value) =>
+          updateSetting("intent_router_min_confidence", value)
+        }
+        min={0.3}
+        max={0.95}
+        step={0.05}
+        disabled={isUpdating("intent_router_min_confidence")}
+        label={t("settings.advanced.intentRouter.minConfidence.label")}
+        description={t(
+          "settings.advanced.intentRouter.minConfidence.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        showValue={true}
+        formatValue={(value) => value.toFixed(2)}
+      />
+    );
+  });
@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface IntentRouterToggleProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const IntentRouterToggle: React.FC<IntentRouterToggleProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("intent_router_enabled") || false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) => updateSetting("intent_router_enabled", enabled)}
+        isUpdating={isUpdating("intent_router_enabled")}
+        label={t("settings.advanced.intentRouter.enabled.label")}
+        description={t("settings.advanced.intentRouter.enabled.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
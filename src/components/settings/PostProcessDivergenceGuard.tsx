@@ -0,0 +1,33 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PostProcessDivergenceGuardProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PostProcessDivergenceGuard: React.FC<PostProcessDivergenceGuardProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("post_process_divergence_guard") ?? true;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) =>
+          updateSetting("post_process_divergence_guard", enabled)
+        }
+        isUpdating={isUpdating("post_process_divergence_guard")}
+        label={t("settings.postProcessing.delivery.divergenceGuard.label")}
+        description={t(
+          "settings.postProcessing.delivery.divergenceGuard.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
@@ -0,0 +1,146 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type PostProcessFallback } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface PostProcessFallbacksProps {
+  grouped?: boolean;
+}
+
+export const PostProcessFallbacks: React.FC<PostProcessFallbacksProps> =
+  React.memo(({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const fallbacks = getSetting("post_process_fallbacks") ?? [];
+    const providers = getSetting("post_process_providers") ?? [];
+
+    const [providerId, setProviderId] = useState<string | null>(null);
+    const [model, setModel] = useState("");
+    const [error, setError] = useState<string | null>(null);
+    const [saving, setSaving] = useState(false);
+
+    const providerLabel = (id: string) =>
+      providers.find((provider) => provider.id === id)?.label ?? id;
+
+    const save = async (next: PostProcessFallback[]) => {
+      setSaving(true);
+      try {
+        const result = await commands.updatePostProcessFallbacks(next);
+        if (result.status === "error") {
+          setError(result.error);
+          return false;
+        }
+        setError(null);
+        await refreshSettings();
+        return true;
+      } catch (e) {
+        console.error("Failed to update post-processing fallbacks:", e);
+        return false;
+      } finally {
+        setSaving(false);
+      }
+    };
+
+    const addFallback = async () => {
+      if (!providerId || !model.trim()) {
+        return;
+      }
+      const next = [
+        ...fallbacks,
+        { provider_id: providerId, model: model.trim() },
+      ];
+      if (await save(next)) {
+        setProviderId(null);
+        setModel("");
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.postProcessing.delivery.fallbacks.title")}
+        description={t(
+          "settings.postProcessing.delivery.fallbacks.description",
+        )}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          {fallbacks.map((fallback, index) => (
+            <div
+              key={`${fallback.provider_id}-${index}`}
+              className="flex items-center gap-2"
+            >
+              <span className="text-sm w-40 truncate">
+                {providerLabel(fallback.provider_id)}
+              </span>
+              <Input
+                defaultValue={fallback.model}
+                onBlur={(event) => {
+                  const value = event.target.value.trim();
+                  if (value && value !== fallback.model) {
+                    save(
+                      fallbacks.map((entry, i) =>
+                        i === index ? { ...entry, model: value } : entry,
+                      ),
+                    );
+                  }
+                }}
+                variant="compact"
+                className="flex-1"
+                disabled={saving}
+              />
+              <Button
+                onClick={() => save(fallbacks.filter((_, i) => i !== index))}
+                variant="secondary"
+                size="sm"
+                disabled={saving}
+              >
+                {t("common.delete")}
+              </Button>
+            </div>
+          ))}
+
+          <div className="flex items-center gap-2">
+            <Dropdown
+              options={providers.map((provider) => ({
+                value: provider.id,
+                label: provider.label,
+              }))}
+              selectedValue={providerId}
+              onSelect={setProviderId}
+              placeholder={t(
+                "settings.postProcessing.delivery.fallbacks.providerPlaceholder",
+              )}
+              className="w-40"
+              disabled={saving}
+            />
+            <Input
+              value={model}
+              onChange={(event) => setModel(event.target.value)}
+              placeholder={t(
+                "settings.postProcessing.delivery.fallbacks.modelPlaceholder",
+              )}
+              variant="compact"
+              className="flex-1"
+              disabled={saving}
+            />
+            <Button
+              onClick={addFallback}
+              variant="primary"
+              size="sm"
+              disabled={saving || !providerId || !model.trim()}
+            >
+              {t("common.add")}
+            </Button>
+          </div>
+
+          {error && <p className="text-xs text-red-400">{error}</p>}
+        </div>
+      </SettingContainer>
+    );
+  });
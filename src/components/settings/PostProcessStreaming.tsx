@@ -0,0 +1,33 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PostProcessStreamingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PostProcessStreaming: React.FC<PostProcessStreamingProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("post_process_streaming") || false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) =>
+          updateSetting("post_process_streaming", enabled)
+        }
+        isUpdating={isUpdating("post_process_streaming")}
+        label={t("settings.postProcessing.delivery.streaming.label")}
+        description={t(
+          "settings.postProcessing.delivery.streaming.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
@@ -0,0 +1,34 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PostProcessTimeoutProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PostProcessTimeout: React.FC<PostProcessTimeoutProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const timeoutMs = getSetting("post_process_timeout_ms") ?? 15000;
+
+    return (
+      <Slider
+        value={timeoutMs}
+        onChange={(value) => updateSetting("post_process_timeout_ms", value)}
+        min={1000}
+        max={120000}
+        step={1000}
+        disabled={isUpdating("post_process_timeout_ms")}
+        label={t("settings.postProcessing.delivery.timeout.label")}
+        description={t("settings.postProcessing.delivery.timeout.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        showValue={true}
+        formatValue={(value) => `${Math.round(value / 1000)}s`}
+      />
+    );
+  });
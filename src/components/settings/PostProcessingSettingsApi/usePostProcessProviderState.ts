@@ -11,6 +11,11 @@ type PostProcessProviderState = {
   isCustomProvider: boolean;
   isAppleProvider: boolean;
   appleIntelligenceUnavailable: boolean;
+  isLocalCleanupProvider: boolean;
+  localModelDir: string;
+  handleLocalModelDirChange: (value: string) => void;
+  isLocalModelDirUpdating: boolean;
+  localModelDirError: string | null;
   baseUrl: string;
   handleBaseUrlChange: (value: string) => void;
   isBaseUrlUpdating: boolean;
@@ -29,6 +34,7 @@ type PostProcessProviderState = {
 };
 
 const APPLE_PROVIDER_ID = "apple_intelligence";
+const LOCAL_CLEANUP_PROVIDER_ID = "local_cleanup";
 
 export const usePostProcessProviderState = (): PostProcessProviderState => {
   const {
@@ -40,6 +46,7 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     updatePostProcessModel,
     fetchPostProcessModels,
     postProcessModelOptions,
+    refreshSettings,
   } = useSettings();
 
   // Settings are guaranteed to have providers after migration
@@ -60,6 +67,15 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
   const [appleIntelligenceUnavailable, setAppleIntelligenceUnavailable] =
     useState(false);
 
+  const isLocalCleanupProvider =
+    selectedProvider?.id === LOCAL_CLEANUP_PROVIDER_ID;
+  const localModelDir = settings?.local_cleanup_model_dir ?? "";
+  const [isLocalModelDirUpdating, setIsLocalModelDirUpdating] =
+    useState(false);
+  const [localModelDirError, setLocalModelDirError] = useState<string | null>(
+    null,
+  );
+
   // Use settings directly as single source of truth
   const baseUrl = selectedProvider?.base_url ?? "";
   const apiKey = settings?.post_process_api_keys?.[selectedProviderId] ?? "";
@@ -107,6 +123,29 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     [selectedProvider, baseUrl, updatePostProcessBaseUrl],
   );
 
+  const handleLocalModelDirChange = useCallback(
+    async (value: string) => {
+      const trimmed = value.trim();
+      if (trimmed === localModelDir) return;
+
+      setIsLocalModelDirUpdating(true);
+      try {
+        const result = await commands.changeLocalCleanupModelDirSetting(
+          trimmed || null,
+        );
+        if (result.status === "error") {
+          setLocalModelDirError(result.error);
+          return;
+        }
+        setLocalModelDirError(null);
+        await refreshSettings();
+      } finally {
+        setIsLocalModelDirUpdating(false);
+      }
+    },
+    [localModelDir, refreshSettings],
+  );
+
   const handleApiKeyChange = useCallback(
     (value: string) => {
       const trimmed = value.trim();
@@ -142,9 +181,14 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
   );
 
   const handleRefreshModels = useCallback(() => {
-    if (isAppleProvider) return;
+    if (isAppleProvider || isLocalCleanupProvider) return;
     void fetchPostProcessModels(selectedProviderId);
-  }, [fetchPostProcessModels, isAppleProvider, selectedProviderId]);
+  }, [
+    fetchPostProcessModels,
+    isAppleProvider,
+    isLocalCleanupProvider,
+    selectedProviderId,
+  ]);
 
   const availableModelsRaw = postProcessModelOptions[selectedProviderId] || [];
 
@@ -194,6 +238,12 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     isCustomProvider,
     isAppleProvider,
     appleIntelligenceUnavailable,
+    isLocalCleanupProvider,
+    localModelDir,
+    handleLocalModelDirChange: (value: string) =>
+      void handleLocalModelDirChange(value),
+    isLocalModelDirUpdating,
+    localModelDirError,
     baseUrl,
     handleBaseUrlChange,
     isBaseUrlUpdating,
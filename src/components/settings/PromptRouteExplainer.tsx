@@ -0,0 +1,142 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import {
+  commands,
+  type PromptRouteCandidate,
+  type PromptRouteExplanation,
+} from "@/bindings";
+import { Alert } from "../ui/Alert";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+interface PromptRouteExplainerProps {
+  grouped?: boolean;
+}
+
+export const PromptRouteExplainer: React.FC<PromptRouteExplainerProps> = ({
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const [text, setText] = useState("");
+  const [appName, setAppName] = useState("");
+  const [explanation, setExplanation] =
+    useState<PromptRouteExplanation | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isExplaining, setIsExplaining] = useState(false);
+
+  const handleExplain = async () => {
+    if (!text.trim()) return;
+
+    setIsExplaining(true);
+    try {
+      const result = await commands.explainPromptRoute(
+        text,
+        appName.trim() || null,
+      );
+      if (result.status === "error") {
+        setError(result.error);
+        setExplanation(null);
+        return;
+      }
+      setError(null);
+      setExplanation(result.data);
+    } finally {
+      setIsExplaining(false);
+    }
+  };
+
+  const describe = (candidate: PromptRouteCandidate) => {
+    if (candidate.blocked_by !== null) {
+      return t("settings.postProcessing.prompts.explain.blockedBy", {
+        keyword: candidate.blocked_by,
+      });
+    }
+    if (candidate.wrong_app) {
+      return t("settings.postProcessing.prompts.explain.wrongApp");
+    }
+    if (candidate.matched_words.length === 0) {
+      return t("settings.postProcessing.prompts.explain.noMatch");
+    }
+    return t("settings.postProcessing.prompts.explain.matched", {
+      words: candidate.matched_words.join(", "),
+    });
+  };
+
+  const routedName = explanation?.candidates.find(
+    (candidate) => candidate.prompt_id === explanation.prompt_id,
+  )?.name;
+
+  return (
+    <SettingContainer
+      title={t("settings.postProcessing.prompts.explain.title")}
+      description={t("settings.postProcessing.prompts.explain.description")}
+      descriptionMode="tooltip"
+      layout="stacked"
+      grouped={grouped}
+    >
+      <div className="space-y-3">
+        <Textarea
+          value={text}
+          onChange={(e) => setText(e.target.value)}
+          placeholder={t(
+            "settings.postProcessing.prompts.explain.placeholder",
+          )}
+        />
+        <div className="flex gap-2">
+          <Input
+            type="text"
+            value={appName}
+            onChange={(e) => setAppName(e.target.value)}
+            placeholder={t(
+              "settings.postProcessing.prompts.explain.appPlaceholder",
+            )}
+            variant="compact"
+            className="flex-1"
+          />
+          <Button
+            onClick={handleExplain}
+            variant="primary"
+            size="md"
+            disabled={!text.trim() || isExplaining}
+          >
+            {t("settings.postProcessing.prompts.explain.run")}
+          </Button>
+        </div>
+
+        {error && (
+          <Alert variant="error" contained>
+            {error}
+          </Alert>
+        )}
+
+        {explanation && (
+          <div className="space-y-2 text-sm">
+            <p className="font-semibold">
+              {t(
+                `settings.postProcessing.prompts.explain.outcomes.${explanation.outcome}`,
+                {
+                  prompt: routedName ?? explanation.prompt_id,
+                  minScore: explanation.min_score.toFixed(2),
+                },
+              )}
+            </p>
+            {explanation.candidates.map((candidate) => (
+              <div
+                key={candidate.prompt_id}
+                className="flex items-start gap-2 text-xs"
+              >
+                <span className="flex-1 font-semibold">{candidate.name}</span>
+                <span className="flex-[2] text-mid-gray">
+                  {describe(candidate)}
+                </span>
+                <span className="font-mono">{candidate.score.toFixed(2)}</span>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </SettingContainer>
+  );
+};
@@ -0,0 +1,148 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type LLMPrompt } from "@/bindings";
+import { Alert } from "../ui/Alert";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PromptRoutingEditorProps {
+  prompt: LLMPrompt;
+}
+
+const splitList = (value: string) =>
+  value
+    .split(",")
+    .map((entry) => entry.trim())
+    .filter(Boolean);
+
+const joinList = (entries: string[] | undefined) => (entries ?? []).join(", ");
+
+export const PromptRoutingEditor: React.FC<PromptRoutingEditorProps> = ({
+  prompt,
+}) => {
+  const { t } = useTranslation();
+  const { refreshSettings } = useSettings();
+  const [triggers, setTriggers] = useState("");
+  const [negativeKeywords, setNegativeKeywords] = useState("");
+  const [apps, setApps] = useState("");
+  const [priority, setPriority] = useState("0");
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setTriggers(joinList(prompt.triggers));
+    setNegativeKeywords(joinList(prompt.negative_keywords));
+    setApps(joinList(prompt.apps));
+    setPriority(String(prompt.priority ?? 0));
+    setError(null);
+  }, [prompt]);
+
+  const isDirty =
+    triggers !== joinList(prompt.triggers) ||
+    negativeKeywords !== joinList(prompt.negative_keywords) ||
+    apps !== joinList(prompt.apps) ||
+    priority !== String(prompt.priority ?? 0);
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      const result = await commands.updatePostProcessPromptRouting(
+        prompt.id,
+        splitList(triggers),
+        splitList(negativeKeywords),
+        splitList(apps),
+        Number.parseInt(priority, 10) || 0,
+      );
+      if (result.status === "error") {
+        setError(result.error);
+        return;
+      }
+      setError(null);
+      await refreshSettings();
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const fields = [
+    {
+      key: "triggers",
+      value: triggers,
+      onChange: setTriggers,
+    },
+    {
+      key: "negativeKeywords",
+      value: negativeKeywords,
+      onChange: setNegativeKeywords,
+    },
+    {
+      key: "apps",
+      value: apps,
+      onChange: setApps,
+    },
+  ];
+
+  return (
+    <div className="space-y-3 pt-2 border-t border-mid-gray/20">
+      <div className="space-y-1">
+        <p className="text-sm font-semibold">
+          {t("settings.postProcessing.prompts.routing.title")}
+        </p>
+        <p className="text-xs text-mid-gray/70">
+          {t("settings.postProcessing.prompts.routing.description")}
+        </p>
+      </div>
+
+      {fields.map((field) => (
+        <div key={field.key} className="space-y-2 flex flex-col">
+          <label className="text-sm font-semibold">
+            {t(
+              `settings.postProcessing.prompts.routing.${field.key}.label`,
+            )}
+          </label>
+          <Input
+            type="text"
+            value={field.value}
+            onChange={(e) => field.onChange(e.target.value)}
+            placeholder={t(
+              `settings.postProcessing.prompts.routing.${field.key}.placeholder`,
+            )}
+            variant="compact"
+            disabled={isSaving}
+          />
+        </div>
+      ))}
+
+      <div className="flex items-center gap-2">
+        <label className="text-sm font-semibold flex-1">
+          {t("settings.postProcessing.prompts.routing.priority")}
+        </label>
+        <Input
+          type="number"
+          min={-5}
+          max={5}
+          value={priority}
+          onChange={(e) => setPriority(e.target.value)}
+          variant="compact"
+          className="w-20"
+          disabled={isSaving}
+        />
+        <Button
+          onClick={handleSave}
+          variant="primary"
+          size="md"
+          disabled={!isDirty || isSaving}
+        >
+          {t("settings.postProcessing.prompts.routing.save")}
+        </Button>
+      </div>
+
+      {error && (
+        <Alert variant="error" contained>
+          {error}
+        </Alert>
+      )}
+    </div>
+  );
+};
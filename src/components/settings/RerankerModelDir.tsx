@@ -0,0 +1,74 @@
+import React, { useCallback, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands } from "@/bindings";
+import { Alert } from "../ui/Alert";
+import { SettingContainer } from "../ui/SettingContainer";
+import { LocalModelDirField } from "./PostProcessingSettingsApi/LocalModelDirField";
+import { useSettings } from "../../hooks/useSettings";
+
+interface RerankerModelDirProps {
+  grouped?: boolean;
+}
+
+export const RerankerModelDir: React.FC<RerankerModelDirProps> = ({
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const [isUpdating, setIsUpdating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const modelDir = getSetting("reranker_model_dir") ?? "";
+
+  const handleChange = useCallback(
+    async (value: string) => {
+      const trimmed = value.trim();
+      if (trimmed === modelDir) return;
+
+      setIsUpdating(true);
+      try {
+        const result = await commands.changeRerankerModelDirSetting(
+          trimmed || null,
+        );
+        if (result.status === "error") {
+          setError(result.error);
+          return;
+        }
+        setError(null);
+        await refreshSettings();
+      } finally {
+        setIsUpdating(false);
+      }
+    },
+    [modelDir, refreshSettings],
+  );
+
+  return (
+    <>
+      <SettingContainer
+        title={t("settings.postProcessing.reranker.modelDir.title")}
+        description={t("settings.postProcessing.reranker.modelDir.description")}
+        descriptionMode="tooltip"
+        layout="horizontal"
+        grouped={grouped}
+      >
+        <div className="flex items-center gap-2">
+          <LocalModelDirField
+            value={modelDir}
+            onBlur={handleChange}
+            placeholder={t(
+              "settings.postProcessing.reranker.modelDir.placeholder",
+            )}
+            disabled={isUpdating}
+            className="min-w-[380px]"
+          />
+        </div>
+      </SettingContainer>
+      {error && (
+        <Alert variant="error" contained>
+          {error}
+        </Alert>
+      )}
+    </>
+  );
+};
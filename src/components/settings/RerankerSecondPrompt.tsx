@@ -0,0 +1,49 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface RerankerSecondPromptProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const NO_PROMPT = "";
+
+export const RerankerSecondPrompt: React.FC<RerankerSecondPromptProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const prompts = getSetting("post_process_prompts") || [];
+    const selectedId = getSetting("reranker_second_prompt_id") ?? NO_PROMPT;
+
+    const options = [
+      {
+        value: NO_PROMPT,
+        label: t("settings.postProcessing.reranker.secondPrompt.none"),
+      },
+      ...prompts.map((prompt) => ({ value: prompt.id, label: prompt.name })),
+    ];
+
+    return (
+      <SettingContainer
+        title={t("settings.postProcessing.reranker.secondPrompt.title")}
+        description={t(
+          "settings.postProcessing.reranker.secondPrompt.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={options}
+          selectedValue={selectedId}
+          onSelect={(value) =>
+            updateSetting("reranker_second_prompt_id", value || null)
+          }
+          disabled={isUpdating("reranker_second_prompt_id")}
+        />
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface RerankerToggleProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const RerankerToggle: React.FC<RerankerToggleProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("reranker_enabled") || false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) => updateSetting("reranker_enabled", enabled)}
+        isUpdating={isUpdating("reranker_enabled")}
+        label={t("settings.postProcessing.reranker.enabled.label")}
+        description={t("settings.postProcessing.reranker.enabled.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
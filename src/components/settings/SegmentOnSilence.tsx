@@ -13,25 +13,24 @@ export const SegmentOnSilence: React.FC<SegmentOnSilenceProps> = React.memo(
     const { t } = useTranslation();
     const { getSetting, updateSetting, isUpdating } = useSettings();
 
-    const audioSegmentSizeSeconds =
-      getSetting("audio_segment_size_seconds") ?? 0;
+    const segmentOnSilenceMs = getSetting("segment_on_silence_ms") ?? 0;
 
     return (
       <Slider
-        value={audioSegmentSizeSeconds as number}
-        onChange={(seconds) =>
-          updateSetting("audio_segment_size_seconds", seconds)
-        }
+        value={segmentOnSilenceMs as number}
+        onChange={(ms) => updateSetting("segment_on_silence_ms", ms)}
         min={0}
-        max={10}
-        step={0.5}
-        disabled={isUpdating("audio_segment_size_seconds")}
+        max={3000}
+        step={250}
+        disabled={isUpdating("segment_on_silence_ms")}
         label={t("settings.general.segmentOnSilence.label")}
         description={t("settings.general.segmentOnSilence.description")}
         descriptionMode={descriptionMode}
         grouped={grouped}
         showValue={true}
-        formatValue={(value) => `${value.toFixed(1)}s`}
+        formatValue={(value) =>
+          value === 0 ? t("common.off") : `${(value / 1000).toFixed(2)}s`
+        }
       />
     );
   },
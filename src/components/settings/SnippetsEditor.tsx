@@ -0,0 +1,167 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type Result, type Snippet } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+interface SnippetsEditorProps {
+  grouped?: boolean;
+}
+
+export const SnippetsEditor: React.FC<SnippetsEditorProps> = React.memo(
+  ({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const snippets = getSetting("snippets") ?? [];
+
+    const [trigger, setTrigger] = useState("");
+    const [body, setBody] = useState("");
+    const [error, setError] = useState<string | null>(null);
+    const [saving, setSaving] = useState(false);
+
+    const run = async (action: () => Promise<Result<unknown, string>>) => {
+      setSaving(true);
+      try {
+        const result = await action();
+        if (result.status === "error") {
+          setError(result.error);
+          return false;
+        }
+        setError(null);
+        await refreshSettings();
+        return true;
+      } catch (e) {
+        console.error("Failed to update snippets:", e);
+        return false;
+      } finally {
+        setSaving(false);
+      }
+    };
+
+    const updateSnippet = (snippet: Snippet, patch: Partial<Snippet>) => {
+      const next = { ...snippet, ...patch };
+      return run(() =>
+        commands.updateSnippet(
+          next.id,
+          next.trigger,
+          next.body,
+          next.enabled ?? true,
+        ),
+      );
+    };
+
+    const addSnippet = async () => {
+      if (!trigger.trim() || !body.trim()) {
+        return;
+      }
+      if (await run(() => commands.addSnippet(trigger.trim(), body))) {
+        setTrigger("");
+        setBody("");
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.snippets.editor.title")}
+        description={t("settings.advanced.snippets.editor.description", {
+          date: "{{date}}",
+          time: "{{time}}",
+          clipboard: "{{clipboard}}",
+        })}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {snippets.map((snippet) => (
+            <div
+              key={snippet.id}
+              className="p-3 border border-mid-gray/20 rounded-lg space-y-2"
+            >
+              <div className="flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={snippet.enabled ?? true}
+                  onChange={(event) =>
+                    updateSnippet(snippet, { enabled: event.target.checked })
+                  }
+                  disabled={saving}
+                />
+                <Input
+                  defaultValue={snippet.trigger}
+                  onBlur={(event) => {
+                    const value = event.target.value.trim();
+                    if (value && value !== snippet.trigger) {
+                      updateSnippet(snippet, { trigger: value });
+                    }
+                  }}
+                  variant="compact"
+                  className="flex-1"
+                  disabled={saving}
+                />
+                <Button
+                  onClick={() => run(() => commands.deleteSnippet(snippet.id))}
+                  variant="secondary"
+                  size="sm"
+                  disabled={saving}
+                >
+                  {t("common.delete")}
+                </Button>
+              </div>
+              <Textarea
+                defaultValue={snippet.body}
+                onBlur={(event) => {
+                  if (
+                    event.target.value.trim() &&
+                    event.target.value !== snippet.body
+                  ) {
+                    updateSnippet(snippet, { body: event.target.value });
+                  }
+                }}
+                variant="compact"
+                className="w-full"
+                disabled={saving}
+              />
+            </div>
+          ))}
+
+          <div className="p-3 border border-dashed border-mid-gray/30 rounded-lg space-y-2">
+            <Input
+              value={trigger}
+              onChange={(event) => setTrigger(event.target.value)}
+              placeholder={t(
+                "settings.advanced.snippets.editor.triggerPlaceholder",
+              )}
+              variant="compact"
+              className="w-full"
+              disabled={saving}
+            />
+            <Textarea
+              value={body}
+              onChange={(event) => setBody(event.target.value)}
+              placeholder={t(
+                "settings.advanced.snippets.editor.bodyPlaceholder",
+              )}
+              variant="compact"
+              className="w-full"
+              disabled={saving}
+            />
+            <Button
+              onClick={addSnippet}
+              variant="primary"
+              size="sm"
+              disabled={saving || !trigger.trim() || !body.trim()}
+            >
+              {t("common.add")}
+            </Button>
+          </div>
+
+          {error && <p className="text-xs text-red-400">{error}</p>}
+        </div>
+      </SettingContainer>
+    );
+  },
+);
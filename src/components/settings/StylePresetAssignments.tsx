@@ -0,0 +1,149 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import type { StylePresetAppRule } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface StylePresetAssignmentsProps {
+  grouped?: boolean;
+}
+
+// Bindings whose dictations go through the style preset stage.
+const DICTATION_BINDINGS = ["transcribe", "transcribe_with_post_process"];
+const NO_PRESET = "";
+
+export const StylePresetAssignments: React.FC<StylePresetAssignmentsProps> =
+  React.memo(({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const presets = getSetting("style_presets") ?? [];
+    const bindings = getSetting("bindings") ?? {};
+    const assignments = getSetting("style_preset_bindings") ?? {};
+    const appRules = getSetting("style_preset_app_rules") ?? [];
+    const saving =
+      isUpdating("style_preset_bindings") ||
+      isUpdating("style_preset_app_rules");
+
+    const [app, setApp] = useState("");
+    const [presetId, setPresetId] = useState(NO_PRESET);
+
+    const presetOptions = [
+      {
+        value: NO_PRESET,
+        label: t("settings.advanced.style.assignments.none"),
+      },
+      ...presets.map((preset) => ({ value: preset.id, label: preset.name })),
+    ];
+
+    const assign = (bindingId: string, value: string) => {
+      const next: Record<string, string> = {};
+      for (const [id, preset] of Object.entries(assignments)) {
+        if (id !== bindingId && preset) {
+          next[id] = preset;
+        }
+      }
+      if (value !== NO_PRESET) {
+        next[bindingId] = value;
+      }
+      updateSetting("style_preset_bindings", next);
+    };
+
+    const saveRules = (next: StylePresetAppRule[]) =>
+      updateSetting("style_preset_app_rules", next);
+
+    const addRule = async () => {
+      if (!app.trim() || presetId === NO_PRESET) {
+        return;
+      }
+      await saveRules([...appRules, { app: app.trim(), preset_id: presetId }]);
+      setApp("");
+      setPresetId(NO_PRESET);
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.style.assignments.title")}
+        description={t("settings.advanced.style.assignments.description")}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {DICTATION_BINDINGS.filter((id) => bindings[id]).map((id) => (
+            <div key={id} className="flex items-center gap-2 text-sm">
+              <span className="flex-1">
+                {t(`settings.general.shortcut.bindings.${id}.name`)}
+              </span>
+              <Dropdown
+                options={presetOptions}
+                selectedValue={assignments[id] ?? NO_PRESET}
+                onSelect={(value) => assign(id, value)}
+                disabled={saving}
+              />
+            </div>
+          ))}
+
+          {appRules.map((rule, index) => (
+            <div
+              key={`${rule.app}-${index}`}
+              className="flex items-center gap-2 text-sm"
+            >
+              <span className="flex-1 font-mono">{rule.app}</span>
+              <Dropdown
+                options={presetOptions.slice(1)}
+                selectedValue={rule.preset_id}
+                onSelect={(value) =>
+                  saveRules(
+                    appRules.map((other, i) =>
+                      i === index ? { ...other, preset_id: value } : other,
+                    ),
+                  )
+                }
+                disabled={saving}
+              />
+              <Button
+                onClick={() =>
+                  saveRules(appRules.filter((_, i) => i !== index))
+                }
+                variant="secondary"
+                size="sm"
+                disabled={saving}
+              >
+                {t("common.delete")}
+              </Button>
+            </div>
+          ))}
+
+          <div className="flex items-center gap-2">
+            <Input
+              value={app}
+              onChange={(event) => setApp(event.target.value)}
+              placeholder={t(
+                "settings.advanced.style.assignments.appPlaceholder",
+              )}
+              variant="compact"
+              className="flex-1"
+              disabled={saving}
+            />
+            <Dropdown
+              options={presetOptions}
+              selectedValue={presetId}
+              onSelect={setPresetId}
+              disabled={saving}
+            />
+            <Button
+              onClick={addRule}
+              variant="primary"
+              size="sm"
+              disabled={saving || !app.trim() || presetId === NO_PRESET}
+            >
+              {t("common.add")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  });
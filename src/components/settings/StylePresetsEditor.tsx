@@ -0,0 +1,234 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import type {
+  CapitalizationPolicy,
+  NumberFormat,
+  PunctuationPolicy,
+  StylePreset,
+} from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+interface StylePresetsEditorProps {
+  grouped?: boolean;
+}
+
+const PUNCTUATION: PunctuationPolicy[] = [
+  "keep",
+  "no_trailing_period",
+  "strip",
+];
+const CAPITALIZATION: CapitalizationPolicy[] = [
+  "keep",
+  "sentence",
+  "lowercase",
+];
+const NUMBERS: NumberFormat[] = ["keep", "digits", "spell_small"];
+const MIN_SENTENCE_WORDS = 5;
+
+const presetIdFromName = (name: string, taken: string[]): string => {
+  const base =
+    name
+      .trim()
+      .toLowerCase()
+      .replace(/[^a-z0-9]+/g, "_")
+      .replace(/^_+|_+$/g, "") || "style";
+  let id = base;
+  let suffix = 2;
+  while (taken.includes(id)) {
+    id = `${base}_${suffix}`;
+    suffix += 1;
+  }
+  return id;
+};
+
+export const StylePresetsEditor: React.FC<StylePresetsEditorProps> =
+  React.memo(({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const presets = getSetting("style_presets") ?? [];
+    const saving = isUpdating("style_presets");
+
+    const save = (next: StylePreset[]) => updateSetting("style_presets", next);
+
+    const updatePreset = (id: string, patch: Partial<StylePreset>) =>
+      save(
+        presets.map((preset) =>
+          preset.id === id ? { ...preset, ...patch } : preset,
+        ),
+      );
+
+    const addPreset = () => {
+      const name = t("settings.advanced.style.presets.newName");
+      save([
+        ...presets,
+        {
+          id: presetIdFromName(name, presets.map((preset) => preset.id)),
+          name,
+        },
+      ]);
+    };
+
+    const options = (prefix: string, values: string[]) =>
+      values.map((value) => ({
+        value,
+        label: t(`settings.advanced.style.presets.${prefix}.${value}`),
+      }));
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.style.presets.title")}
+        description={t("settings.advanced.style.presets.description")}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {presets.map((preset) => (
+            <div
+              key={preset.id}
+              className="p-3 border border-mid-gray/20 rounded-lg space-y-2"
+            >
+              <div className="flex items-center gap-2">
+                <Input
+                  defaultValue={preset.name}
+                  onBlur={(event) => {
+                    const value = event.target.value.trim();
+                    if (value && value !== preset.name) {
+                      updatePreset(preset.id, { name: value });
+                    }
+                  }}
+                  variant="compact"
+                  className="flex-1"
+                  disabled={saving}
+                />
+                <Button
+                  onClick={() =>
+                    save(presets.filter(({ id }) => id !== preset.id))
+                  }
+                  variant="secondary"
+                  size="sm"
+                  disabled={saving}
+                >
+                  {t("common.delete")}
+                </Button>
+              </div>
+              <div className="grid grid-cols-3 gap-2 text-xs">
+                <div className="space-y-1">
+                  <span>
+                    {t("settings.advanced.style.presets.punctuation.label")}
+                  </span>
+                  <Dropdown
+                    options={options("punctuation", PUNCTUATION)}
+                    selectedValue={preset.punctuation ?? "keep"}
+                    onSelect={(value) =>
+                      updatePreset(preset.id, {
+                        punctuation: value as PunctuationPolicy,
+                      })
+                    }
+                    disabled={saving}
+                  />
+                </div>
+                <div className="space-y-1">
+                  <span>
+                    {t("settings.advanced.style.presets.capitalization.label")}
+                  </span>
+                  <Dropdown
+                    options={options("capitalization", CAPITALIZATION)}
+                    selectedValue={preset.capitalization ?? "keep"}
+                    onSelect={(value) =>
+                      updatePreset(preset.id, {
+                        capitalization: value as CapitalizationPolicy,
+                      })
+                    }
+                    disabled={saving}
+                  />
+                </div>
+                <div className="space-y-1">
+                  <span>
+                    {t("settings.advanced.style.presets.numbers.label")}
+                  </span>
+                  <Dropdown
+                    options={options("numbers", NUMBERS)}
+                    selectedValue={preset.numbers ?? "keep"}
+                    onSelect={(value) =>
+                      updatePreset(preset.id, {
+                        numbers: value as NumberFormat,
+                      })
+                    }
+                    disabled={saving}
+                  />
+                </div>
+              </div>
+              <div className="flex items-center gap-4 text-xs">
+                <label className="flex items-center gap-2">
+                  <input
+                    type="checkbox"
+                    checked={preset.remove_fillers ?? false}
+                    onChange={(event) =>
+                      updatePreset(preset.id, {
+                        remove_fillers: event.target.checked,
+                      })
+                    }
+                    disabled={saving}
+                  />
+                  {t("settings.advanced.style.presets.removeFillers")}
+                </label>
+                <label className="flex items-center gap-2">
+                  {t("settings.advanced.style.presets.maxSentenceWords")}
+                  <Input
+                    type="number"
+                    min={MIN_SENTENCE_WORDS}
+                    defaultValue={preset.max_sentence_words ?? ""}
+                    onBlur={(event) => {
+                      const value = parseInt(event.target.value, 10);
+                      const next = Number.isNaN(value)
+                        ? null
+                        : Math.max(value, MIN_SENTENCE_WORDS);
+                      if (next !== (preset.max_sentence_words ?? null)) {
+                        updatePreset(preset.id, { max_sentence_words: next });
+                      }
+                    }}
+                    variant="compact"
+                    className="w-20"
+                    disabled={saving}
+                  />
+                </label>
+              </div>
+              <Textarea
+                defaultValue={preset.rewrite_instructions ?? ""}
+                onBlur={(event) => {
+                  if (
+                    event.target.value !== (preset.rewrite_instructions ?? "")
+                  ) {
+                    updatePreset(preset.id, {
+                      rewrite_instructions: event.target.value,
+                    });
+                  }
+                }}
+                placeholder={t(
+                  "settings.advanced.style.presets.instructionsPlaceholder",
+                )}
+                variant="compact"
+                className="w-full"
+                disabled={saving}
+              />
+            </div>
+          ))}
+
+          <Button
+            onClick={addPreset}
+            variant="primary"
+            size="sm"
+            disabled={saving}
+          >
+            {t("common.add")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface TemplateLlmFillToggleProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const TemplateLlmFillToggle: React.FC<TemplateLlmFillToggleProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("template_llm_fill") || false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) => updateSetting("template_llm_fill", enabled)}
+        isUpdating={isUpdating("template_llm_fill")}
+        label={t("settings.advanced.templates.llmFill.label")}
+        description={t("settings.advanced.templates.llmFill.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
@@ -0,0 +1,167 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import type { TextTemplate } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Textarea } from "../ui/Textarea";
+
+interface TemplatesEditorProps {
+  grouped?: boolean;
+}
+
+const templateIdFromName = (name: string, taken: string[]): string => {
+  const base =
+    name
+      .trim()
+      .toLowerCase()
+      .replace(/[^a-z0-9]+/g, "_")
+      .replace(/^_+|_+$/g, "") || "template";
+  let id = base;
+  let suffix = 2;
+  while (taken.includes(id)) {
+    id = `${base}_${suffix}`;
+    suffix += 1;
+  }
+  return id;
+};
+
+export const TemplatesEditor: React.FC<TemplatesEditorProps> = React.memo(
+  ({ grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const templates = getSetting("templates") ?? [];
+    const saving = isUpdating("templates");
+
+    const [name, setName] = useState("");
+    const [body, setBody] = useState("");
+
+    const save = (next: TextTemplate[]) => updateSetting("templates", next);
+
+    const updateTemplate = (id: string, patch: Partial<TextTemplate>) =>
+      save(
+        templates.map((template) =>
+          template.id === id ? { ...template, ...patch } : template,
+        ),
+      );
+
+    const addTemplate = async () => {
+      if (!name.trim() || !body.trim()) {
+        return;
+      }
+      const id = templateIdFromName(
+        name,
+        templates.map((template) => template.id),
+      );
+      await save([
+        ...templates,
+        { id, name: name.trim(), body, enabled: true },
+      ]);
+      setName("");
+      setBody("");
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.templates.editor.title")}
+        description={t("settings.advanced.templates.editor.description", {
+          example: "{{name}}",
+        })}
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {templates.map((template) => (
+            <div
+              key={template.id}
+              className="p-3 border border-mid-gray/20 rounded-lg space-y-2"
+            >
+              <div className="flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={template.enabled ?? true}
+                  onChange={(event) =>
+                    updateTemplate(template.id, {
+                      enabled: event.target.checked,
+                    })
+                  }
+                  disabled={saving}
+                />
+                <Input
+                  defaultValue={template.name}
+                  onBlur={(event) => {
+                    const value = event.target.value.trim();
+                    if (value && value !== template.name) {
+                      updateTemplate(template.id, { name: value });
+                    }
+                  }}
+                  variant="compact"
+                  className="flex-1"
+                  disabled={saving}
+                />
+                <Button
+                  onClick={() =>
+                    save(templates.filter(({ id }) => id !== template.id))
+                  }
+                  variant="secondary"
+                  size="sm"
+                  disabled={saving}
+                >
+                  {t("common.delete")}
+                </Button>
+              </div>
+              <Textarea
+                defaultValue={template.body}
+                onBlur={(event) => {
+                  if (
+                    event.target.value.trim() &&
+                    event.target.value !== template.body
+                  ) {
+                    updateTemplate(template.id, { body: event.target.value });
+                  }
+                }}
+                variant="compact"
+                className="w-full font-mono"
+                disabled={saving}
+              />
+            </div>
+          ))}
+
+          <div className="p-3 border border-dashed border-mid-gray/30 rounded-lg space-y-2">
+            <Input
+              value={name}
+              onChange={(event) => setName(event.target.value)}
+              placeholder={t(
+                "settings.advanced.templates.editor.namePlaceholder",
+              )}
+              variant="compact"
+              className="w-full"
+              disabled={saving}
+            />
+            <Textarea
+              value={body}
+              onChange={(event) => setBody(event.target.value)}
+              placeholder={t(
+                "settings.advanced.templates.editor.bodyPlaceholder",
+                { example: "{{title}}" },
+              )}
+              variant="compact"
+              className="w-full font-mono"
+              disabled={saving}
+            />
+            <Button
+              onClick={addTemplate}
+              variant="primary"
+              size="sm"
+              disabled={saving || !name.trim() || !body.trim()}
+            >
+              {t("common.add")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
@@ -17,6 +17,22 @@ import { HistoryLimit } from "../HistoryLimit";
 import { RecordingRetentionPeriodSelector } from "../RecordingRetentionPeriod";
 import { ExperimentalToggle } from "../ExperimentalToggle";
 import { AtFileExpansionToggle } from "../AtFileExpansionToggle";
+import { VoiceCommandsToggle } from "../VoiceCommandsToggle";
+import { TemplateModeToggle } from "../TemplateModeToggle";
+import { TemplateLlmFillToggle } from "../TemplateLlmFillToggle";
+import { TemplatesEditor } from "../TemplatesEditor";
+import { StylePresetsEditor } from "../StylePresetsEditor";
+import { StylePresetAssignments } from "../StylePresetAssignments";
+import { SnippetsToggle } from "../SnippetsToggle";
+import { SnippetsEditor } from "../SnippetsEditor";
+import { CliModeToggle } from "../CliModeToggle";
+import { CliLlmAssistToggle } from "../CliLlmAssistToggle";
+import { CliDenyPatterns } from "../CliDenyPatterns";
+import { IntentRouterToggle } from "../IntentRouterToggle";
+import { IntentRouterModelDir } from "../IntentRouterModelDir";
+import { IntentRouterConfidence } from "../IntentRouterConfidence";
+import { ConfigPackSettings } from "../ConfigPackSettings";
+import { ShortcutInput } from "../ShortcutInput";
 import { useSettings } from "../../../hooks/useSettings";
 import { KeyboardImplementationSelector } from "../debug/KeyboardImplementationSelector";
 
@@ -24,6 +40,10 @@ export const AdvancedSettings: React.FC = () => {
   const { t } = useTranslation();
   const { getSetting } = useSettings();
   const experimentalEnabled = getSetting("experimental_enabled") || false;
+  const templatesEnabled = getSetting("templates_enabled") || false;
+  const snippetsEnabled = getSetting("snippets_enabled") || false;
+  const cliModeEnabled = getSetting("cli_mode_enabled") || false;
+  const intentRouterEnabled = getSetting("intent_router_enabled") || false;
 
   return (
     <div className="max-w-3xl w-full mx-auto space-y-6">
@@ -46,6 +66,56 @@ export const AdvancedSettings: React.FC = () => {
       <SettingsGroup title={t("settings.advanced.groups.transcription")}>
         <CustomWords descriptionMode="tooltip" grouped />
         <AppendTrailingSpace descriptionMode="tooltip" grouped={true} />
+        <VoiceCommandsToggle descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.advanced.groups.style")}>
+        <StylePresetsEditor grouped={true} />
+        <StylePresetAssignments grouped={true} />
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.advanced.groups.snippets")}>
+        <SnippetsToggle descriptionMode="tooltip" grouped={true} />
+        {snippetsEnabled && <SnippetsEditor grouped={true} />}
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.advanced.groups.templates")}>
+        <TemplateModeToggle descriptionMode="tooltip" grouped={true} />
+        {templatesEnabled && (
+          <>
+            <TemplateLlmFillToggle descriptionMode="tooltip" grouped={true} />
+            <ShortcutInput
+              shortcutId="template_next_placeholder"
+              grouped={true}
+            />
+            <TemplatesEditor grouped={true} />
+          </>
+        )}
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.advanced.groups.cliMode")}>
+        <CliModeToggle descriptionMode="tooltip" grouped={true} />
+        {cliModeEnabled && (
+          <>
+            <ShortcutInput shortcutId="transcribe_cli" grouped={true} />
+            <CliLlmAssistToggle descriptionMode="tooltip" grouped={true} />
+            <CliDenyPatterns grouped={true} />
+          </>
+        )}
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.advanced.groups.intentRouter")}>
+        <IntentRouterToggle descriptionMode="tooltip" grouped={true} />
+        {intentRouterEnabled && (
+          <>
+            <IntentRouterModelDir grouped={true} />
+            <IntentRouterConfidence descriptionMode="tooltip" grouped={true} />
+          </>
+        )}
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.advanced.groups.configPacks")}>
+        <ConfigPackSettings grouped={true} />
       </SettingsGroup>
 
       <SettingsGroup title={t("settings.advanced.groups.history")}>
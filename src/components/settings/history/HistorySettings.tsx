@@ -2,11 +2,12 @@ import React, { useState, useEffect, useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import { AudioPlayer } from "../../ui/AudioPlayer";
 import { Button } from "../../ui/Button";
-import { Copy, Star, Check, Trash2, FolderOpen } from "lucide-react";
+import { Textarea } from "../../ui/Textarea";
+import { Copy, Star, Check, Trash2, FolderOpen, Pencil } from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { readFile } from "@tauri-apps/plugin-fs";
-import { commands, type HistoryEntry } from "@/bindings";
+import { commands, type HistoryEntry, type JargonProposal } from "@/bindings";
 import { formatDateTime } from "@/utils/dateFormat";
 import { useOsType } from "@/hooks/useOsType";
 
@@ -203,7 +204,11 @@ export const HistorySettings: React.FC = () => {
                 key={entry.id}
                 entry={entry}
                 onToggleSaved={() => toggleSaved(entry.id)}
-                onCopyText={() => copyToClipboard(entry.transcription_text)}
+                onCopyText={() =>
+                  copyToClipboard(
+                    entry.edited_text ?? entry.transcription_text,
+                  )
+                }
                 getAudioUrl={getAudioUrl}
                 deleteAudio={deleteAudioEntry}
               />
@@ -232,6 +237,11 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
 }) => {
   const { t, i18n } = useTranslation();
   const [showCopied, setShowCopied] = useState(false);
+  const [editing, setEditing] = useState(false);
+  const [draft, setDraft] = useState("");
+  const [proposals, setProposals] = useState<JargonProposal[]>([]);
+
+  const text = entry.edited_text ?? entry.transcription_text;
 
   const handleLoadAudio = useCallback(
     () => getAudioUrl(entry.file_name),
@@ -253,6 +263,35 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
     }
   };
 
+  const startEditing = () => {
+    setDraft(text);
+    setEditing(true);
+  };
+
+  const saveEdit = async () => {
+    const result = await commands.updateHistoryEntryText(entry.id, draft);
+    if (result.status === "ok") {
+      setProposals(result.data);
+      setEditing(false);
+    } else {
+      console.error("Failed to save edit:", result.error);
+    }
+  };
+
+  const resolveProposal = async (
+    proposal: JargonProposal,
+    accept: boolean,
+  ) => {
+    const result = accept
+      ? await commands.acceptJargonProposals([proposal])
+      : await commands.rejectJargonProposals([proposal]);
+    if (result.status === "ok") {
+      setProposals((current) => current.filter((p) => p !== proposal));
+    } else {
+      console.error("Failed to resolve jargon proposal:", result.error);
+    }
+  };
+
   const formattedDate = formatDateTime(String(entry.timestamp), i18n.language);
 
   return (
@@ -271,6 +310,13 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
               <Copy width={16} height={16} />
             )}
           </button>
+          <button
+            onClick={startEditing}
+            className="p-2 text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
+            title={t("settings.history.edit")}
+          >
+            <Pencil width={16} height={16} />
+          </button>
           <button
             onClick={onToggleSaved}
             className={`p-2 rounded-md transition-colors cursor-pointer ${
@@ -299,9 +345,71 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
           </button>
         </div>
       </div>
-      <p className="italic text-text/90 text-sm pb-2 select-text cursor-text">
-        {entry.transcription_text}
-      </p>
+      {editing ? (
+        <div className="flex flex-col gap-2">
+          <Textarea
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            variant="compact"
+          />
+          <div className="flex justify-end gap-2">
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={() => setEditing(false)}
+            >
+              {t("settings.history.cancelEdit")}
+            </Button>
+            <Button variant="primary" size="sm" onClick={saveEdit}>
+              {t("settings.history.saveEdit")}
+            </Button>
+          </div>
+        </div>
+      ) : (
+        <p className="italic text-text/90 text-sm pb-2 select-text cursor-text">
+          {text}
+        </p>
+      )}
+      {proposals.length > 0 && (
+        <div className="flex flex-col gap-2 rounded-md border border-mid-gray/20 px-3 py-2">
+          <p className="text-xs font-medium text-mid-gray uppercase tracking-wide">
+            {t("settings.history.proposals.title")}
+          </p>
+          {proposals.map((proposal) => (
+            <div
+              key={`${proposal.kind}:${proposal.from}:${proposal.to}`}
+              className="flex items-center justify-between gap-2 text-sm"
+            >
+              <span>
+                {proposal.kind === "correction"
+                  ? t("settings.history.proposals.correction", {
+                      from: proposal.from,
+                      to: proposal.to,
+                    })
+                  : t("settings.history.proposals.customWord", {
+                      word: proposal.to,
+                    })}
+              </span>
+              <div className="flex gap-2">
+                <Button
+                  variant="secondary"
+                  size="sm"
+                  onClick={() => resolveProposal(proposal, false)}
+                >
+                  {t("settings.history.proposals.reject")}
+                </Button>
+                <Button
+                  variant="primary-soft"
+                  size="sm"
+                  onClick={() => resolveProposal(proposal, true)}
+                >
+                  {t("settings.history.proposals.accept")}
+                </Button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
       <AudioPlayer onLoadRequest={handleLoadAudio} className="w-full" />
     </div>
   );
@@ -2,13 +2,13 @@ import React, { useEffect, useMemo, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { readTextFile, writeTextFile } from "@tauri-apps/plugin-fs";
-import { commands } from "@/bindings";
+import { commands, type JargonCorrection } from "@/bindings";
 import { Button } from "../../ui/Button";
 import { Input } from "../../ui/Input";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { SettingContainer } from "../../ui/SettingContainer";
+import { JargonSourcesSettings } from "./JargonSourcesSettings";
 
-type JargonCorrection = { from: string; to: string };
 type LocalJargonPack = {
   id: string;
   label: string;
@@ -308,6 +308,8 @@ export const JargonPacksSettings: React.FC = () => {
         </div>
       </SettingsGroup>
 
+      <JargonSourcesSettings />
+
       <SettingsGroup title={t("settings.jargonPacks.summary.title")}>
         <div className="px-4 py-2 text-sm text-mid-gray">
           {t("settings.jargonPacks.summary.text", {
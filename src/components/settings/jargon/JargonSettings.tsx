@@ -5,6 +5,8 @@ import { SettingsGroup } from "../../ui/SettingsGroup";
 import { SettingContainer } from "../../ui/SettingContainer";
 import { Input } from "../../ui/Input";
 import { Button } from "../../ui/Button";
+import { Slider } from "../../ui/Slider";
+import { ToggleSwitch } from "../../ui/ToggleSwitch";
 import { commands } from "@/bindings";
 
 interface JargonProfile {
@@ -31,7 +33,8 @@ const SELECTOR_PRESETS: Record<
 
 export const JargonSettings: React.FC = () => {
   const { t } = useTranslation();
-  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const { getSetting, updateSetting, isUpdating, refreshSettings } =
+    useSettings();
 
   const enabledProfiles =
     (getSetting("jargon_enabled_profiles" as any) as string[]) || [];
@@ -52,7 +55,15 @@ export const JargonSettings: React.FC = () => {
   const domainSelectorBlendManual =
     (getSetting("domain_selector_blend_manual_profiles" as any) as boolean) ??
     true;
+  const domainSelectorLearnedWeights =
+    getSetting("domain_selector_learned_weights") ?? false;
+  const profileFeedback = getSetting("domain_selector_profile_feedback") ?? {};
+  const hasProfileFeedback = Object.keys(profileFeedback).length > 0;
   const debugMode = (getSetting("debug_mode" as any) as boolean) || false;
+  const phoneticEnabled = getSetting("phonetic_matching_enabled") ?? false;
+  const phoneticThreshold = getSetting("phonetic_match_threshold") ?? 0.8;
+  const workspaceJargonEnabled =
+    getSetting("workspace_jargon_enabled") ?? false;
 
   const [builtinProfiles, setBuiltinProfiles] = useState<
     Record<string, JargonProfile>
@@ -232,6 +243,33 @@ export const JargonSettings: React.FC = () => {
                 ? t("settings.jargon.selector.blendManualOn")
                 : t("settings.jargon.selector.blendManualOff")}
             </Button>
+            <Button
+              onClick={() =>
+                updateSetting(
+                  "domain_selector_learned_weights",
+                  !domainSelectorLearnedWeights,
+                )
+              }
+              variant={domainSelectorLearnedWeights ? "primary" : "secondary"}
+              size="sm"
+              disabled={isUpdating("domain_selector_learned_weights")}
+            >
+              {domainSelectorLearnedWeights
+                ? t("settings.jargon.selector.learnedWeightsOn")
+                : t("settings.jargon.selector.learnedWeightsOff")}
+            </Button>
+            {hasProfileFeedback && (
+              <Button
+                onClick={async () => {
+                  await commands.resetDomainSelectorFeedback();
+                  await refreshSettings();
+                }}
+                variant="secondary"
+                size="sm"
+              >
+                {t("settings.jargon.selector.resetLearnedWeights")}
+              </Button>
+            )}
           </div>
           <div className="mt-3">
             <p className="text-xs text-mid-gray mb-2">
@@ -483,6 +521,47 @@ export const JargonSettings: React.FC = () => {
         )}
       </SettingsGroup>
 
+      <SettingsGroup title={t("settings.jargon.phonetic.title")}>
+        <ToggleSwitch
+          checked={phoneticEnabled}
+          onChange={(enabled) =>
+            updateSetting("phonetic_matching_enabled", enabled)
+          }
+          isUpdating={isUpdating("phonetic_matching_enabled")}
+          label={t("settings.jargon.phonetic.enabled.label")}
+          description={t("settings.jargon.phonetic.enabled.description")}
+          descriptionMode="inline"
+          grouped
+        />
+        <Slider
+          value={phoneticThreshold}
+          onChange={(value) =>
+            updateSetting("phonetic_match_threshold", value)
+          }
+          min={0.5}
+          max={1.0}
+          disabled={!phoneticEnabled}
+          label={t("settings.jargon.phonetic.threshold.label")}
+          description={t("settings.jargon.phonetic.threshold.description")}
+          descriptionMode="inline"
+          grouped
+        />
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.jargon.workspace.title")}>
+        <ToggleSwitch
+          checked={workspaceJargonEnabled}
+          onChange={(enabled) =>
+            updateSetting("workspace_jargon_enabled", enabled)
+          }
+          isUpdating={isUpdating("workspace_jargon_enabled")}
+          label={t("settings.jargon.workspace.enabled.label")}
+          description={t("settings.jargon.workspace.enabled.description")}
+          descriptionMode="inline"
+          grouped
+        />
+      </SettingsGroup>
+
       {(totalTerms > 0 || totalCorrections > 0) && (
         <SettingsGroup title={t("settings.jargon.summary.title")}>
           <div className="px-4 py-2 text-sm text-mid-gray">
@@ -0,0 +1,259 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { open } from "@tauri-apps/plugin-dialog";
+import {
+  commands,
+  type JargonSource,
+  type JargonSourceStatus,
+} from "@/bindings";
+import { useSettings } from "../../../hooks/useSettings";
+import { Button } from "../../ui/Button";
+import { Dropdown } from "../../ui/Dropdown";
+import { SettingsGroup } from "../../ui/SettingsGroup";
+import { SettingContainer } from "../../ui/SettingContainer";
+
+// Bindings a source can be limited to.
+const DICTATION_BINDINGS = ["transcribe", "transcribe_with_post_process"];
+const ANY = "";
+
+const sourceIdFromPath = (path: string, taken: string[]): string => {
+  const name = path.split(/[\\/]/).filter(Boolean).pop() ?? "";
+  const base =
+    name
+      .toLowerCase()
+      .replace(/\.[a-z0-9]+$/, "")
+      .replace(/[^a-z0-9]+/g, "_")
+      .replace(/^_+|_+$/g, "") || "source";
+  let id = base;
+  let suffix = 2;
+  while (taken.includes(id)) {
+    id = `${base}_${suffix}`;
+    suffix += 1;
+  }
+  return id;
+};
+
+export const JargonSourcesSettings: React.FC = () => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const sources = getSetting("jargon_sources") ?? [];
+  const bindings = getSetting("bindings") ?? {};
+  const prompts = getSetting("post_process_prompts") ?? [];
+
+  const [statuses, setStatuses] = useState<JargonSourceStatus[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    const load = () =>
+      commands
+        .getJargonSourceStatuses()
+        .then(setStatuses)
+        .catch((e) => console.error("Failed to load jargon sources:", e));
+    load();
+    const unlisten = listen("jargon-sources-changed", load);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const save = async (next: JargonSource[]) => {
+    setSaving(true);
+    try {
+      const result = await commands.updateJargonSources(next);
+      if (result.status === "error") {
+        setError(result.error);
+        return;
+      }
+      setError(null);
+      setStatuses(result.data);
+      await refreshSettings();
+    } catch (e) {
+      console.error("Failed to update jargon sources:", e);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const updateSource = (id: string, patch: Partial<JargonSource>) =>
+    save(
+      sources.map((source) =>
+        source.id === id ? { ...source, ...patch } : source,
+      ),
+    );
+
+  const move = (index: number, offset: number) => {
+    const next = [...sources];
+    const [source] = next.splice(index, 1);
+    next.splice(index + offset, 0, source);
+    save(next);
+  };
+
+  const addSource = async (directory: boolean) => {
+    try {
+      const selected = await open({
+        title: t("settings.jargonPacks.sources.dialogTitle"),
+        directory,
+        multiple: false,
+      });
+      if (!selected || Array.isArray(selected)) {
+        return;
+      }
+      const id = sourceIdFromPath(selected, sources.map((source) => source.id));
+      const label = selected.split(/[\\/]/).filter(Boolean).pop() ?? id;
+      await save([
+        ...sources,
+        {
+          id,
+          label,
+          path: selected,
+          enabled: true,
+          binding_ids: [],
+          prompt_ids: [],
+        },
+      ]);
+    } catch (e) {
+      console.error("Failed to add jargon source:", e);
+    }
+  };
+
+  const bindingOptions = [
+    { value: ANY, label: t("settings.jargonPacks.sources.anyBinding") },
+    ...DICTATION_BINDINGS.filter((id) => bindings[id]).map((id) => ({
+      value: id,
+      label: t(`settings.general.shortcut.bindings.${id}.name`),
+    })),
+  ];
+  const promptOptions = [
+    { value: ANY, label: t("settings.jargonPacks.sources.anyPrompt") },
+    ...prompts.map((prompt) => ({ value: prompt.id, label: prompt.name })),
+  ];
+
+  return (
+    <SettingsGroup title={t("settings.jargonPacks.sources.title")}>
+      <SettingContainer
+        title={t("settings.jargonPacks.sources.title")}
+        description={t("settings.jargonPacks.sources.description")}
+        descriptionMode="inline"
+        grouped
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          {sources.length === 0 && (
+            <p className="text-sm text-mid-gray">
+              {t("settings.jargonPacks.sources.empty")}
+            </p>
+          )}
+          {sources.map((source, index) => {
+            const status = statuses.find((s) => s.id === source.id);
+            return (
+              <div
+                key={source.id}
+                className="p-3 border border-mid-gray/20 rounded-lg space-y-2"
+              >
+                <div className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={source.enabled ?? true}
+                    onChange={(event) =>
+                      updateSource(source.id, {
+                        enabled: event.target.checked,
+                      })
+                    }
+                    disabled={saving}
+                  />
+                  <span className="flex-1 min-w-0">
+                    <span className="font-medium">{source.label}</span>
+                    <span className="block text-xs font-mono text-mid-gray truncate">
+                      {source.path}
+                    </span>
+                  </span>
+                  <Button
+                    onClick={() => move(index, -1)}
+                    variant="secondary"
+                    size="sm"
+                    disabled={saving || index === 0}
+                  >
+                    {t("settings.jargonPacks.sources.up")}
+                  </Button>
+                  <Button
+                    onClick={() => move(index, 1)}
+                    variant="secondary"
+                    size="sm"
+                    disabled={saving || index === sources.length - 1}
+                  >
+                    {t("settings.jargonPacks.sources.down")}
+                  </Button>
+                  <Button
+                    onClick={() =>
+                      save(sources.filter((other) => other.id !== source.id))
+                    }
+                    variant="secondary"
+                    size="sm"
+                    disabled={saving}
+                  >
+                    {t("common.delete")}
+                  </Button>
+                </div>
+                <div className="flex items-center gap-2">
+                  <Dropdown
+                    options={bindingOptions}
+                    selectedValue={source.binding_ids?.[0] ?? ANY}
+                    onSelect={(value) =>
+                      updateSource(source.id, {
+                        binding_ids: value === ANY ? [] : [value],
+                      })
+                    }
+                    disabled={saving}
+                  />
+                  <Dropdown
+                    options={promptOptions}
+                    selectedValue={source.prompt_ids?.[0] ?? ANY}
+                    onSelect={(value) =>
+                      updateSource(source.id, {
+                        prompt_ids: value === ANY ? [] : [value],
+                      })
+                    }
+                    disabled={saving}
+                  />
+                </div>
+                {status && (
+                  <p className="text-xs text-mid-gray">
+                    {t("settings.jargonPacks.list.summary", {
+                      terms: status.terms,
+                      corrections: status.corrections,
+                    })}
+                  </p>
+                )}
+                {status?.error && (
+                  <p className="text-xs text-red-400">{status.error}</p>
+                )}
+              </div>
+            );
+          })}
+
+          <div className="flex flex-wrap gap-2">
+            <Button
+              onClick={() => addSource(false)}
+              variant="secondary"
+              size="sm"
+              disabled={saving}
+            >
+              {t("settings.jargonPacks.sources.addFile")}
+            </Button>
+            <Button
+              onClick={() => addSource(true)}
+              variant="secondary"
+              size="sm"
+              disabled={saving}
+            >
+              {t("settings.jargonPacks.sources.addFolder")}
+            </Button>
+          </div>
+          {error && <p className="text-xs text-red-400">{error}</p>}
+        </div>
+      </SettingContainer>
+    </SettingsGroup>
+  );
+};
@@ -13,13 +13,24 @@ import {
 import { Button } from "../../ui/Button";
 import { ResetButton } from "../../ui/ResetButton";
 import { Input } from "../../ui/Input";
+import { ToggleSwitch } from "../../ui/ToggleSwitch";
 
 import { ProviderSelect } from "../PostProcessingSettingsApi/ProviderSelect";
 import { BaseUrlField } from "../PostProcessingSettingsApi/BaseUrlField";
 import { ApiKeyField } from "../PostProcessingSettingsApi/ApiKeyField";
+import { LocalModelDirField } from "../PostProcessingSettingsApi/LocalModelDirField";
 import { ModelSelect } from "../PostProcessingSettingsApi/ModelSelect";
 import { usePostProcessProviderState } from "../PostProcessingSettingsApi/usePostProcessProviderState";
 import { ShortcutInput } from "../ShortcutInput";
+import { PostProcessStreaming } from "../PostProcessStreaming";
+import { PostProcessTimeout } from "../PostProcessTimeout";
+import { PostProcessFallbacks } from "../PostProcessFallbacks";
+import { PostProcessDivergenceGuard } from "../PostProcessDivergenceGuard";
+import { RerankerToggle } from "../RerankerToggle";
+import { RerankerModelDir } from "../RerankerModelDir";
+import { RerankerSecondPrompt } from "../RerankerSecondPrompt";
+import { PromptRoutingEditor } from "../PromptRoutingEditor";
+import { PromptRouteExplainer } from "../PromptRouteExplainer";
 import { useSettings } from "../../../hooks/useSettings";
 
 const PostProcessingSettingsApiComponent: React.FC = () => {
@@ -50,6 +61,35 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
             {t("settings.postProcessing.api.appleIntelligence.unavailable")}
           </Alert>
         ) : null
+      ) : state.isLocalCleanupProvider ? (
+        <>
+          <SettingContainer
+            title={t("settings.postProcessing.api.localCleanup.modelDir.title")}
+            description={t(
+              "settings.postProcessing.api.localCleanup.modelDir.description",
+            )}
+            descriptionMode="tooltip"
+            layout="horizontal"
+            grouped={true}
+          >
+            <div className="flex items-center gap-2">
+              <LocalModelDirField
+                value={state.localModelDir}
+                onBlur={state.handleLocalModelDirChange}
+                placeholder={t(
+                  "settings.postProcessing.api.localCleanup.modelDir.placeholder",
+                )}
+                disabled={state.isLocalModelDirUpdating}
+                className="min-w-[380px]"
+              />
+            </div>
+          </SettingContainer>
+          {state.localModelDirError && (
+            <Alert variant="error" contained>
+              {state.localModelDirError}
+            </Alert>
+          )}
+        </>
       ) : (
         <>
           {state.selectedProvider?.id === "custom" && (
@@ -96,7 +136,7 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
         </>
       )}
 
-      {!state.isAppleProvider && (
+      {!state.isAppleProvider && !state.isLocalCleanupProvider && (
         <SettingContainer
           title={t("settings.postProcessing.api.model.title")}
           description={
@@ -214,6 +254,20 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
     }
   };
 
+  const handleToggleRestructures = async (restructures: boolean) => {
+    if (!selectedPromptId) return;
+
+    try {
+      await commands.changePostProcessPromptRestructures(
+        selectedPromptId,
+        restructures,
+      );
+      await refreshSettings();
+    } catch (error) {
+      console.error("Failed to update prompt:", error);
+    }
+  };
+
   const handleDeletePrompt = async (promptId: string) => {
     if (!promptId) return;
 
@@ -359,6 +413,21 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
                 {t("settings.postProcessing.prompts.deletePrompt")}
               </Button>
             </div>
+
+            <ToggleSwitch
+              checked={selectedPrompt.restructures ?? false}
+              onChange={handleToggleRestructures}
+              label={t("settings.postProcessing.prompts.restructures.label")}
+              description={t(
+                "settings.postProcessing.prompts.restructures.description",
+              )}
+              descriptionMode="inline"
+              grouped
+            />
+
+            {autoPromptSelection && (
+              <PromptRoutingEditor prompt={selectedPrompt} />
+            )}
           </div>
         )}
 
@@ -444,6 +513,10 @@ PostProcessingSettingsPrompts.displayName = "PostProcessingSettingsPrompts";
 
 export const PostProcessingSettings: React.FC = () => {
   const { t } = useTranslation();
+  const { getSetting } = useSettings();
+  const rerankerEnabled = getSetting("reranker_enabled") || false;
+  const autoPromptSelection =
+    getSetting("post_process_auto_prompt_selection") || false;
 
   return (
     <div className="max-w-3xl w-full mx-auto space-y-6">
@@ -461,6 +534,24 @@ export const PostProcessingSettings: React.FC = () => {
 
       <SettingsGroup title={t("settings.postProcessing.prompts.title")}>
         <PostProcessingSettingsPrompts />
+        {autoPromptSelection && <PromptRouteExplainer grouped={true} />}
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.postProcessing.delivery.title")}>
+        <PostProcessStreaming descriptionMode="tooltip" grouped={true} />
+        <PostProcessTimeout descriptionMode="tooltip" grouped={true} />
+        <PostProcessFallbacks grouped={true} />
+        <PostProcessDivergenceGuard descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.postProcessing.reranker.title")}>
+        <RerankerToggle descriptionMode="tooltip" grouped={true} />
+        {rerankerEnabled && (
+          <>
+            <RerankerSecondPrompt descriptionMode="tooltip" grouped={true} />
+            <RerankerModelDir grouped={true} />
+          </>
+        )}
       </SettingsGroup>
     </div>
   );
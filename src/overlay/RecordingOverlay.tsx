@@ -18,6 +18,7 @@ const RecordingOverlay: React.FC = () => {
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
+  const [partialText, setPartialText] = useState("");
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
   const direction = getLanguageDirection(i18n.language);
 
@@ -29,6 +30,7 @@ const RecordingOverlay: React.FC = () => {
         await syncLanguageFromSettings();
         const overlayState = event.payload as OverlayState;
         setState(overlayState);
+        setPartialText("");
         setIsVisible(true);
       });
 
@@ -51,11 +53,20 @@ const RecordingOverlay: React.FC = () => {
         setLevels(smoothed.slice(0, 9));
       });
 
+      // Listen for streamed post-processing output
+      const unlistenPartial = await listen<string>(
+        "post-process-partial",
+        (event) => {
+          setPartialText(event.payload);
+        },
+      );
+
       // Cleanup function
       return () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
+        unlistenPartial();
       };
     };
 
@@ -96,9 +107,12 @@ const RecordingOverlay: React.FC = () => {
         {state === "transcribing" && (
           <div className="transcribing-text">{t("overlay.transcribing")}</div>
         )}
-        {state === "processing" && (
+        {state === "processing" && !partialText && (
           <div className="transcribing-text">{t("overlay.processing")}</div>
         )}
+        {state === "processing" && partialText && (
+          <div className="partial-text">{partialText.slice(-24)}</div>
+        )}
       </div>
 
       <div className="overlay-right">
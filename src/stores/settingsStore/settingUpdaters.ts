@@ -1,5 +1,13 @@
 import { commands } from "@/bindings";
-import type { AppSettings as Settings } from "@/bindings";
+import type {
+  AppSettings as Settings,
+  JargonSource,
+  PostProcessFallback,
+  StylePreset,
+  StylePresetAppRule,
+  TextTemplate,
+  VoiceCommandConfig,
+} from "@/bindings";
 
 export const settingUpdaters: {
   [K in keyof Settings]?: (value: Settings[K]) => Promise<unknown>;
@@ -45,6 +53,10 @@ export const settingUpdaters: {
   custom_words: (value) => commands.updateCustomWords(value as string[]),
   word_correction_threshold: (value) =>
     commands.changeWordCorrectionThresholdSetting(value as number),
+  phonetic_matching_enabled: (value) =>
+    commands.changePhoneticMatchingSetting(value as boolean),
+  phonetic_match_threshold: (value) =>
+    commands.changePhoneticMatchThresholdSetting(value as number),
   paste_method: (value) => commands.changePasteMethodSetting(value as string),
   typing_tool: (value) => commands.changeTypingToolSetting(value as string),
   clipboard_handling: (value) =>
@@ -57,12 +69,43 @@ export const settingUpdaters: {
     commands.changePostProcessEnabledSetting(value as boolean),
   post_process_auto_prompt_selection: (value) =>
     commands.changePostProcessAutoPromptSelectionSetting(value as boolean),
+  post_process_streaming: (value) =>
+    commands.changePostProcessStreamingSetting(value as boolean),
+  post_process_fallbacks: (value) =>
+    commands.updatePostProcessFallbacks(value as PostProcessFallback[]),
+  post_process_timeout_ms: (value) =>
+    commands.changePostProcessTimeoutMsSetting(value as number),
+  post_process_divergence_guard: (value) =>
+    commands.changePostProcessDivergenceGuardSetting(value as boolean),
+  voice_commands_enabled: (value) =>
+    commands.changeVoiceCommandsEnabledSetting(value as boolean),
+  voice_commands: (value) =>
+    commands.updateVoiceCommands(value as VoiceCommandConfig[]),
+  templates_enabled: (value) =>
+    commands.changeTemplatesEnabledSetting(value as boolean),
+  template_llm_fill: (value) =>
+    commands.changeTemplateLlmFillSetting(value as boolean),
+  templates: (value) => commands.updateTemplates(value as TextTemplate[]),
+  snippets_enabled: (value) =>
+    commands.changeSnippetsEnabledSetting(value as boolean),
+  style_presets: (value) =>
+    commands.updateStylePresets(value as StylePreset[]),
+  style_preset_bindings: (value) =>
+    commands.updateStylePresetBindings(value as Record<string, string>),
+  style_preset_app_rules: (value) =>
+    commands.updateStylePresetAppRules(value as StylePresetAppRule[]),
+  cli_mode_enabled: (value) =>
+    commands.changeCliModeEnabledSetting(value as boolean),
+  cli_mode_llm_assist: (value) =>
+    commands.changeCliModeLlmAssistSetting(value as boolean),
+  cli_mode_deny_patterns: (value) =>
+    commands.updateCliModeDenyPatterns(value as string[]),
   post_process_selected_prompt_id: (value) =>
     commands.setPostProcessSelectedPrompt(value as string),
   mute_while_recording: (value) =>
     commands.changeMuteWhileRecordingSetting(value as boolean),
-  audio_segment_size_seconds: (value) =>
-    commands.changeAudioSegmentSizeSecondsSetting(value as number),
+  segment_on_silence_ms: (value) =>
+    commands.changeSegmentOnSilenceMsSetting(value as number),
   append_trailing_space: (value) =>
     commands.changeAppendTrailingSpaceSetting(value as boolean),
   at_file_expansion_enabled: (value) =>
@@ -73,6 +116,10 @@ export const settingUpdaters: {
     commands.updateJargonCustomTerms(value as string[]),
   jargon_custom_corrections: (value) =>
     commands.updateJargonCustomCorrections(value as any),
+  jargon_sources: (value) =>
+    commands.updateJargonSources(value as JargonSource[]),
+  workspace_jargon_enabled: (value) =>
+    commands.updateWorkspaceJargonEnabledSetting(value as boolean),
   domain_selector_enabled: (value) =>
     commands.updateDomainSelectorEnabledSetting(value as boolean),
   domain_selector_timeout_ms: (value) =>
@@ -87,6 +134,20 @@ export const settingUpdaters: {
     commands.updateDomainSelectorBlendManualProfilesSetting(
       value as boolean,
     ),
+  domain_selector_learned_weights: (value) =>
+    commands.updateDomainSelectorLearnedWeightsSetting(value as boolean),
+  intent_router_enabled: (value) =>
+    commands.updateIntentRouterEnabledSetting(value as boolean),
+  intent_router_timeout_ms: (value) =>
+    commands.updateIntentRouterTimeoutMsSetting(value as number),
+  intent_router_min_confidence: (value) =>
+    commands.updateIntentRouterMinConfidenceSetting(value as number),
+  reranker_enabled: (value) =>
+    commands.updateRerankerEnabledSetting(value as boolean),
+  reranker_second_prompt_id: (value) =>
+    commands.changeRerankerSecondPromptSetting(value as string | null),
+  reranker_timeout_ms: (value) =>
+    commands.updateRerankerTimeoutMsSetting(value as number),
   log_level: (value) => commands.setLogLevel(value as any),
   app_language: (value) => commands.changeAppLanguageSetting(value as string),
   experimental_enabled: (value) =>